@@ -19,21 +19,26 @@ pub fn generate_enum_write(
     };
 
     let pack = if parsed.catch_all.is_some() {
-        let match_arms = parsed.variants.clone().into_iter().map(|variant| {
-            let value =
-                proc_macro2::TokenStream::from_str(&variant.discriminant.to_string()).unwrap();
-            let variant_name = variant.name;
-
-            if variant.catch_all {
-                quote! {
-                    #name::#variant_name (value) => { *value }
-                }
-            } else {
-                quote! {
-                    #name::#variant_name => { #value }
+        let match_arms = parsed
+            .variants
+            .clone()
+            .into_iter()
+            .filter(|variant| !variant.is_alternative)
+            .map(|variant| {
+                let value =
+                    proc_macro2::TokenStream::from_str(&variant.discriminant.to_string()).unwrap();
+                let variant_name = variant.name;
+
+                if variant.catch_all {
+                    quote! {
+                        #name::#variant_name (value) => { *value }
+                    }
+                } else {
+                    quote! {
+                        #name::#variant_name => { #value }
+                    }
                 }
-            }
-        });
+            });
 
         quote! {
             let value: #repr_type = match self {
@@ -199,21 +204,27 @@ pub fn generate_enum_read(parsed: EnumMeta, input: &DeriveInput) -> proc_macro2:
     };
 
     let into_primitive_impl = if parsed.catch_all.is_some() {
-        let match_arms_from = parsed.variants.clone().into_iter().map(|variant| {
-            let value =
-                proc_macro2::TokenStream::from_str(&variant.discriminant.to_string()).unwrap();
-            let variant_name = variant.name;
-
-            if variant.catch_all {
-                quote! {
-                    #name::#variant_name (value) => { value }
-                }
-            } else {
-                quote! {
-                    #name::#variant_name => { #value }
+        // Alternatives map back to the primary discriminant of their variant, so are skipped here.
+        let match_arms_from = parsed
+            .variants
+            .clone()
+            .into_iter()
+            .filter(|variant| !variant.is_alternative)
+            .map(|variant| {
+                let value =
+                    proc_macro2::TokenStream::from_str(&variant.discriminant.to_string()).unwrap();
+                let variant_name = variant.name;
+
+                if variant.catch_all {
+                    quote! {
+                        #name::#variant_name (value) => { value }
+                    }
+                } else {
+                    quote! {
+                        #name::#variant_name => { #value }
+                    }
                 }
-            }
-        });
+            });
 
         quote! {
             impl From<#name> for #repr_type {
@@ -24,6 +24,8 @@ pub struct VariantMeta {
     pub default: bool,
     #[allow(unused)]
     pub alternatives: Vec<i128>,
+    /// This record is an alternative discriminant of another variant.
+    pub is_alternative: bool,
 }
 
 pub fn parse_enum(
@@ -110,6 +112,7 @@ pub fn parse_enum(
             catch_all: is_catch_all,
             alternatives: alternatives.clone(),
             default: is_default,
+            is_alternative: false,
         };
 
         if is_catch_all {
@@ -145,6 +148,7 @@ pub fn parse_enum(
                 alternatives: Vec::new(),
                 default: false,
                 catch_all: false,
+                is_alternative: true,
             };
 
             variants.push(alt);
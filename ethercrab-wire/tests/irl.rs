@@ -156,6 +156,25 @@ fn enum_default_and_catch_all() {
     }
 }
 
+#[test]
+fn enum_alternatives_and_catch_all() {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+    #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+    #[repr(u16)]
+    pub enum Alternatives {
+        Nop = 0,
+        #[wire(alternatives = [2,3])]
+        DeviceSpecific = 1,
+        #[wire(catch_all)]
+        Other(u16),
+    }
+
+    assert_eq!(Alternatives::from(3u16), Alternatives::DeviceSpecific);
+    assert_eq!(u16::from(Alternatives::DeviceSpecific), 1);
+    assert_eq!(Alternatives::from(0x1000u16), Alternatives::Other(0x1000));
+    assert_eq!(u16::from(Alternatives::Other(0x1000)), 0x1000);
+}
+
 #[test]
 fn enum_default_only() {
     #[derive(Default, Debug, Copy, Clone, ethercrab_wire::EtherCrabWireReadWrite)]
@@ -0,0 +1,76 @@
+//! Print the contents of an SII EEPROM image file, e.g. one produced by the `dump-eeprom` example.
+//!
+//! No network interface is required.
+
+use ethercrab::sii::DecodedSiiImage;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .expect("Provide EEPROM image path as first argument.");
+
+    let image = DecodedSiiImage::from_file(path)?;
+
+    let string = |index: u8| {
+        usize::from(index)
+            .checked_sub(1)
+            .and_then(|index| image.strings.get(index))
+            .map(String::as_str)
+            .unwrap_or("")
+    };
+
+    println!("Identity: {}", image.identity);
+    println!("Station alias: {:#06x}", image.config_area.station_alias);
+    println!("EEPROM size: {} bytes", image.eeprom_size);
+    println!(
+        "Mailbox: {:?}, protocols {:?}",
+        image.mailbox, image.mailbox_protocols
+    );
+
+    if let Some(general) = image.general {
+        println!("Name: {}", string(general.name_string_idx));
+        println!("Order code: {}", string(general.order_string_idx));
+        println!("Group: {}", string(general.group_string_idx));
+    }
+
+    println!("FMMUs: {:?}", image.fmmus);
+
+    for (i, sm) in image.sync_managers.iter().enumerate() {
+        println!(
+            "SM{}: start {:#06x}, length {}, {:?}",
+            i, sm.start_addr, sm.length, sm.usage_type
+        );
+    }
+
+    for (direction, pdos) in [("TxPDO", &image.tx_pdos), ("RxPDO", &image.rx_pdos)] {
+        for pdo in pdos {
+            println!(
+                "{} {:#06x} \"{}\", SM {}",
+                direction,
+                pdo.index,
+                pdo.name.as_deref().unwrap_or(""),
+                pdo.sync_manager
+            );
+
+            for entry in pdo.entries.iter() {
+                println!(
+                    "    {:#06x}:{:02x} \"{}\", {} bits",
+                    entry.entry.index,
+                    entry.entry.sub_index,
+                    entry.name.as_deref().unwrap_or(""),
+                    entry.entry.bit_len
+                );
+            }
+        }
+    }
+
+    for op_mode in image.dc_op_modes.iter() {
+        println!(
+            "DC op mode \"{}\": AssignActivate {:#06x}",
+            string(op_mode.name_string_idx),
+            op_mode.assign_activate
+        );
+    }
+
+    Ok(())
+}
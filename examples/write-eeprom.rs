@@ -0,0 +1,106 @@
+//! Write an entire EEPROM image, e.g. one produced by the `dump-eeprom` example, to a given sub
+//! device.
+//!
+//! Pass `--force` as the fourth argument to write an image whose vendor or product ID does not match
+//! the device.
+//!
+//! **This will overwrite the entire EEPROM of the selected device. Be careful!**
+
+use env_logger::Env;
+use ethercrab::{
+    MainDevice, MainDeviceConfig, PduStorage, Timeouts, error::Error, std::ethercat_now,
+};
+
+/// Maximum number of SubDevices that can be stored. This must be a power of 2 greater than 1.
+const MAX_SUBDEVICES: usize = 16;
+/// Maximum PDU data payload size - set this to the max PDI size or higher.
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+/// Maximum number of EtherCAT frames that can be in flight at any one time.
+const MAX_FRAMES: usize = 16;
+/// Maximum total PDI length.
+const PDI_LEN: usize = 64;
+
+static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+fn main() -> Result<(), Error> {
+    smol::block_on(async {
+        env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
+
+        let interface = std::env::args()
+            .nth(1)
+            .expect("Provide network interface as first argument.");
+
+        let index: u16 = std::env::args()
+            .nth(2)
+            .expect("Provide device index (starting from zero) as second argument.")
+            .parse()
+            .expect("Invalid index: must be a number");
+
+        let image_path = std::env::args()
+            .nth(3)
+            .expect("Provide path to EEPROM image as third argument.");
+
+        let force = std::env::args().nth(4).is_some_and(|arg| arg == "--force");
+
+        let image = std::fs::read(&image_path).expect("Could not read EEPROM image");
+
+        log::info!(
+            "Starting EEPROM write tool, interface {}, device index {}, image {} ({} bytes)",
+            interface,
+            index,
+            image_path,
+            image.len()
+        );
+
+        let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+        let maindevice = MainDevice::new(
+            pdu_loop,
+            Timeouts::default(),
+            MainDeviceConfig {
+                dc_static_sync_iterations: 0,
+                ..MainDeviceConfig::default()
+            },
+        );
+
+        #[cfg(target_os = "windows")]
+        std::thread::spawn(move || {
+            ethercrab::std::tx_rx_task_blocking(
+                &interface,
+                tx,
+                rx,
+                ethercrab::std::TxRxTaskConfig { spinloop: false },
+            )
+            .expect("TX/RX task")
+        });
+        #[cfg(not(target_os = "windows"))]
+        smol::spawn(ethercrab::std::tx_rx_task(&interface, tx, rx).expect("spawn TX/RX task"))
+            .detach();
+
+        let group = maindevice
+            .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(ethercat_now)
+            .await
+            .expect("Init");
+
+        let subdevice = group
+            .subdevice(&maindevice, usize::from(index))
+            .expect("Could not find device for given index");
+
+        log::info!(
+            "Writing EEPROM for device index {}: {:#06x} {} {}...",
+            index,
+            subdevice.configured_address(),
+            subdevice.name(),
+            subdevice.identity()
+        );
+
+        subdevice
+            .eeprom_write_image_dangerously(&maindevice, &image, force)
+            .await?;
+
+        log::info!("Done, wrote and verified {} bytes", image.len());
+        log::info!("Power cycle the device for changes to take effect");
+
+        Ok(())
+    })
+}
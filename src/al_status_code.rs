@@ -3,6 +3,7 @@
 /// Defined in ETG1000.6 Table 11.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireRead)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u16)]
 pub enum AlStatusCode {
     /// No error
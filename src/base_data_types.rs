@@ -6,6 +6,7 @@
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PrimitiveDataType {
+    /// Unknown or unsupported data type.
     Unknown = 0x00,
 
     /// Boolean, bit, on or off.
@@ -107,3 +108,21 @@ pub enum PrimitiveDataType {
     /// LREAL 64 Long float
     F64 = 0x11,
 }
+
+impl PrimitiveDataType {
+    /// Whether this is a signed integer type.
+    #[cfg(feature = "pdi-symbols")]
+    pub(crate) const fn is_signed(self) -> bool {
+        matches!(
+            self,
+            Self::I8
+                | Self::I16
+                | Self::I24
+                | Self::I32
+                | Self::I40
+                | Self::I48
+                | Self::I56
+                | Self::I64
+        )
+    }
+}
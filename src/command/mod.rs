@@ -11,14 +11,23 @@ pub use writes::{WrappedWrite, Writes};
 const NOP: u8 = 0x00;
 const APRD: u8 = 0x01;
 const FPRD: u8 = 0x04;
-const BRD: u8 = 0x07;
+pub(crate) const BRD: u8 = 0x07;
 const LRD: u8 = 0x0A;
 const BWR: u8 = 0x08;
+pub(crate) const BRW: u8 = 0x09;
 const APWR: u8 = 0x02;
 const FPWR: u8 = 0x05;
 const FRMW: u8 = 0x0E;
 const LWR: u8 = 0x0B;
 const LRW: u8 = 0x0c;
+// Not sent by EtherCrab, but may be present in frames received from the network
+const APRW: u8 = 0x03;
+const ARMW: u8 = 0x0D;
+
+/// Whether a raw command code addresses a SubDevice by its position in the network.
+pub(crate) const fn is_position_addressed(command_code: u8) -> bool {
+    matches!(command_code, APRD | APWR | APRW | ARMW)
+}
 
 /// PDU command.
 ///
@@ -111,6 +111,84 @@ async fn write_dc_parameters(
     Ok(())
 }
 
+/// Restore the DC system time offset and propagation delay of a SubDevice that has been reset,
+/// aligning its system time with the reference SubDevice, then run static drift compensation.
+///
+/// The propagation delay measured during init is reused, so the network topology must not have
+/// changed.
+pub(crate) async fn restore_dc_parameters(
+    maindevice: &MainDevice<'_>,
+    subdevice: &SubDevice,
+    reference: u16,
+) -> Result<(), Error> {
+    // Latch the local time the same frame is received at into every SubDevice
+    Command::bwr(RegisterAddress::DcTimePort0.into())
+        .ignore_wkc()
+        .send(maindevice, 0u32)
+        .await?;
+
+    // A reset SubDevice has a zero offset, so its receive time is in its local time
+    let receive_time = Command::fprd(
+        subdevice.configured_address(),
+        RegisterAddress::DcReceiveTime.into(),
+    )
+    .receive::<u64>(maindevice)
+    .await?;
+
+    let reference_receive_time = Command::fprd(reference, RegisterAddress::DcReceiveTime.into())
+        .receive::<u64>(maindevice)
+        .await?;
+
+    let reference_offset = Command::fprd(reference, RegisterAddress::DcSystemTimeOffset.into())
+        .receive::<u64>(maindevice)
+        .await?;
+
+    let reference_delay = Command::fprd(
+        reference,
+        RegisterAddress::DcSystemTimeTransmissionDelay.into(),
+    )
+    .receive::<u32>(maindevice)
+    .await?;
+
+    // The frame reaches this SubDevice later than the reference by the difference between their
+    // propagation delays
+    let system_time = reference_receive_time
+        .wrapping_add(reference_offset)
+        .wrapping_add(u64::from(subdevice.propagation_delay))
+        .wrapping_sub(u64::from(reference_delay));
+
+    let system_time_offset = system_time.wrapping_sub(receive_time) as i64;
+
+    fmt::trace!(
+        "Restoring SubDevice {:#06x} system time offset to {} ns (reference receive time {} ns, receive time {} ns)",
+        subdevice.configured_address(),
+        system_time_offset,
+        reference_receive_time.wrapping_add(reference_offset),
+        receive_time
+    );
+
+    Command::fpwr(
+        subdevice.configured_address(),
+        RegisterAddress::DcSystemTimeOffset.into(),
+    )
+    .send(maindevice, system_time_offset)
+    .await?;
+
+    Command::fpwr(
+        subdevice.configured_address(),
+        RegisterAddress::DcSystemTimeTransmissionDelay.into(),
+    )
+    .send(maindevice, subdevice.propagation_delay)
+    .await?;
+
+    run_dc_static_sync(
+        maindevice,
+        reference,
+        maindevice.config.dc_static_sync_iterations,
+    )
+    .await
+}
+
 /// Find the SubDevice parent device in the list of SubDevices before it in the linear topology.
 ///
 /// # Implementation detail
@@ -468,22 +546,18 @@ pub(crate) async fn configure_dc<'subdevices>(
 /// alignment at this stage isn't critical.
 pub(crate) async fn run_dc_static_sync(
     maindevice: &MainDevice<'_>,
-    dc_reference_subdevice: &SubDevice,
+    dc_reference_address: u16,
     iterations: u32,
 ) -> Result<(), Error> {
     fmt::debug!(
-        "Performing static drift compensation using SubDevice {:#06x} {} as reference. This can take some time...",
-        dc_reference_subdevice.configured_address(),
-        dc_reference_subdevice.name
+        "Performing static drift compensation using SubDevice {:#06x} as reference. This can take some time...",
+        dc_reference_address,
     );
 
     for _ in 0..iterations {
-        Command::frmw(
-            dc_reference_subdevice.configured_address(),
-            RegisterAddress::DcSystemTime.into(),
-        )
-        .receive_wkc::<u64>(maindevice)
-        .await?;
+        Command::frmw(dc_reference_address, RegisterAddress::DcSystemTime.into())
+            .receive_wkc::<u64>(maindevice)
+            .await?;
     }
 
     fmt::debug!("Static drift compensation complete");
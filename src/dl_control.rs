@@ -0,0 +1,106 @@
+/// Loop control setting for a single SubDevice port.
+///
+/// A closed port forwards frames straight back to the port they came from, cutting off anything
+/// connected to it. Defined in ETG1000.4 Table 32 - DL control.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[wire(bits = 2)]
+#[repr(u8)]
+pub enum PortLoop {
+    /// Close the port if it has no link, and open it when a link is detected.
+    #[default]
+    Auto = 0x00,
+    /// Close the port if it has no link. Once a link is detected the port stays closed until it is
+    /// explicitly opened by writing [`PortLoop::AutoClose`] again.
+    AutoClose = 0x01,
+    /// Always open, regardless of link state.
+    Open = 0x02,
+    /// Always closed, regardless of link state.
+    Closed = 0x03,
+}
+
+/// DL control register.
+///
+/// Defined in ETG1000.4 Table 32 - DL control.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 4)]
+pub struct DlControl {
+    /// If `true`, non-EtherCAT frames are destroyed instead of forwarded.
+    #[wire(bits = 1)]
+    pub forwarding_rule: bool,
+    /// If `true`, loop settings are reverted after about a second.
+    #[wire(bits = 1, post_skip = 6)]
+    pub temporary_loop: bool,
+    #[wire(bits = 2)]
+    pub loop_port0: PortLoop,
+    #[wire(bits = 2)]
+    pub loop_port1: PortLoop,
+    #[wire(bits = 2)]
+    pub loop_port2: PortLoop,
+    #[wire(bits = 2)]
+    pub loop_port3: PortLoop,
+    #[wire(bits = 3)]
+    pub rx_fifo_size: u8,
+    /// If `true`, EBUS ports use low jitter mode.
+    #[wire(bits = 1, post_skip = 2)]
+    pub ebus_low_jitter: bool,
+    /// If `true`, EBUS ports signal a lost link to the remote port.
+    #[wire(bits = 1, post_skip = 1)]
+    pub ebus_remote_link_down_signalling: bool,
+    #[wire(bits = 1, post_skip = 7)]
+    pub station_alias: bool,
+}
+
+impl DlControl {
+    /// Loop setting of each port, indexed by port number.
+    pub fn port_loops(&self) -> [PortLoop; 4] {
+        [
+            self.loop_port0,
+            self.loop_port1,
+            self.loop_port2,
+            self.loop_port3,
+        ]
+    }
+
+    /// Set the loop setting for the given port number. Port numbers above 3 are ignored.
+    pub fn set_port_loop(&mut self, port: u8, setting: PortLoop) {
+        match port {
+            0 => self.loop_port0 = setting,
+            1 => self.loop_port1 = setting,
+            2 => self.loop_port2 = setting,
+            3 => self.loop_port3 = setting,
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireWrite};
+
+    #[test]
+    fn close_port1() {
+        // Forwarding rule set, all ports auto, 8 byte RX FIFO, EBUS low jitter and remote link down
+        // signalling
+        let raw = [0x01, 0x00, 0x4f, 0x00];
+
+        let mut control = DlControl::unpack_from_slice(&raw).unwrap();
+
+        assert_eq!(control.port_loops(), [PortLoop::Auto; 4]);
+        assert!(control.ebus_low_jitter);
+        assert!(control.ebus_remote_link_down_signalling);
+
+        control.set_port_loop(1, PortLoop::Closed);
+        control.set_port_loop(3, PortLoop::Open);
+
+        let mut buf = [0u8; 4];
+
+        assert_eq!(
+            control.pack_to_slice(&mut buf).unwrap(),
+            &[0x01, 0b1000_1100, 0x4f, 0x00]
+        );
+    }
+}
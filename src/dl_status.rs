@@ -52,6 +52,30 @@ pub struct DlStatus {
     pub signal_port3: bool,
 }
 
+impl DlStatus {
+    /// Whether the given port number has a physical link.
+    pub fn link(&self, port: u8) -> bool {
+        match port {
+            0 => self.link_port0,
+            1 => self.link_port1,
+            2 => self.link_port2,
+            3 => self.link_port3,
+            _ => false,
+        }
+    }
+
+    /// Whether the given port number is closed, i.e. forwarding frames back to itself.
+    pub fn loopback(&self, port: u8) -> bool {
+        match port {
+            0 => self.loopback_port0,
+            1 => self.loopback_port1,
+            2 => self.loopback_port2,
+            3 => self.loopback_port3,
+            _ => true,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,328 @@
+//! Caches for SubDevice EEPROM contents.
+
+use crate::{SubDeviceIdentity, fmt};
+
+/// A store of SubDevice EEPROM images, used to avoid slow SII reads on every startup.
+///
+/// Images are keyed by the SubDevice's complete identity, including its revision and serial
+/// number. EtherCrab copies a SubDevice's EEPROM into the cache the first time it is seen, then
+/// serves later reads from the cache. SubDevices reporting a serial number of zero are never
+/// cached, and the configuration area in words `0x0000`-`0x0007` is always read from the
+/// SubDevice. Pass an implementation of this trait to
+/// [`MainDevice::with_eeprom_cache`](crate::MainDevice::with_eeprom_cache) to enable caching.
+///
+/// EtherCrab provides [`MemoryEepromCache`] and, with the `std` feature,
+/// [`FileEepromCache`](crate::FileEepromCache).
+pub trait EepromCache: Sync {
+    /// Copy `buf.len()` bytes of the cached image for `identity`, starting at byte `offset`, into
+    /// `buf`.
+    ///
+    /// Returns `false` if no image is cached for `identity`, or if the requested range is not
+    /// fully contained in the cached image.
+    fn read(&self, identity: &SubDeviceIdentity, offset: usize, buf: &mut [u8]) -> bool;
+
+    /// Store `data` at byte `offset` in the image for `identity`, creating the image if it does
+    /// not yet exist.
+    ///
+    /// Images are written in order from offset zero. Returns `false` if the data could not be
+    /// stored, in which case EtherCrab will stop populating the cache for this SubDevice.
+    fn write(&self, identity: &SubDeviceIdentity, offset: usize, data: &[u8]) -> bool;
+
+    /// Called once an image has been completely written, e.g. to persist it to storage.
+    fn commit(&self, _identity: &SubDeviceIdentity) {}
+
+    /// Remove the image for `identity`, e.g. because it is stale or the EEPROM was written to.
+    fn remove(&self, identity: &SubDeviceIdentity);
+}
+
+impl core::fmt::Debug for dyn EepromCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("EepromCache")
+    }
+}
+
+#[derive(Debug)]
+struct CachedImage<const LEN: usize> {
+    identity: SubDeviceIdentity,
+    len: usize,
+    data: [u8; LEN],
+}
+
+/// An in-memory [`EepromCache`] holding up to `N` images of up to `LEN` bytes each.
+///
+/// Images larger than `LEN` bytes are not cached.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::{MainDevice, MainDeviceConfig, MemoryEepromCache, PduStorage, Timeouts};
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+/// // Up to 32 SubDevices with EEPROM images of at most 2KiB each
+/// static EEPROM_CACHE: MemoryEepromCache<32, 2048> = MemoryEepromCache::new();
+///
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default())
+///     .with_eeprom_cache(&EEPROM_CACHE, true);
+/// ```
+#[derive(Debug)]
+pub struct MemoryEepromCache<const N: usize, const LEN: usize> {
+    images: spin::RwLock<heapless::Vec<CachedImage<LEN>, N>>,
+}
+
+impl<const N: usize, const LEN: usize> Default for MemoryEepromCache<N, LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const LEN: usize> MemoryEepromCache<N, LEN> {
+    /// Create a new, empty cache.
+    pub const fn new() -> Self {
+        Self {
+            images: spin::RwLock::new(heapless::Vec::new()),
+        }
+    }
+}
+
+impl<const N: usize, const LEN: usize> EepromCache for MemoryEepromCache<N, LEN> {
+    fn read(&self, identity: &SubDeviceIdentity, offset: usize, buf: &mut [u8]) -> bool {
+        let images = self.images.read();
+
+        let Some(image) = images.iter().find(|image| image.identity == *identity) else {
+            return false;
+        };
+
+        let end = offset + buf.len();
+
+        if end > image.len {
+            return false;
+        }
+
+        buf.copy_from_slice(&image.data[offset..end]);
+
+        true
+    }
+
+    fn write(&self, identity: &SubDeviceIdentity, offset: usize, data: &[u8]) -> bool {
+        let end = offset + data.len();
+
+        if end > LEN {
+            fmt::debug!(
+                "EEPROM image for {} is too large to cache (max {} bytes)",
+                identity,
+                LEN
+            );
+
+            self.remove(identity);
+
+            return false;
+        }
+
+        let mut images = self.images.write();
+
+        let image = match images.iter().position(|image| image.identity == *identity) {
+            Some(idx) => &mut images[idx],
+            None => {
+                let new = CachedImage {
+                    identity: *identity,
+                    len: 0,
+                    data: [0u8; LEN],
+                };
+
+                if images.push(new).is_err() {
+                    fmt::debug!("EEPROM cache is full, not caching {}", identity);
+
+                    return false;
+                }
+
+                fmt::unwrap_opt!(images.last_mut())
+            }
+        };
+
+        image.data[offset..end].copy_from_slice(data);
+        image.len = image.len.max(end);
+
+        true
+    }
+
+    fn remove(&self, identity: &SubDeviceIdentity) {
+        self.images
+            .write()
+            .retain(|image| image.identity != *identity);
+    }
+}
+
+/// An [`EepromCache`] that persists each image to a file in the given directory.
+///
+/// Images are loaded from disk on first use and kept in memory afterwards. Files are named after
+/// the SubDevice's vendor ID, product ID, revision and serial number.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FileEepromCache {
+    dir: std::path::PathBuf,
+    images: std::sync::Mutex<std::collections::HashMap<SubDeviceIdentity, Vec<u8>>>,
+}
+
+#[cfg(feature = "std")]
+impl FileEepromCache {
+    /// Create a new cache storing images in `dir`. The directory is created when the first image
+    /// is stored if it does not already exist.
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            images: std::sync::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+
+    fn path(&self, identity: &SubDeviceIdentity) -> std::path::PathBuf {
+        self.dir.join(format!(
+            "{:08x}-{:08x}-{:08x}-{:08x}.bin",
+            identity.vendor_id, identity.product_id, identity.revision, identity.serial
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl EepromCache for FileEepromCache {
+    fn read(&self, identity: &SubDeviceIdentity, offset: usize, buf: &mut [u8]) -> bool {
+        let mut images = self.images.lock().expect("EEPROM cache lock poisoned");
+
+        if !images.contains_key(identity) {
+            match std::fs::read(self.path(identity)) {
+                Ok(image) => {
+                    images.insert(*identity, image);
+                }
+                Err(_) => return false,
+            }
+        }
+
+        let Some(chunk) = images
+            .get(identity)
+            .and_then(|image| image.get(offset..offset + buf.len()))
+        else {
+            return false;
+        };
+
+        buf.copy_from_slice(chunk);
+
+        true
+    }
+
+    fn write(&self, identity: &SubDeviceIdentity, offset: usize, data: &[u8]) -> bool {
+        let mut images = self.images.lock().expect("EEPROM cache lock poisoned");
+
+        let image = images.entry(*identity).or_default();
+
+        let end = offset + data.len();
+
+        if image.len() < end {
+            image.resize(end, 0);
+        }
+
+        image[offset..end].copy_from_slice(data);
+
+        true
+    }
+
+    fn commit(&self, identity: &SubDeviceIdentity) {
+        let images = self.images.lock().expect("EEPROM cache lock poisoned");
+
+        let Some(image) = images.get(identity) else {
+            return;
+        };
+
+        let res = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.path(identity), image));
+
+        if let Err(e) = res {
+            fmt::warn!("Failed to save EEPROM cache for {}: {}", identity, e);
+        }
+    }
+
+    fn remove(&self, identity: &SubDeviceIdentity) {
+        self.images
+            .lock()
+            .expect("EEPROM cache lock poisoned")
+            .remove(identity);
+
+        let _ = std::fs::remove_file(self.path(identity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: SubDeviceIdentity = SubDeviceIdentity {
+        vendor_id: 2,
+        product_id: 0x0b0c3052,
+        revision: 0x00110000,
+        serial: 0,
+    };
+
+    #[test]
+    fn memory_cache() {
+        let cache = MemoryEepromCache::<2, 16>::new();
+
+        let mut buf = [0u8; 4];
+
+        assert!(!cache.read(&IDENTITY, 0, &mut buf));
+
+        assert!(cache.write(&IDENTITY, 0, &[1, 2, 3, 4, 5, 6, 7, 8]));
+        assert!(cache.write(&IDENTITY, 8, &[9, 10]));
+
+        assert!(cache.read(&IDENTITY, 6, &mut buf));
+        assert_eq!(buf, [7, 8, 9, 10]);
+
+        // Past the end of the cached data
+        assert!(!cache.read(&IDENTITY, 8, &mut buf));
+
+        // Too large
+        assert!(!cache.write(&IDENTITY, 12, &[0; 8]));
+        assert!(!cache.read(&IDENTITY, 0, &mut buf));
+    }
+
+    #[test]
+    fn memory_cache_full() {
+        let cache = MemoryEepromCache::<1, 16>::new();
+
+        let other = SubDeviceIdentity {
+            serial: 1,
+            ..IDENTITY
+        };
+
+        assert!(cache.write(&IDENTITY, 0, &[1, 2]));
+        assert!(!cache.write(&other, 0, &[1, 2]));
+
+        cache.remove(&IDENTITY);
+
+        assert!(cache.write(&other, 0, &[1, 2]));
+    }
+
+    #[test]
+    fn file_cache() {
+        let dir =
+            std::env::temp_dir().join(format!("ethercrab-eeprom-cache-{}", std::process::id()));
+
+        let cache = FileEepromCache::new(&dir);
+
+        assert!(cache.write(&IDENTITY, 0, &[1, 2, 3, 4]));
+        cache.commit(&IDENTITY);
+
+        // New instance loads image from disk
+        let cache = FileEepromCache::new(&dir);
+
+        let mut buf = [0u8; 2];
+
+        assert!(cache.read(&IDENTITY, 2, &mut buf));
+        assert_eq!(buf, [3, 4]);
+
+        cache.remove(&IDENTITY);
+
+        assert!(!FileEepromCache::new(&dir).read(&IDENTITY, 0, &mut buf));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
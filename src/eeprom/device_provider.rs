@@ -1,7 +1,10 @@
+use core::ops::Deref;
+
 use crate::{
-    Command, MainDevice,
+    Command, MainDevice, SubDeviceIdentity,
     eeprom::{
         EepromDataProvider,
+        cache::EepromCache,
         types::{SiiControl, SiiRequest},
     },
     error::{EepromError, Error},
@@ -16,11 +19,22 @@ use crate::{
 /// SII EEPROM is WORD-addressed.
 pub(crate) const SII_FIRST_CATEGORY_START: u16 = 0x0040u16;
 
+/// Size in bytes of chunks read from an [`EepromCache`].
+const CACHED_CHUNK_LEN: usize = 8;
+
+/// Reads starting below this word are never served from an [`EepromCache`].
+///
+/// Words `0x0000`-`0x0007` hold the configuration area, including the station alias, which can
+/// differ between SubDevices sharing a cached image.
+const FIRST_CACHED_WORD: u16 = 0x0008;
+
 /// EEPROM data provider that communicates with a physical sub device.
 #[derive(Clone)]
 pub struct DeviceEeprom<'subdevice> {
     maindevice: &'subdevice MainDevice<'subdevice>,
     configured_address: u16,
+    /// Cache to serve reads from, and the identity of this SubDevice in the cache.
+    cache: Option<(&'static dyn EepromCache, SubDeviceIdentity)>,
 }
 
 impl<'subdevice> DeviceEeprom<'subdevice> {
@@ -29,6 +43,42 @@ impl<'subdevice> DeviceEeprom<'subdevice> {
         Self {
             maindevice,
             configured_address,
+            cache: None,
+        }
+    }
+
+    /// Serve reads from the given cache where possible, falling back to the SubDevice's EEPROM for
+    /// any data not held in the cache and for the configuration area.
+    pub fn with_cache(
+        mut self,
+        cache: &'static dyn EepromCache,
+        identity: SubDeviceIdentity,
+    ) -> Self {
+        self.cache = Some((cache, identity));
+
+        self
+    }
+
+    async fn clear_device_errors(&self) -> Result<(), Error> {
+        let status = Command::fprd(self.configured_address, RegisterAddress::SiiControl.into())
+            .receive::<SiiControl>(self.maindevice)
+            .await?;
+
+        // Clear errors
+        let status = if status.has_error() {
+            fmt::trace!("Resetting EEPROM error flags");
+
+            Command::fpwr(self.configured_address, RegisterAddress::SiiControl.into())
+                .send_receive(self.maindevice, status.error_reset())
+                .await?
+        } else {
+            status
+        };
+
+        if status.has_error() {
+            Err(Error::Eeprom(EepromError::ClearErrors))
+        } else {
+            Ok(())
         }
     }
 
@@ -59,6 +109,18 @@ impl EepromDataProvider for DeviceEeprom<'_> {
         &mut self,
         start_word: u16,
     ) -> Result<impl core::ops::Deref<Target = [u8]>, Error> {
+        if let Some((cache, identity)) = self.cache.filter(|_| start_word >= FIRST_CACHED_WORD) {
+            let mut buf = [0u8; CACHED_CHUNK_LEN];
+
+            if cache.read(&identity, usize::from(start_word) * 2, &mut buf) {
+                return Ok(Chunk::Cached(buf));
+            }
+
+            // Errors are not cleared before cached reads, so make sure they're cleared before
+            // falling back to the device.
+            self.clear_device_errors().await?;
+        }
+
         Command::fpwr(self.configured_address, RegisterAddress::SiiControl.into())
             .send(self.maindevice, SiiRequest::read(start_word))
             .await?;
@@ -68,6 +130,7 @@ impl EepromDataProvider for DeviceEeprom<'_> {
         Command::fprd(self.configured_address, RegisterAddress::SiiData.into())
             .receive_slice(self.maindevice, status.read_size.chunk_len())
             .await
+            .map(Chunk::Device)
             .inspect(|data| {
                 #[cfg(not(feature = "defmt"))]
                 fmt::trace!("Read addr {:#06x}: {:02x?}", start_word, &data[..]);
@@ -77,12 +140,17 @@ impl EepromDataProvider for DeviceEeprom<'_> {
     }
 
     async fn write_word(&mut self, start_word: u16, data: [u8; 2]) -> Result<(), Error> {
+        // The cached image no longer matches the device
+        if let Some((cache, identity)) = self.cache.take() {
+            cache.remove(&identity);
+        }
+
         // Check if the EEPROM is busy
         self.wait_while_busy().await?;
 
         let mut retry_count = 0;
 
-        loop {
+        let status = loop {
             // Set data to write
             Command::fpwr(self.configured_address, RegisterAddress::SiiData.into())
                 .send(self.maindevice, data)
@@ -102,33 +170,46 @@ impl EepromDataProvider for DeviceEeprom<'_> {
 
                 retry_count += 1;
             } else {
-                break;
+                break status;
             }
+        };
+
+        if status.write_error {
+            fmt::error!(
+                "SubDevice {:#06x} EEPROM write error at word {:#06x}",
+                self.configured_address,
+                start_word
+            );
+
+            return Err(Error::Eeprom(EepromError::Write));
         }
 
         Ok(())
     }
 
     async fn clear_errors(&self) -> Result<(), Error> {
-        let status = Command::fprd(self.configured_address, RegisterAddress::SiiControl.into())
-            .receive::<SiiControl>(self.maindevice)
-            .await?;
+        // Errors are instead cleared if a read misses the cache
+        if self.cache.is_some() {
+            return Ok(());
+        }
 
-        // Clear errors
-        let status = if status.has_error() {
-            fmt::trace!("Resetting EEPROM error flags");
+        self.clear_device_errors().await
+    }
+}
 
-            Command::fpwr(self.configured_address, RegisterAddress::SiiControl.into())
-                .send_receive(self.maindevice, status.error_reset())
-                .await?
-        } else {
-            status
-        };
+/// A chunk of EEPROM data, either read from the device or from a cache.
+enum Chunk<D> {
+    Cached([u8; CACHED_CHUNK_LEN]),
+    Device(D),
+}
 
-        if status.has_error() {
-            Err(Error::Eeprom(EepromError::ClearErrors))
-        } else {
-            Ok(())
+impl<D: Deref<Target = [u8]>> Deref for Chunk<D> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Chunk::Cached(buf) => buf,
+            Chunk::Device(data) => data,
         }
     }
 }
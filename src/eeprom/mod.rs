@@ -6,6 +6,7 @@ use crate::{
 };
 use embedded_io_async::{ErrorType, ReadExactError};
 
+pub mod cache;
 pub mod device_provider;
 pub mod types;
 
@@ -253,10 +253,19 @@ pub enum CategoryType {
     TxPdo = 50,
     RxPdo = 51,
     DistributedClock = 60,
-    // Device specific: 0x1000-0xfffe
+    /// Device specific (`0x1000`-`0xfffe`) or otherwise unknown category.
+    #[wire(catch_all)]
+    Other(u16),
     End = 0xffff,
 }
 
+impl CategoryType {
+    /// Whether this category lies in the vendor specific range `0x1000`-`0xfffe`.
+    pub(crate) fn is_vendor_specific(&self) -> bool {
+        matches!(self, Self::Other(raw) if (0x1000..0xffff).contains(raw))
+    }
+}
+
 /// The type of PDO to search for.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, Copy, Clone)]
@@ -277,15 +286,19 @@ impl From<PdoType> for CategoryType {
     }
 }
 
-/// ETG1000.6 Table 23
+/// FMMU usage, defined in ETG1000.6 Table 23
 #[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireRead)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum FmmuUsage {
+    /// FMMU is not used.
     #[wire(alternatives = [0xff])]
     Unused = 0x00,
+    /// Used for process data outputs from MainDevice.
     Outputs = 0x01,
+    /// Used for process data inputs to MainDevice.
     Inputs = 0x02,
+    /// Used to map the sync manager status into the PDI.
     SyncManagerStatus = 0x03,
 }
 
@@ -301,6 +314,77 @@ pub struct FmmuEx {
     pub sync_manager: u8,
 }
 
+/// An entry in the ETG2010 "SyncUnit" category.
+///
+/// These are the EEPROM equivalent of the `Su` elements in a SubDevice's ESI file, describing how
+/// the process data of each sync unit may be exchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireRead)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 2)]
+pub struct SiiSyncUnit {
+    /// The sync unit must be mapped to its own logical address range.
+    #[wire(bits = 1)]
+    pub separate_su: bool,
+    /// The sync unit must be exchanged in its own frame.
+    #[wire(bits = 1)]
+    pub separate_frame: bool,
+    /// The outputs of this sync unit depend on the state of its inputs.
+    #[wire(bits = 1)]
+    pub depend_on_input_state: bool,
+    /// The sync unit supports repeated frames.
+    #[wire(bits = 1, post_skip = 12)]
+    pub frame_repeat_support: bool,
+}
+
+/// A Distributed Clocks operation mode, defined in ETG2010 "Structure Category DC".
+///
+/// These are the EEPROM equivalent of the `Dc/OpMode` elements in a SubDevice's ESI file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 24)]
+pub struct SiiDcOpMode {
+    /// SYNC0 cycle time in nanoseconds. Zero means the cycle time is derived from the bus cycle
+    /// time using [`sync0_cycle_factor`](SiiDcOpMode::sync0_cycle_factor).
+    #[wire(bytes = 4)]
+    pub cycle_time_sync0: u32,
+    /// SYNC0 shift time in nanoseconds.
+    #[wire(bytes = 4)]
+    pub shift_time_sync0: u32,
+    /// SYNC1 shift time in nanoseconds.
+    #[wire(bytes = 4)]
+    pub shift_time_sync1: u32,
+    /// SYNC1 cycle time factor.
+    #[wire(bytes = 2)]
+    pub sync1_cycle_factor: i16,
+    /// Value to write to the SubDevice's `AssignActivate` register (`0x0980`) for this mode.
+    #[wire(bytes = 2)]
+    pub assign_activate: u16,
+    /// SYNC0 cycle time factor.
+    #[wire(bytes = 2)]
+    pub sync0_cycle_factor: i16,
+    /// Index into EEPROM Strings section for the operation mode name.
+    #[wire(bytes = 1)]
+    pub name_string_idx: u8,
+    /// Index into EEPROM Strings section for the operation mode description.
+    #[wire(bytes = 1, post_skip_bytes = 4)]
+    pub description_string_idx: u8,
+}
+
+/// The location of a raw SII category in a SubDevice's EEPROM.
+///
+/// Use [`SubDevice::eeprom_read_raw`](crate::SubDevice::eeprom_read_raw) to read the category's
+/// contents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SiiCategory {
+    /// Raw category type.
+    pub category: u16,
+    /// Start address of the category data in **words**, excluding the category header.
+    pub start_word: u16,
+    /// Length of the category data in words.
+    pub len_words: u16,
+}
+
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PortStatuses(pub [PortStatus; 4]);
@@ -506,6 +590,7 @@ impl core::fmt::Debug for SyncManager {
 }
 
 bitflags::bitflags! {
+    /// Sync manager enable flags.
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
     pub struct SyncManagerEnable: u8 {
         /// Bit 0: enable.
@@ -544,6 +629,7 @@ impl defmt::Format for SyncManagerEnable {
     }
 }
 
+/// Sync manager usage type.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireRead)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
@@ -570,13 +656,13 @@ pub struct Pdo {
     pub(crate) index: u16,
     #[wire(bytes = 1)]
     pub(crate) num_entries: u8,
-    #[wire(bytes = 1, post_skip_bytes = 4)]
+    #[wire(bytes = 1)]
     pub(crate) sync_manager: u8,
     // #[wire(bytes = 1)]
     // pub(crate) dc_sync: u8,
-    // /// Index into EEPROM Strings section for PDO name.
-    // #[wire(bytes = 1)]
-    // pub(crate) name_string_idx: u8,
+    /// Index into EEPROM Strings section for PDO name.
+    #[wire(bytes = 1, pre_skip_bytes = 1, post_skip_bytes = 2)]
+    pub(crate) name_string_idx: u8,
     // #[wire(bytes = 2)]
     // pub(crate) flags: PdoFlags,
 
@@ -622,16 +708,18 @@ pub struct Pdo {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[wire(bytes = 8)]
 pub struct PdoEntry {
-    // #[wire(bytes = 2)]
-    // pub(crate) index: u16,
-    // #[wire(bytes = 1)]
-    // pub(crate) sub_index: u8,
-    // #[wire(bytes = 1)]
-    // pub(crate) name_string_idx: u8,
-    // // See page 103 of ETG2000
-    // #[wire(bytes = 1)]
-    // pub(crate) data_type: PrimitiveDataType,
-    #[wire(bytes = 1, pre_skip_bytes = 5, post_skip_bytes = 2)]
+    #[wire(bytes = 2)]
+    pub(crate) index: u16,
+    #[wire(bytes = 1)]
+    pub(crate) sub_index: u8,
+    /// Index into EEPROM Strings section for PDO entry name.
+    #[wire(bytes = 1)]
+    pub(crate) name_string_idx: u8,
+    /// See page 103 of ETG2000. Read as a raw value as vendor specific types may be used, which
+    /// are not represented in [`PrimitiveDataType`](crate::PrimitiveDataType).
+    #[wire(bytes = 1)]
+    pub(crate) data_type: u8,
+    #[wire(bytes = 1, post_skip_bytes = 2)]
     pub(crate) data_length_bits: u8,
     // #[wire(bytes = 2)]
     // pub(crate) flags: u16,
@@ -652,7 +740,7 @@ pub struct PdoEntry {
 
 bitflags::bitflags! {
     /// Defined in ETG2010 Table 14 offset 0x0006.
-    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
     pub struct PdoFlags: u16 {
         /// PdoMandatory [Esi:RTxPdo@Mandatory]
         const PDO_MANDATORY = 0x0001;
@@ -717,7 +805,7 @@ bitflags::bitflags! {
     ///
     /// Defined in ETG1000.6 Table 18 or ETG2010 Table 4.
     // NOTE: Is actually a u16, but only the lower byte has any data in it
-    #[derive(Copy, Clone, Default, Debug, PartialEq)]
+    #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
     pub struct MailboxProtocols: u8 {
         /// ADS over EtherCAT (routing and parallel services).
         const AOE = 0x01;
@@ -58,6 +58,15 @@ pub enum Error {
         /// Actual PDI length.
         desired_length: usize,
     },
+    /// A SubDevice's mapped process data is a different length to the type it is being read into
+    /// or written from.
+    PdiLengthMismatch {
+        /// Length of the mapped process data in bytes.
+        pdi_length: usize,
+
+        /// Packed length of the type in bytes.
+        type_length: usize,
+    },
     /// An item in a list could not be found.
     NotFound {
         /// Item kind.
@@ -75,6 +84,11 @@ pub enum Error {
     StateTransition,
     /// An unknown SubDevice was encountered during device discovery/initialisation.
     UnknownSubDevice,
+    /// More than one SubDevice was assigned the same configured station address.
+    DuplicateAddress {
+        /// The duplicated address.
+        configured_address: u16,
+    },
     /// An invalid state was encountered.
     InvalidState {
         /// The desired state.
@@ -133,6 +147,14 @@ impl core::fmt::Display for Error {
                 "Process Data Image is too long ({} bytes), max length is {}",
                 desired_length, max_length
             ),
+            Error::PdiLengthMismatch {
+                pdi_length,
+                type_length,
+            } => write!(
+                f,
+                "mapped process data is {} bytes but type is {} bytes",
+                pdi_length, type_length
+            ),
             Error::NotFound { item, index } => {
                 write!(f, "item kind {:?} not found (index: {:?})", item, index)
             }
@@ -142,6 +164,11 @@ impl core::fmt::Display for Error {
                 f.write_str("a SubDevice failed to transition to a new state")
             }
             Error::UnknownSubDevice => f.write_str("unknown SubDevice"),
+            Error::DuplicateAddress { configured_address } => write!(
+                f,
+                "more than one SubDevice assigned address {:#06x}",
+                configured_address
+            ),
             Error::InvalidState {
                 expected,
                 actual,
@@ -179,6 +206,14 @@ pub enum Item {
     Group,
     /// A SDO sub-index.
     SdoSubIndex,
+    /// A Distributed Clocks operation mode from the SII.
+    DcOpMode,
+    /// A sync unit from the SII.
+    SyncUnit,
+    /// A raw SII category.
+    Category,
+    /// A SubDevice port.
+    Port,
 }
 
 /// Low-level PDU (Process Data Unit) error.
@@ -393,6 +428,24 @@ pub enum EepromError {
     SectionUnderrun,
     /// An attempt to clear errors on the device failed.
     ClearErrors,
+    /// The SubDevice reported an error when writing to its EEPROM.
+    Write,
+    /// Data read back from the EEPROM after a write did not match what was written.
+    Verify {
+        /// Address of the first mismatched word.
+        word: u16,
+    },
+    /// An EEPROM image is too short or is not a whole number of words long.
+    InvalidImage,
+    /// The vendor or product ID in an EEPROM image does not match the SubDevice being written to.
+    IdentityMismatch,
+    /// The configuration area checksum stored in the EEPROM does not match the calculated value.
+    Checksum {
+        /// Checksum stored in word `0x0007` of the EEPROM.
+        stored: u8,
+        /// Checksum calculated from the first 7 words of the EEPROM.
+        calculated: u8,
+    },
 }
 
 impl core::fmt::Display for EepromError {
@@ -403,6 +456,17 @@ impl core::fmt::Display for EepromError {
             EepromError::NoCategory => f.write_str("category not found"),
             EepromError::SectionUnderrun => f.write_str("section too short to fill buffer"),
             EepromError::ClearErrors => f.write_str("clear device errors failed"),
+            EepromError::Write => f.write_str("write failed"),
+            EepromError::Verify { word } => {
+                write!(f, "verification failed at word {:#06x}", word)
+            }
+            EepromError::InvalidImage => f.write_str("invalid image"),
+            EepromError::IdentityMismatch => f.write_str("image identity does not match device"),
+            EepromError::Checksum { stored, calculated } => write!(
+                f,
+                "configuration area checksum {:#04x} does not match calculated {:#04x}",
+                stored, calculated
+            ),
         }
     }
 }
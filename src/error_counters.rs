@@ -0,0 +1,201 @@
+//! ESC error counters, used to diagnose bad cables and connectors.
+
+use crate::error::Error;
+use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireSized};
+
+/// Raw layout of the error counter registers from `0x0300` to `0x0313`.
+///
+/// Defined in ETG1000.4 Table 38 and the Beckhoff ESC datasheet section II, registers
+/// `0x0300:0x0313`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireRead)]
+#[wire(bytes = 20)]
+struct ErrorCounterRegisters {
+    #[wire(bytes = 1)]
+    invalid_frames_port0: u8,
+    #[wire(bytes = 1)]
+    rx_errors_port0: u8,
+    #[wire(bytes = 1)]
+    invalid_frames_port1: u8,
+    #[wire(bytes = 1)]
+    rx_errors_port1: u8,
+    #[wire(bytes = 1)]
+    invalid_frames_port2: u8,
+    #[wire(bytes = 1)]
+    rx_errors_port2: u8,
+    #[wire(bytes = 1)]
+    invalid_frames_port3: u8,
+    #[wire(bytes = 1)]
+    rx_errors_port3: u8,
+    #[wire(bytes = 1)]
+    forwarded_rx_errors_port0: u8,
+    #[wire(bytes = 1)]
+    forwarded_rx_errors_port1: u8,
+    #[wire(bytes = 1)]
+    forwarded_rx_errors_port2: u8,
+    #[wire(bytes = 1)]
+    forwarded_rx_errors_port3: u8,
+    #[wire(bytes = 1)]
+    processing_unit_errors: u8,
+    // Skip PDI error code and a reserved byte
+    #[wire(bytes = 1, post_skip_bytes = 2)]
+    pdi_errors: u8,
+    #[wire(bytes = 1)]
+    lost_links_port0: u8,
+    #[wire(bytes = 1)]
+    lost_links_port1: u8,
+    #[wire(bytes = 1)]
+    lost_links_port2: u8,
+    #[wire(bytes = 1)]
+    lost_links_port3: u8,
+}
+
+/// Error counters for a single port of a SubDevice.
+///
+/// All counters saturate at 255 and are only cleared by
+/// [`SubDeviceRef::reset_error_counters`](crate::SubDeviceRef::reset_error_counters) or a power
+/// cycle.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PortErrorCounters {
+    /// Frames received on this port with an invalid checksum or length.
+    ///
+    /// Any value here usually points to a bad cable or connector on this port.
+    pub invalid_frames: u8,
+    /// Physical layer errors seen by this port, counted whether they happen inside or outside a
+    /// frame.
+    pub rx_errors: u8,
+    /// Frames received on this port that were already marked as invalid by a SubDevice further up
+    /// the line.
+    ///
+    /// Only the first SubDevice to see a corrupted frame counts it as an invalid frame, so a
+    /// forwarded error points to a fault before this SubDevice.
+    pub forwarded_rx_errors: u8,
+    /// How many times the link on this port was lost.
+    ///
+    /// Not every ESC supports this counter, in which case it is always zero.
+    pub lost_links: u8,
+}
+
+impl PortErrorCounters {
+    /// Whether any counter for this port is non-zero.
+    pub fn has_errors(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// A snapshot of the error counters of a single SubDevice.
+///
+/// Read with [`SubDeviceRef::error_counters`](crate::SubDeviceRef::error_counters), or for every
+/// SubDevice in a group at once with
+/// [`SubDeviceGroup::error_counters`](crate::SubDeviceGroup::error_counters).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorCounters {
+    /// The configured address of the SubDevice these counters were read from.
+    pub configured_address: u16,
+    /// Counters for each port, indexed by port number.
+    pub ports: [PortErrorCounters; 4],
+    /// Frames that were corrupted by the SubDevice's EtherCAT processing unit.
+    pub processing_unit_errors: u8,
+    /// Errors on the interface between the ESC and the SubDevice's application controller.
+    pub pdi_errors: u8,
+}
+
+impl ErrorCounters {
+    /// The length in bytes of the error counter registers.
+    pub(crate) const PACKED_LEN: usize = ErrorCounterRegisters::PACKED_LEN;
+
+    /// Parse the error counter registers read from the SubDevice at `configured_address`.
+    pub(crate) fn unpack_from_slice(configured_address: u16, buf: &[u8]) -> Result<Self, Error> {
+        let raw = ErrorCounterRegisters::unpack_from_slice(buf)?;
+
+        Ok(Self {
+            configured_address,
+            ports: [
+                PortErrorCounters {
+                    invalid_frames: raw.invalid_frames_port0,
+                    rx_errors: raw.rx_errors_port0,
+                    forwarded_rx_errors: raw.forwarded_rx_errors_port0,
+                    lost_links: raw.lost_links_port0,
+                },
+                PortErrorCounters {
+                    invalid_frames: raw.invalid_frames_port1,
+                    rx_errors: raw.rx_errors_port1,
+                    forwarded_rx_errors: raw.forwarded_rx_errors_port1,
+                    lost_links: raw.lost_links_port1,
+                },
+                PortErrorCounters {
+                    invalid_frames: raw.invalid_frames_port2,
+                    rx_errors: raw.rx_errors_port2,
+                    forwarded_rx_errors: raw.forwarded_rx_errors_port2,
+                    lost_links: raw.lost_links_port2,
+                },
+                PortErrorCounters {
+                    invalid_frames: raw.invalid_frames_port3,
+                    rx_errors: raw.rx_errors_port3,
+                    forwarded_rx_errors: raw.forwarded_rx_errors_port3,
+                    lost_links: raw.lost_links_port3,
+                },
+            ],
+            processing_unit_errors: raw.processing_unit_errors,
+            pdi_errors: raw.pdi_errors,
+        })
+    }
+
+    /// Whether any counter is non-zero.
+    pub fn has_errors(&self) -> bool {
+        self.ports.iter().any(PortErrorCounters::has_errors)
+            || self.processing_unit_errors > 0
+            || self.pdi_errors > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack() {
+        let buf = [
+            // Invalid frame and RX error counters, ports 0-3
+            0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x00, 0x00, //
+            // Forwarded RX errors, ports 0-3
+            0x05, 0x06, 0x00, 0x00, //
+            // Processing unit, PDI, PDI error code, reserved
+            0x07, 0x08, 0xff, 0xff, //
+            // Lost links, ports 0-3
+            0x09, 0x0a, 0x00, 0x00,
+        ];
+
+        let counters = ErrorCounters::unpack_from_slice(0x1001, &buf).unwrap();
+
+        assert_eq!(
+            counters,
+            ErrorCounters {
+                configured_address: 0x1001,
+                ports: [
+                    PortErrorCounters {
+                        invalid_frames: 1,
+                        rx_errors: 2,
+                        forwarded_rx_errors: 5,
+                        lost_links: 9,
+                    },
+                    PortErrorCounters {
+                        invalid_frames: 3,
+                        rx_errors: 4,
+                        forwarded_rx_errors: 6,
+                        lost_links: 10,
+                    },
+                    PortErrorCounters::default(),
+                    PortErrorCounters::default(),
+                ],
+                processing_unit_errors: 7,
+                pdi_errors: 8,
+            }
+        );
+        assert!(counters.has_errors());
+        assert!(!counters.ports[2].has_errors());
+    }
+}
@@ -0,0 +1,305 @@
+//! Optional SubDevice segments that can be connected and removed at runtime.
+
+use crate::{
+    BASE_SUBDEVICE_ADDRESS, MainDevice, SubDeviceGroup, SubDeviceGroupHandle, SubDeviceRef,
+    al_control::AlControl,
+    command::Command,
+    error::Error,
+    fmt,
+    pdi::PdiOffset,
+    register::RegisterAddress,
+    subdevice::SubDevice,
+    subdevice_group::{Op, PreOp},
+};
+use lock_api::RawRwLock;
+
+/// A change in the connection state of a [`HotConnectGroup`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HotConnectEvent {
+    /// The segment's connection state did not change.
+    Unchanged,
+    /// The segment was detected and its SubDevices are now in OP.
+    Connected,
+    /// The segment was removed from the network.
+    Disconnected,
+}
+
+/// A group for an optional segment of SubDevices that may be connected and removed at runtime,
+/// also known as an ETG hot-connect group.
+///
+/// The segment is identified by the station alias of its first SubDevice, which must also be
+/// passed to [`MainDevice::with_hot_connect`] so [`MainDevice::init`] leaves the segment alone.
+/// Call [`poll`](HotConnectGroup::poll) periodically to detect when the segment is connected or
+/// removed. When connected, the segment's SubDevices are addressed using
+/// [`MainDeviceConfig::addressing`](crate::MainDeviceConfig::addressing), configured into a PDI
+/// region reserved for this group and moved to OP, without affecting any other groups. If a
+/// SubDevice would be given an address already in use on the network, connecting fails with
+/// [`Error::DuplicateAddress`].
+///
+/// Hot-connect segments must be at the end of the network. Distributed Clocks are not configured
+/// for SubDevices in a hot-connect segment.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ethercrab::{
+///     HotConnectEvent, HotConnectGroup, MainDevice, MainDeviceConfig, PduStorage, Timeouts,
+///     error::Error, std::ethercat_now,
+/// };
+///
+/// const TOOL_CHANGER_ALIAS: u16 = 0x0100;
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// # async fn case() -> Result<(), Error> {
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default())
+///     .with_hot_connect(&[TOOL_CHANGER_ALIAS]);
+///
+/// let group = maindevice
+///     .init_single_group::<16, 64>(ethercat_now)
+///     .await?
+///     .into_op(&maindevice)
+///     .await?;
+///
+/// let mut tool = HotConnectGroup::<8, 32>::new(TOOL_CHANGER_ALIAS);
+///
+/// loop {
+///     group.tx_rx(&maindevice).await?;
+///
+///     match tool.poll(&maindevice).await? {
+///         HotConnectEvent::Connected => log::info!("Tool connected"),
+///         HotConnectEvent::Disconnected => log::info!("Tool removed"),
+///         HotConnectEvent::Unchanged => (),
+///     }
+///
+///     if let Some(tool) = tool.group() {
+///         tool.tx_rx(&maindevice).await?;
+///     }
+/// }
+/// # }
+/// ```
+pub struct HotConnectGroup<
+    const MAX_SUBDEVICES: usize,
+    const MAX_PDI: usize,
+    R: RawRwLock = crate::DefaultLock,
+> {
+    alias: u16,
+    /// PDI region reserved for this group the first time the segment is connected.
+    pdi_start: Option<PdiOffset>,
+    group: Option<SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, Op>>,
+}
+
+impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock + Sync>
+    HotConnectGroup<MAX_SUBDEVICES, MAX_PDI, R>
+{
+    /// Create a new, disconnected group for the segment whose first SubDevice has the given
+    /// station alias.
+    pub const fn new(alias: u16) -> Self {
+        Self {
+            alias,
+            pdi_start: None,
+            group: None,
+        }
+    }
+
+    /// The station alias of the first SubDevice in the segment.
+    pub fn alias(&self) -> u16 {
+        self.alias
+    }
+
+    /// Whether the segment is currently connected.
+    pub fn is_connected(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// The segment's SubDevices, if it is connected.
+    pub fn group(&self) -> Option<&SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, Op>> {
+        self.group.as_ref()
+    }
+
+    /// Check whether the segment has been connected or removed, configuring it if it has just
+    /// been connected.
+    ///
+    /// If an error occurs while configuring a newly connected segment, it is left disconnected
+    /// and configuration is retried on the next call.
+    pub async fn poll(&mut self, maindevice: &MainDevice<'_>) -> Result<HotConnectEvent, Error> {
+        if self.group.is_some() {
+            self.check_removed(maindevice).await
+        } else {
+            self.connect(maindevice).await
+        }
+    }
+
+    async fn check_removed(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<HotConnectEvent, Error> {
+        let Some((first_address, len)) = self.group.as_ref().and_then(|group| {
+            group
+                .iter(maindevice)
+                .next()
+                .map(|first| (first.configured_address(), group.len()))
+        }) else {
+            return Ok(HotConnectEvent::Unchanged);
+        };
+
+        // A removed SubDevice does not increment the working counter
+        let present = match Command::fprd(
+            first_address,
+            RegisterAddress::ConfiguredStationAlias.into(),
+        )
+        .receive::<u16>(maindevice)
+        .await
+        {
+            Ok(alias) => alias == self.alias,
+            Err(Error::WorkingCounter { .. }) => false,
+            Err(e) => return Err(e),
+        };
+
+        if present {
+            return Ok(HotConnectEvent::Unchanged);
+        }
+
+        fmt::info!("Hot-connect segment {:#06x} removed", self.alias);
+
+        maindevice.set_num_subdevices((maindevice.num_subdevices() - len) as u16);
+
+        self.group = None;
+
+        Ok(HotConnectEvent::Disconnected)
+    }
+
+    async fn connect(&mut self, maindevice: &MainDevice<'_>) -> Result<HotConnectEvent, Error> {
+        let known = maindevice.num_subdevices() as u16;
+        let present = maindevice.count_subdevices().await?;
+
+        if present <= known {
+            return Ok(HotConnectEvent::Unchanged);
+        }
+
+        let alias = Command::aprd(known, RegisterAddress::ConfiguredStationAlias.into())
+            .receive::<u16>(maindevice)
+            .await?;
+
+        // Another segment may be connected here instead
+        if alias != self.alias {
+            return Ok(HotConnectEvent::Unchanged);
+        }
+
+        fmt::info!(
+            "Hot-connect segment {:#06x} detected at position {}",
+            self.alias,
+            known
+        );
+
+        let group = SubDeviceGroup::<MAX_SUBDEVICES, MAX_PDI, R, PreOp>::default();
+
+        // SubDevices may not be in INIT and may still have their old addresses if the segment was
+        // previously connected. The addresses are cleared so they aren't mistaken for duplicates.
+        for position in known..present {
+            Command::apwr(position, RegisterAddress::AlControl.into())
+                .send(maindevice, AlControl::reset())
+                .await?;
+
+            Command::apwr(position, RegisterAddress::ConfiguredStationAddress.into())
+                .send(maindevice, 0u16)
+                .await?;
+        }
+
+        for position in known..present {
+            let configured_address = BASE_SUBDEVICE_ADDRESS.wrapping_add(position);
+
+            set_address(maindevice, position, configured_address).await?;
+
+            let mut subdevice = SubDevice::new(maindevice, position, configured_address).await?;
+
+            // The next segment starts here
+            if position > known && maindevice.is_hot_connect_alias(subdevice.alias_address) {
+                break;
+            }
+
+            if maindevice.needs_explicit_device_ids() {
+                subdevice.explicit_device_id =
+                    SubDeviceRef::new(maindevice, configured_address, ())
+                        .explicit_device_id()
+                        .await?;
+            }
+
+            let address = maindevice.config.addressing.address(
+                subdevice.identity,
+                subdevice.alias_address,
+                subdevice.explicit_device_id,
+                position,
+            );
+
+            if address != configured_address {
+                fmt::debug!(
+                    "Readdressing SubDevice {:#06x} at position {} to {:#06x}",
+                    configured_address,
+                    position,
+                    address
+                );
+
+                set_address(maindevice, position, address).await?;
+
+                subdevice.configured_address = address;
+            }
+
+            // SAFETY: No references to the group's SubDevices are held.
+            unsafe { group.push(subdevice)? };
+        }
+
+        let pdi_start = *self
+            .pdi_start
+            .get_or_insert_with(|| maindevice.reserve_pdi(MAX_PDI));
+
+        group
+            .as_ref()
+            .into_pre_op(pdi_start, maindevice, true)
+            .await?;
+
+        let group = group.into_op(maindevice).await?;
+
+        maindevice.set_num_subdevices(known + group.len() as u16);
+
+        self.group = Some(group);
+
+        Ok(HotConnectEvent::Connected)
+    }
+}
+
+/// Give the SubDevice at `position` a configured address, checking no other SubDevice already has
+/// it.
+async fn set_address(
+    maindevice: &MainDevice<'_>,
+    position: u16,
+    configured_address: u16,
+) -> Result<(), Error> {
+    if maindevice.address_in_use(configured_address).await? {
+        fmt::error!(
+            "More than one SubDevice assigned address {:#06x}",
+            configured_address
+        );
+
+        return Err(Error::DuplicateAddress { configured_address });
+    }
+
+    Command::apwr(position, RegisterAddress::ConfiguredStationAddress.into())
+        .send(maindevice, configured_address)
+        .await
+}
+
+impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock> core::fmt::Debug
+    for HotConnectGroup<MAX_SUBDEVICES, MAX_PDI, R>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HotConnectGroup")
+            .field("alias", &self.alias)
+            .field("pdi_start", &self.pdi_start)
+            .field("connected", &self.group.is_some())
+            .finish()
+    }
+}
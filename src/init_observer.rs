@@ -0,0 +1,134 @@
+//! Progress events emitted during network initialisation.
+
+use crate::{SubDevice, SubDeviceState, error::Error, init_report::QuarantinedSubDevice};
+use core::time::Duration;
+
+/// Receives [`InitEvent`]s as [`MainDevice::init`](crate::MainDevice::init) progresses, e.g. to
+/// show startup progress or produce structured logs.
+///
+/// Events are delivered synchronously from the init future, so implementations should return
+/// quickly. Closures taking an [`InitEvent`] implement this trait.
+///
+/// Pass an implementation to
+/// [`MainDevice::with_init_observer`](crate::MainDevice::with_init_observer) to receive events.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::{InitEvent, MainDevice, MainDeviceConfig, PduStorage, Timeouts};
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// fn observer(event: InitEvent<'_>) {
+///     match event {
+///         InitEvent::SubDevicesCounted { count } => println!("Found {} SubDevices", count),
+///         InitEvent::SubDeviceDiscovered { subdevice, duration } => {
+///             println!("{} discovered in {:?}", subdevice.name(), duration)
+///         }
+///         other => println!("{:?}", other),
+///     }
+/// }
+///
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default())
+///     .with_init_observer(&observer);
+/// ```
+pub trait InitObserver: Sync {
+    /// Called for each event as initialisation progresses.
+    fn on_event(&self, event: InitEvent<'_>);
+}
+
+impl<F> InitObserver for F
+where
+    F: Fn(InitEvent<'_>) + Sync,
+{
+    fn on_event(&self, event: InitEvent<'_>) {
+        self(event)
+    }
+}
+
+impl core::fmt::Debug for dyn InitObserver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("InitObserver")
+    }
+}
+
+/// An event emitted during [`MainDevice::init`](crate::MainDevice::init).
+///
+/// Durations are measured with the `now` function passed to `init`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InitEvent<'a> {
+    /// The SubDevices on the network were counted.
+    SubDevicesCounted {
+        /// The number of SubDevices.
+        count: u16,
+    },
+    /// A SubDevice's identity, name and other EEPROM data were read.
+    SubDeviceDiscovered {
+        /// The discovered SubDevice.
+        subdevice: &'a SubDevice,
+        /// The time taken to read the SubDevice's information, mostly spent reading its EEPROM.
+        duration: Duration,
+    },
+    /// Distributed Clocks were configured.
+    DcConfigured {
+        /// The configured address of the SubDevice chosen as the DC reference clock, or `None` if
+        /// no SubDevices support DC.
+        reference: Option<u16>,
+    },
+    /// A region of the Process Data Image was reserved for a group.
+    PdiAllocated {
+        /// The start address of the group's PDI segment.
+        start_address: u32,
+        /// The maximum length of the group's PDI segment in bytes.
+        len: usize,
+    },
+    /// A SubDevice transitioned into a new state.
+    StateChanged {
+        /// The SubDevice's configured station address.
+        configured_address: u16,
+        /// The state the SubDevice is now in.
+        state: SubDeviceState,
+    },
+    /// Something unexpected happened, but initialisation continued.
+    Warning(InitWarning<'a>),
+    /// Initialisation completed.
+    Finished {
+        /// The total time taken to initialise the network.
+        duration: Duration,
+    },
+}
+
+/// A non-fatal problem encountered during initialisation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InitWarning<'a> {
+    /// No SubDevices were found on the network.
+    NoSubDevices,
+    /// A SubDevice's EEPROM configuration area checksum does not match its contents.
+    ///
+    /// Only emitted when
+    /// [`MainDeviceConfig::eeprom_checksum`](crate::MainDeviceConfig::eeprom_checksum) is
+    /// [`EepromChecksumBehaviour::Warn`](crate::EepromChecksumBehaviour::Warn).
+    EepromChecksum {
+        /// The SubDevice's configured station address.
+        configured_address: u16,
+        /// The checksum stored in the EEPROM.
+        stored: u8,
+        /// The checksum calculated from the EEPROM contents.
+        calculated: u8,
+    },
+    /// A SubDevice failed to initialise and was left in INIT by
+    /// [`MainDevice::init_tolerant`](crate::MainDevice::init_tolerant).
+    Quarantined(&'a QuarantinedSubDevice),
+    /// Distributed Clocks could not be configured by
+    /// [`MainDevice::init_tolerant`](crate::MainDevice::init_tolerant).
+    DcNotConfigured(Error),
+}
+
+/// Convert the difference between two `now` timestamps in nanoseconds to a [`Duration`].
+pub(crate) fn elapsed(start: u64, end: u64) -> Duration {
+    Duration::from_nanos(end.saturating_sub(start))
+}
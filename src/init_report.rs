@@ -0,0 +1,162 @@
+//! Results of a tolerant network initialisation.
+
+use crate::{SubDevice, error::Error, fmt};
+
+/// The stage of [`MainDevice::init_tolerant`](crate::MainDevice::init_tolerant) at which a
+/// SubDevice failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InitStage {
+    /// Reading the SubDevice's identity, name and other information from its EEPROM, or its
+    /// Explicit Device ID.
+    Discovery,
+    /// Assigning the SubDevice to a group. The group filter closure returned an error.
+    GroupAssignment,
+    /// Configuring the SubDevice's mailbox and transitioning it from INIT to PRE-OP.
+    Mailbox,
+}
+
+impl core::fmt::Display for InitStage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InitStage::Discovery => f.write_str("discovery"),
+            InitStage::GroupAssignment => f.write_str("group assignment"),
+            InitStage::Mailbox => f.write_str("mailbox configuration"),
+        }
+    }
+}
+
+/// A SubDevice that failed to initialise and was left in INIT.
+///
+/// Quarantined SubDevices are not added to any group.
+#[derive(Debug)]
+pub struct QuarantinedSubDevice {
+    /// The SubDevice's position in the network, starting from zero.
+    pub index: u16,
+    /// The SubDevice's configured station address.
+    pub configured_address: u16,
+    /// SubDevice metadata, if it was successfully discovered.
+    pub subdevice: Option<SubDevice>,
+    /// The stage at which initialisation failed.
+    pub stage: InitStage,
+    /// The error that caused the SubDevice to be quarantined.
+    pub error: Error,
+}
+
+/// A report of SubDevices that could not be initialised by
+/// [`MainDevice::init_tolerant`](crate::MainDevice::init_tolerant).
+#[derive(Debug)]
+pub struct InitReport<const MAX_SUBDEVICES: usize> {
+    quarantined: heapless::Vec<QuarantinedSubDevice, MAX_SUBDEVICES>,
+    dc_error: Option<Error>,
+}
+
+impl<const MAX_SUBDEVICES: usize> Default for InitReport<MAX_SUBDEVICES> {
+    fn default() -> Self {
+        Self {
+            quarantined: heapless::Vec::new(),
+            dc_error: None,
+        }
+    }
+}
+
+impl<const MAX_SUBDEVICES: usize> InitReport<MAX_SUBDEVICES> {
+    /// Returns `true` if every SubDevice was initialised and Distributed Clocks were configured
+    /// without error.
+    pub fn is_complete(&self) -> bool {
+        self.quarantined.is_empty() && self.dc_error.is_none()
+    }
+
+    /// SubDevices that failed to initialise, in network order.
+    pub fn quarantined(&self) -> &[QuarantinedSubDevice] {
+        &self.quarantined
+    }
+
+    /// The error encountered when configuring Distributed Clocks, if any.
+    ///
+    /// DC configuration is skipped with [`Error::Topology`] if any SubDevice failed
+    /// [`InitStage::Discovery`], as the network topology cannot be determined.
+    pub fn dc_error(&self) -> Option<Error> {
+        self.dc_error
+    }
+
+    pub(crate) fn has_discovery_failures(&self) -> bool {
+        self.quarantined
+            .iter()
+            .any(|failed| failed.stage == InitStage::Discovery)
+    }
+
+    pub(crate) fn set_dc_error(&mut self, error: Error) {
+        self.dc_error = Some(error);
+    }
+
+    pub(crate) fn quarantine(
+        &mut self,
+        index: u16,
+        configured_address: u16,
+        subdevice: Option<SubDevice>,
+        stage: InitStage,
+        error: Error,
+    ) -> Result<&QuarantinedSubDevice, Error> {
+        fmt::error!(
+            "SubDevice {:#06x} failed {}: {}. Leaving in INIT.",
+            configured_address,
+            stage,
+            error
+        );
+
+        self.quarantined
+            .push(QuarantinedSubDevice {
+                index,
+                configured_address,
+                subdevice,
+                stage,
+                error,
+            })
+            .map_err(|_| Error::Capacity(crate::error::Item::SubDevice))?;
+
+        Ok(fmt::unwrap_opt!(self.quarantined.last()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        crate::test_logger();
+
+        let mut report = InitReport::<2>::default();
+
+        assert!(report.is_complete());
+
+        report
+            .quarantine(1, 0x1001, None, InitStage::Mailbox, Error::Topology)
+            .unwrap();
+
+        assert!(!report.is_complete());
+        assert!(!report.has_discovery_failures());
+
+        report
+            .quarantine(2, 0x1002, None, InitStage::Discovery, Error::Topology)
+            .unwrap();
+
+        assert!(report.has_discovery_failures());
+        assert_eq!(
+            report
+                .quarantined()
+                .iter()
+                .map(|failed| failed.configured_address)
+                .collect::<Vec<_>>(),
+            [0x1001, 0x1002]
+        );
+
+        assert_eq!(
+            report
+                .quarantine(3, 0x1003, None, InitStage::Discovery, Error::Topology)
+                .map(|failed| failed.index),
+            Err(Error::Capacity(crate::error::Item::SubDevice))
+        );
+    }
+}
@@ -9,6 +9,9 @@
 //!   when the `std` feature is enabled.
 //! - `serde` - enable `serde` impls for some public items.
 //! - `xdp` - enable support for XDP on some (currently only Linux) systems.
+//! - `pdi-symbols` (enabled by default) - store PDO entry names and positions for each SubDevice so
+//!   process data can be accessed by name with [`SubDeviceRef::input`] and similar methods. This
+//!   adds around 900 bytes to each SubDevice.
 //!
 //! For `no_std` targets, it is recommended to add this crate with
 //!
@@ -163,55 +166,96 @@ mod al_status_code;
 mod base_data_types;
 mod command;
 mod dc;
+mod dl_control;
 mod dl_status;
 mod eeprom;
 pub mod error;
+mod error_counters;
 mod ethernet;
 mod fmmu;
 mod generate;
+mod hot_connect;
+mod init_observer;
+mod init_report;
+mod line_break;
 mod mailbox;
 mod maindevice;
 mod maindevice_config;
 mod pdi;
 mod pdu_loop;
 mod register;
+pub mod sii;
 mod subdevice;
+mod subdevice_config;
 pub mod subdevice_group;
 mod subdevice_state;
+mod supervisor;
 mod sync_manager_channel;
 mod timer_factory;
+mod topology;
 mod vendors;
 
 #[cfg(feature = "std")]
 pub mod std;
 
 pub use al_status_code::AlStatusCode;
+pub use base_data_types::PrimitiveDataType;
 pub use command::{Command, Reads, WrappedRead, WrappedWrite, Writes};
+pub use dl_control::PortLoop;
+#[cfg(feature = "std")]
+pub use eeprom::cache::FileEepromCache;
+pub use eeprom::cache::{EepromCache, MemoryEepromCache};
+pub use eeprom::types::{SiiCategory, SiiDcOpMode, SiiSyncUnit};
+pub use error_counters::{ErrorCounters, PortErrorCounters};
 pub use ethercrab_wire::{
     EtherCrabWireRead, EtherCrabWireReadSized, EtherCrabWireReadWrite, EtherCrabWireSized,
     EtherCrabWireWrite, EtherCrabWireWriteSized,
 };
 use ethernet::EthernetAddress;
+pub use hot_connect::{HotConnectEvent, HotConnectGroup};
+pub use init_observer::{InitEvent, InitObserver, InitWarning};
+pub use init_report::{InitReport, InitStage, QuarantinedSubDevice};
+pub use line_break::{LineBreak, LineBreakMonitor, LineBreakReport};
 pub use mailbox::coe::SubIndex;
 pub use maindevice::MainDevice;
-pub use maindevice_config::{MainDeviceConfig, RetryBehaviour};
-pub use pdu_loop::{PduLoop, PduRx, PduStorage, PduTx, ReceiveAction, SendableFrame};
+pub use maindevice_config::{
+    AddressingStrategy, EepromChecksumBehaviour, MainDeviceConfig, RetryBehaviour,
+};
+pub use pdu_loop::{
+    PduLoop, PduRx, PduStorage, PduTx, ReceiveAction, RedundantPort, SendableFrame,
+};
 pub use register::{DcSupport, RegisterAddress};
+#[cfg(feature = "pdi-symbols")]
+pub use subdevice::PdiSymbol;
 pub use subdevice::{
-    DcSync, ObjectDescriptionListQuery, ObjectDescriptionListQueryCounts, SubDevice,
-    SubDeviceIdentity, SubDevicePdi, SubDeviceRef,
+    DcSync, ObjectDescriptionListQuery, ObjectDescriptionListQueryCounts, Pdo, PdoEntry,
+    PdoMapping, SubDevice, SubDeviceIdentity, SubDevicePdi, SubDeviceRef, ports::Topology,
+};
+#[cfg(feature = "std")]
+pub use subdevice_config::ConfigCallback;
+pub use subdevice_config::{
+    ConfigAction, IdentityPattern, SdoValue, SdoWrite, SubDeviceConfig, SubDeviceConfigEntry,
+    SubDeviceConfigs,
 };
 pub use subdevice_group::{GroupId, SubDeviceGroup, SubDeviceGroupHandle, TxRxResponse};
 pub use subdevice_state::SubDeviceState;
+pub use supervisor::{BackoffPolicy, FaultSupervisor, SupervisorEvent, SupervisorObserver};
 pub use timer_factory::Timeouts;
+pub use topology::{NetworkTopology, TopologyNode, TopologyPort};
 
 const LEN_MASK: u16 = 0b0000_0111_1111_1111;
 const ETHERCAT_ETHERTYPE: u16 = 0x88a4;
 const MAINDEVICE_ADDR: EthernetAddress = EthernetAddress([0x10, 0x10, 0x10, 0x10, 0x10, 0x10]);
+/// Source address of frames sent from the redundant interface of a redundant ring.
+const REDUNDANT_MAINDEVICE_ADDR: EthernetAddress =
+    EthernetAddress([0x10, 0x10, 0x10, 0x10, 0x10, 0x11]);
 
 /// Starting address for discovered subdevices.
 const BASE_SUBDEVICE_ADDRESS: u16 = 0x1000;
 
+/// Starting address for SubDevices temporarily addressed by [`MainDevice::recover`].
+const RECOVERY_ADDRESS: u16 = 0xf000;
+
 /// The default lock provided by EtherCrab.
 ///
 /// This is a [`spin::RwLock`] containing [`spin::Yield`] when the `std` feature is enabled. For
@@ -0,0 +1,424 @@
+//! Work out where an EtherCAT line or ring is broken.
+
+use crate::{
+    MainDevice, NetworkTopology, TxRxResponse,
+    command::Command,
+    dl_status::DlStatus,
+    error::{Error, Item},
+    fmt,
+    register::RegisterAddress,
+};
+
+/// A broken connection between two points in the network, found by
+/// [`MainDevice::locate_line_break`].
+///
+/// A point is either a SubDevice, identified by its index in the network, or the MainDevice.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineBreak {
+    /// The SubDevice on the MainDevice side of the break, or `None` if the break is between the
+    /// MainDevice and the first SubDevice.
+    pub upstream: Option<u16>,
+    /// The port on [`upstream`](LineBreak::upstream) that leads to the break, if known.
+    pub upstream_port: Option<u8>,
+    /// Whether [`upstream`](LineBreak::upstream) still has an open link on its port, or `None`
+    /// if it could not be read.
+    pub upstream_link: Option<bool>,
+    /// The SubDevice on the far side of the break, or `None` if the port leads back to the
+    /// redundant interface of the MainDevice.
+    pub downstream: Option<u16>,
+    /// The port on [`downstream`](LineBreak::downstream) that leads to the break.
+    pub downstream_port: Option<u8>,
+    /// Whether [`downstream`](LineBreak::downstream) still has an open link on its port, or
+    /// `None` if it could not be read.
+    pub downstream_link: Option<bool>,
+}
+
+/// The result of [`MainDevice::locate_line_break`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineBreakReport<const MAX_SUBDEVICES: usize> {
+    /// The number of SubDevices in the topology the network was checked against.
+    pub expected: u16,
+    /// The number of SubDevices that responded to a broadcast read.
+    pub responding: u16,
+    /// The indices of SubDevices that could not be reached by their configured address.
+    pub unreachable: heapless::Vec<u16, MAX_SUBDEVICES>,
+    /// Every connection that is broken, in network order.
+    pub breaks: heapless::Vec<LineBreak, MAX_SUBDEVICES>,
+}
+
+impl<const MAX_SUBDEVICES: usize> LineBreakReport<MAX_SUBDEVICES> {
+    /// Whether every SubDevice is reachable and every connection in the topology is intact.
+    pub fn is_intact(&self) -> bool {
+        self.unreachable.is_empty() && self.breaks.is_empty()
+    }
+
+    pub(crate) async fn diagnose(
+        maindevice: &MainDevice<'_>,
+        topology: &NetworkTopology<MAX_SUBDEVICES>,
+    ) -> Result<Self, Error> {
+        // No response at all means the break is right at the MainDevice
+        let responding = match maindevice.count_subdevices().await {
+            Ok(count) => count,
+            Err(Error::Timeout(_)) => 0,
+            Err(e) => return Err(e),
+        };
+
+        let mut statuses = heapless::Vec::<Option<DlStatus>, MAX_SUBDEVICES>::new();
+
+        for node in topology.subdevices() {
+            let status = if responding == 0 {
+                None
+            } else {
+                match Command::fprd(node.configured_address, RegisterAddress::DlStatus.into())
+                    .receive::<DlStatus>(maindevice)
+                    .await
+                {
+                    Ok(status) => Some(status),
+                    Err(Error::WorkingCounter { .. } | Error::Timeout(_)) => None,
+                    Err(e) => return Err(e),
+                }
+            };
+
+            statuses
+                .push(status)
+                .map_err(|_| Error::Capacity(Item::SubDevice))?;
+        }
+
+        let report = Self::analyse(topology, responding, &statuses)?;
+
+        for line_break in report.breaks.iter() {
+            fmt::warn!(
+                "Line break between {:?} port {:?} and {:?} port {:?}",
+                line_break.upstream,
+                line_break.upstream_port,
+                line_break.downstream,
+                line_break.downstream_port
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Find broken connections from the DL status of each SubDevice in `topology`, or `None` for
+    /// SubDevices that could not be reached.
+    fn analyse(
+        topology: &NetworkTopology<MAX_SUBDEVICES>,
+        responding: u16,
+        statuses: &[Option<DlStatus>],
+    ) -> Result<Self, Error> {
+        let nodes = topology.subdevices();
+
+        let status = |index: u16| {
+            nodes
+                .iter()
+                .zip(statuses)
+                .find(|(node, _)| node.index == index)
+                .and_then(|(_, status)| status.as_ref())
+        };
+
+        let port_open = |status: &DlStatus, port: u8| status.link(port) && !status.loopback(port);
+
+        let mut report = Self {
+            expected: nodes.len() as u16,
+            responding,
+            unreachable: heapless::Vec::new(),
+            breaks: heapless::Vec::new(),
+        };
+
+        let mut check = |upstream: Option<u16>,
+                         upstream_port: Option<u8>,
+                         downstream: Option<u16>,
+                         downstream_port: Option<u8>|
+         -> Result<(), Error> {
+            // The MainDevice itself is always reachable
+            let upstream_status = upstream.map(status);
+            let downstream_status = downstream.map(status);
+
+            let upstream_reachable = upstream_status.is_none_or(|status| status.is_some());
+            let downstream_reachable = downstream_status.is_none_or(|status| status.is_some());
+
+            let upstream_link = upstream_status
+                .flatten()
+                .zip(upstream_port)
+                .map(|(status, port)| port_open(status, port));
+            let downstream_link = downstream_status
+                .flatten()
+                .zip(downstream_port)
+                .map(|(status, port)| port_open(status, port));
+
+            let broken = match (upstream_reachable, downstream_reachable) {
+                (true, true) => upstream_link == Some(false) || downstream_link == Some(false),
+                // Exactly one side can be reached, so the break must be between them
+                (true, false) | (false, true) => true,
+                // Both sides are behind a break somewhere else
+                (false, false) => false,
+            };
+
+            if broken {
+                report
+                    .breaks
+                    .push(LineBreak {
+                        upstream,
+                        upstream_port,
+                        upstream_link,
+                        downstream,
+                        downstream_port,
+                        downstream_link,
+                    })
+                    .map_err(|_| Error::Capacity(Item::SubDevice))?;
+            }
+
+            Ok(())
+        };
+
+        for node in nodes {
+            check(
+                node.parent,
+                node.parent_port,
+                Some(node.index),
+                Some(node.entry_port),
+            )?;
+
+            // Active ports with nothing downstream lead back to the MainDevice's redundant
+            // interface in a ring.
+            for port in node.ports.iter().filter(|port| {
+                port.active && port.number != node.entry_port && port.downstream_to.is_none()
+            }) {
+                check(Some(node.index), Some(port.number), None, None)?;
+            }
+        }
+
+        for (node, status) in nodes.iter().zip(statuses) {
+            if status.is_none() {
+                report
+                    .unreachable
+                    .push(node.index)
+                    .map_err(|_| Error::Capacity(Item::SubDevice))?;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Run [`MainDevice::locate_line_break`] automatically when the working counter of a group drops.
+///
+/// The expected working counter is learnt as the highest value seen by
+/// [`check`](LineBreakMonitor::check), so the monitor should only be fed responses once the group
+/// has reached its final state. A diagnosis is run once each time the working counter drops, and
+/// not again until it has recovered.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ethercrab::{
+///     LineBreakMonitor, MainDevice, MainDeviceConfig, NetworkTopology, PduStorage, Timeouts,
+///     error::Error, std::ethercat_now,
+/// };
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// # async fn case() -> Result<(), Error> {
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+///
+/// let group = maindevice.init_single_group::<16, 64>(ethercat_now).await?;
+///
+/// let mut monitor = LineBreakMonitor::new(NetworkTopology::<16>::from_groups(&[&group])?);
+///
+/// let group = group.into_op(&maindevice).await?;
+///
+/// loop {
+///     let response = group.tx_rx(&maindevice).await?;
+///
+///     if let Some(report) = monitor.check(&maindevice, &response).await? {
+///         for line_break in report.breaks {
+///             log::error!("Network broken: {:?}", line_break);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineBreakMonitor<const MAX_SUBDEVICES: usize> {
+    topology: NetworkTopology<MAX_SUBDEVICES>,
+    expected_working_counter: u16,
+    faulted: bool,
+}
+
+impl<const MAX_SUBDEVICES: usize> LineBreakMonitor<MAX_SUBDEVICES> {
+    /// Create a new monitor that checks the network against the given topology.
+    pub fn new(topology: NetworkTopology<MAX_SUBDEVICES>) -> Self {
+        Self {
+            topology,
+            expected_working_counter: 0,
+            faulted: false,
+        }
+    }
+
+    /// The topology the network is checked against.
+    pub fn topology(&self) -> &NetworkTopology<MAX_SUBDEVICES> {
+        &self.topology
+    }
+
+    /// Check the working counter of a process data cycle, locating the break if it has dropped.
+    pub async fn check<const N: usize, T>(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+        response: &TxRxResponse<N, T>,
+    ) -> Result<Option<LineBreakReport<MAX_SUBDEVICES>>, Error> {
+        if response.working_counter >= self.expected_working_counter {
+            self.expected_working_counter = response.working_counter;
+            self.faulted = false;
+
+            return Ok(None);
+        }
+
+        self.fault(maindevice).await
+    }
+
+    /// Check an error returned by any other operation, locating the break if it is a working
+    /// counter error.
+    pub async fn check_error(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+        error: &Error,
+    ) -> Result<Option<LineBreakReport<MAX_SUBDEVICES>>, Error> {
+        if !matches!(error, Error::WorkingCounter { .. }) {
+            return Ok(None);
+        }
+
+        self.fault(maindevice).await
+    }
+
+    async fn fault(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<Option<LineBreakReport<MAX_SUBDEVICES>>, Error> {
+        if self.faulted {
+            return Ok(None);
+        }
+
+        self.faulted = true;
+
+        maindevice.locate_line_break(&self.topology).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SubDevice, subdevice::ports::tests::make_ports, topology::TopologyNode};
+    use core::num::NonZeroU16;
+
+    fn dl_status(links: [bool; 4], loops: [bool; 4]) -> DlStatus {
+        DlStatus {
+            pdi_operational: true,
+            watchdog_ok: true,
+            extended_link_detection: false,
+            link_port0: links[0],
+            link_port1: links[1],
+            link_port2: links[2],
+            link_port3: links[3],
+            loopback_port0: loops[0],
+            signal_port0: links[0],
+            loopback_port1: loops[1],
+            signal_port1: links[1],
+            loopback_port2: loops[2],
+            signal_port2: links[2],
+            loopback_port3: loops[3],
+            signal_port3: links[3],
+        }
+    }
+
+    /// Three SubDevices in a line, connected from port 1 to port 0.
+    fn line() -> NetworkTopology<4> {
+        let subdevices = (0..3u16)
+            .map(|index| {
+                let mut subdevice = SubDevice {
+                    index,
+                    configured_address: 0x1000 + index,
+                    ports: make_ports(true, false, index < 2, false),
+                    parent_index: index.checked_sub(1),
+                    ..SubDevice::default()
+                };
+
+                subdevice.ports.0[2].downstream_to =
+                    NonZeroU16::new(index + 1).filter(|_| index < 2);
+
+                TopologyNode::new(&subdevice)
+            })
+            .collect();
+
+        NetworkTopology::new(subdevices)
+    }
+
+    #[test]
+    fn intact() {
+        let topology = line();
+
+        let passthrough = dl_status([true, true, false, false], [false, false, true, true]);
+        let end = dl_status([true, false, false, false], [false, true, true, true]);
+
+        let report = LineBreakReport::analyse(
+            &topology,
+            3,
+            &[Some(passthrough), Some(passthrough), Some(end)],
+        )
+        .unwrap();
+
+        assert!(report.is_intact(), "{:?}", report);
+    }
+
+    #[test]
+    fn broken_after_second() {
+        let topology = line();
+
+        let passthrough = dl_status([true, true, false, false], [false, false, true, true]);
+        // Link on port 1 lost, so the ESC has closed it
+        let closed = dl_status([true, false, false, false], [false, true, true, true]);
+
+        let report =
+            LineBreakReport::analyse(&topology, 2, &[Some(passthrough), Some(closed), None])
+                .unwrap();
+
+        assert_eq!(report.expected, 3);
+        assert_eq!(report.responding, 2);
+        assert_eq!(report.unreachable, [2]);
+        assert_eq!(
+            report.breaks,
+            [LineBreak {
+                upstream: Some(1),
+                upstream_port: Some(1),
+                upstream_link: Some(false),
+                downstream: Some(2),
+                downstream_port: Some(0),
+                downstream_link: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn broken_at_maindevice() {
+        let topology = line();
+
+        let report = LineBreakReport::analyse(&topology, 0, &[None, None, None]).unwrap();
+
+        assert_eq!(report.unreachable, [0, 1, 2]);
+        assert_eq!(
+            report.breaks,
+            [LineBreak {
+                upstream: None,
+                upstream_port: None,
+                upstream_link: None,
+                downstream: Some(0),
+                downstream_port: Some(0),
+                downstream_link: None,
+            }]
+        );
+    }
+}
@@ -1,17 +1,24 @@
 use crate::{
-    BASE_SUBDEVICE_ADDRESS, MainDeviceConfig, SubDeviceGroup, Timeouts,
+    AddressingStrategy, BASE_SUBDEVICE_ADDRESS, LineBreakReport, MainDeviceConfig, NetworkTopology,
+    RECOVERY_ADDRESS, SubDeviceGroup, Timeouts,
     al_control::AlControl,
     al_status_code::AlStatusCode,
     command::Command,
     dc,
-    eeprom::types::SyncManager,
+    eeprom::{
+        cache::EepromCache,
+        types::{SiiOwner, SyncManager},
+    },
     error::{Error, Item},
     fmmu::Fmmu,
     fmt,
+    init_observer::{self, InitEvent, InitObserver, InitWarning},
+    init_report::{InitReport, InitStage},
     pdi::PdiOffset,
     pdu_loop::{PduLoop, ReceivedPdu},
     register::RegisterAddress,
-    subdevice::SubDevice,
+    subdevice::{SubDevice, SubDeviceRef},
+    subdevice_config::SubDeviceConfigs,
     subdevice_group::{self, SubDeviceGroupHandle},
     subdevice_state::SubDeviceState,
     timer_factory::IntoTimeout,
@@ -19,7 +26,7 @@ use crate::{
 use core::{
     cell::UnsafeCell,
     mem::size_of,
-    sync::atomic::{AtomicU16, Ordering},
+    sync::atomic::{AtomicU16, AtomicU32, Ordering},
 };
 use ethercrab_wire::{EtherCrabWireSized, EtherCrabWireWrite};
 use heapless::FnvIndexMap;
@@ -44,6 +51,18 @@ pub struct MainDevice<'sto> {
     dc_reference_configured_address: AtomicU16,
     pub(crate) timeouts: Timeouts,
     pub(crate) config: MainDeviceConfig,
+    /// Per-SubDevice configuration applied during init and PRE-OP -> SAFE-OP.
+    pub(crate) subdevice_configs: Option<&'static SubDeviceConfigs>,
+    /// Cache of SubDevice EEPROM images.
+    pub(crate) eeprom_cache: Option<&'static dyn EepromCache>,
+    /// Whether to compare cached EEPROM images against the SubDevice's checksum on first contact.
+    pub(crate) validate_eeprom_cache: bool,
+    /// Receives progress events during init.
+    init_observer: Option<&'static dyn InitObserver>,
+    /// Station aliases of the first SubDevice in each optional hot-connect segment.
+    hot_connect_aliases: &'static [u16],
+    /// The end of the PDI allocated so far, in bytes. Hot-connect groups reserve PDI from here.
+    pdi_end: AtomicU32,
 }
 
 unsafe impl Sync for MainDevice<'_> {}
@@ -61,6 +80,97 @@ impl<'sto> MainDevice<'sto> {
             dc_reference_configured_address: AtomicU16::new(0),
             timeouts,
             config,
+            subdevice_configs: None,
+            eeprom_cache: None,
+            validate_eeprom_cache: false,
+            init_observer: None,
+            hot_connect_aliases: &[],
+            pdi_end: AtomicU32::new(0),
+        }
+    }
+
+    /// Set a registry of per-SubDevice configurations to apply automatically during
+    /// [`init`](MainDevice::init) and each group's PRE-OP -> SAFE-OP transition.
+    ///
+    /// See [`SubDeviceConfigs`] for details and an example.
+    pub const fn with_subdevice_configs(mut self, configs: &'static SubDeviceConfigs) -> Self {
+        self.subdevice_configs = Some(configs);
+
+        self
+    }
+
+    /// Set a cache to store SubDevice EEPROM images in.
+    ///
+    /// Each SubDevice's EEPROM is copied into the cache the first time it is seen during
+    /// [`init`](MainDevice::init). Afterwards, EEPROM reads for a SubDevice with the same identity
+    /// and serial number are served from the cache, which can significantly speed up startup.
+    ///
+    /// If `validate` is `true`, the checksum word of a cached image is compared against the
+    /// SubDevice's EEPROM on first contact, and the cached image is discarded if they differ. This
+    /// costs one EEPROM read per SubDevice.
+    ///
+    /// See [`MemoryEepromCache`](crate::MemoryEepromCache) for an example.
+    pub const fn with_eeprom_cache(
+        mut self,
+        cache: &'static dyn EepromCache,
+        validate: bool,
+    ) -> Self {
+        self.eeprom_cache = Some(cache);
+        self.validate_eeprom_cache = validate;
+
+        self
+    }
+
+    /// Set an observer to receive progress events during [`init`](MainDevice::init).
+    ///
+    /// See [`InitObserver`] for details and an example.
+    pub const fn with_init_observer(mut self, observer: &'static dyn InitObserver) -> Self {
+        self.init_observer = Some(observer);
+
+        self
+    }
+
+    /// Declare optional hot-connect segments, each identified by the station alias of its first
+    /// SubDevice.
+    ///
+    /// [`init`](MainDevice::init) stops at the first SubDevice with one of these aliases, leaving
+    /// it and any SubDevices after it in INIT. Segments are then connected at runtime with a
+    /// [`HotConnectGroup`](crate::HotConnectGroup), whether they were present at startup or not.
+    ///
+    /// Hot-connect segments must be at the end of the network. As SubDevices in these segments
+    /// are not counted by [`num_subdevices`](MainDevice::num_subdevices),
+    /// [`wait_for_state`](MainDevice::wait_for_state) should not be used while a segment is
+    /// connected. Use each group's state transition methods instead.
+    pub const fn with_hot_connect(mut self, aliases: &'static [u16]) -> Self {
+        self.hot_connect_aliases = aliases;
+
+        self
+    }
+
+    /// Whether `alias` identifies the first SubDevice of a hot-connect segment.
+    pub(crate) fn is_hot_connect_alias(&self, alias: u16) -> bool {
+        alias != 0 && self.hot_connect_aliases.contains(&alias)
+    }
+
+    /// Reserve `len` bytes of PDI after everything allocated so far.
+    pub(crate) fn reserve_pdi(&self, len: usize) -> PdiOffset {
+        let start_address = self.pdi_end.fetch_add(len as u32, Ordering::Relaxed);
+
+        PdiOffset {
+            start_address,
+            ..PdiOffset::default()
+        }
+    }
+
+    /// Update the number of SubDevices after a hot-connect segment is connected or removed.
+    pub(crate) fn set_num_subdevices(&self, num_subdevices: u16) {
+        self.num_subdevices.store(num_subdevices, Ordering::Relaxed);
+    }
+
+    /// Send an event to the init observer, if one is set.
+    pub(crate) fn emit(&self, event: InitEvent<'_>) {
+        if let Some(observer) = self.init_observer {
+            observer.on_event(event);
         }
     }
 
@@ -142,6 +252,334 @@ impl<'sto> MainDevice<'sto> {
         Ok(())
     }
 
+    /// Read identity, name and other EEPROM data from every SubDevice.
+    ///
+    /// SubDevices are added to `subdevices` in network order. If `report` is given, SubDevices
+    /// that could not be read are quarantined instead of returning an error.
+    async fn discover_subdevices<const MAX_SUBDEVICES: usize>(
+        &self,
+        num_subdevices: u16,
+        subdevices: &mut heapless::Deque<SubDevice, MAX_SUBDEVICES>,
+        mut report: Option<&mut InitReport<MAX_SUBDEVICES>>,
+        now: impl Fn() -> u64 + Copy,
+    ) -> Result<(), Error> {
+        #[cfg(feature = "std")]
+        if self.config.discovery_concurrency > 1 {
+            return self
+                .discover_subdevices_concurrent(num_subdevices, subdevices, report, now)
+                .await;
+        }
+
+        #[cfg(not(feature = "std"))]
+        if self.config.discovery_concurrency > 1 {
+            fmt::warn!(
+                "discovery_concurrency of {} has no effect without the std feature, reading SubDevices sequentially",
+                self.config.discovery_concurrency
+            );
+        }
+
+        for subdevice_idx in 0..num_subdevices {
+            let configured_address = BASE_SUBDEVICE_ADDRESS.wrapping_add(subdevice_idx);
+
+            let start = now();
+
+            let subdevice = match SubDevice::new(self, subdevice_idx, configured_address).await {
+                Ok(subdevice) => subdevice,
+                Err(e) => {
+                    self.quarantine_undiscovered(report.as_deref_mut(), subdevice_idx, e)?;
+
+                    continue;
+                }
+            };
+
+            self.emit(InitEvent::SubDeviceDiscovered {
+                subdevice: &subdevice,
+                duration: init_observer::elapsed(start, now()),
+            });
+
+            subdevices
+                .push_back(subdevice)
+                .map_err(|_| Error::Capacity(Item::SubDevice))?;
+        }
+
+        Ok(())
+    }
+
+    /// Like [`discover_subdevices`](MainDevice::discover_subdevices), but reads up to
+    /// [`MainDeviceConfig::discovery_concurrency`] SubDevices at the same time.
+    ///
+    /// In-flight reads are boxed so the size of the [`init`](MainDevice::init) future does not
+    /// grow with the concurrency limit.
+    #[cfg(feature = "std")]
+    async fn discover_subdevices_concurrent<const MAX_SUBDEVICES: usize>(
+        &self,
+        num_subdevices: u16,
+        subdevices: &mut heapless::Deque<SubDevice, MAX_SUBDEVICES>,
+        mut report: Option<&mut InitReport<MAX_SUBDEVICES>>,
+        now: impl Fn() -> u64 + Copy,
+    ) -> Result<(), Error> {
+        type InFlight<'a> =
+            core::pin::Pin<Box<dyn Future<Output = Result<SubDevice, Error>> + Send + 'a>>;
+
+        let concurrency = self
+            .config
+            .discovery_concurrency
+            .min(usize::from(num_subdevices));
+
+        let mut next_idx = 0u16;
+
+        let mut slots: Vec<Option<(u16, u64, InFlight<'_>)>> =
+            (0..concurrency).map(|_| None).collect();
+
+        core::future::poll_fn(|cx| {
+            let mut pending = false;
+
+            for slot in slots.iter_mut() {
+                loop {
+                    // Start reading the next SubDevice if this slot is free
+                    if slot.is_none() {
+                        if next_idx >= num_subdevices {
+                            break;
+                        }
+
+                        *slot = Some((
+                            next_idx,
+                            now(),
+                            Box::pin(SubDevice::new(
+                                self,
+                                next_idx,
+                                BASE_SUBDEVICE_ADDRESS.wrapping_add(next_idx),
+                            )),
+                        ));
+
+                        next_idx += 1;
+                    }
+
+                    let Some((subdevice_idx, start, fut)) = slot.as_mut() else {
+                        break;
+                    };
+
+                    let core::task::Poll::Ready(res) = fut.as_mut().poll(cx) else {
+                        pending = true;
+
+                        break;
+                    };
+
+                    let (subdevice_idx, start) = (*subdevice_idx, *start);
+
+                    *slot = None;
+
+                    let subdevice = match res {
+                        Ok(subdevice) => subdevice,
+                        Err(e) => {
+                            self.quarantine_undiscovered(report.as_deref_mut(), subdevice_idx, e)?;
+
+                            continue;
+                        }
+                    };
+
+                    self.emit(InitEvent::SubDeviceDiscovered {
+                        subdevice: &subdevice,
+                        duration: init_observer::elapsed(start, now()),
+                    });
+
+                    if subdevices.push_back(subdevice).is_err() {
+                        return core::task::Poll::Ready(Err(Error::Capacity(Item::SubDevice)));
+                    }
+                }
+            }
+
+            if pending {
+                core::task::Poll::Pending
+            } else {
+                core::task::Poll::Ready(Ok(()))
+            }
+        })
+        .await?;
+
+        // SubDevices may complete out of order when read concurrently
+        subdevices
+            .as_mut_slices()
+            .0
+            .sort_unstable_by_key(|subdevice| subdevice.index);
+
+        Ok(())
+    }
+
+    /// Whether SubDevice Explicit Device IDs are needed for addressing or to match a registered
+    /// configuration.
+    pub(crate) fn needs_explicit_device_ids(&self) -> bool {
+        self.config.addressing == AddressingStrategy::ExplicitDeviceId
+            || self
+                .subdevice_configs
+                .is_some_and(|configs| configs.uses_explicit_device_id())
+    }
+
+    /// Whether any SubDevice on the network currently has the given configured address.
+    pub(crate) async fn address_in_use(&self, configured_address: u16) -> Result<bool, Error> {
+        Command::fprd(configured_address, RegisterAddress::Type.into())
+            .receive_wkc::<u8>(self)
+            .await
+            .map(|wkc| wkc > 0)
+    }
+
+    /// Read the Explicit Device ID of each discovered SubDevice, if it is needed for addressing or
+    /// to match a registered configuration.
+    ///
+    /// If `report` is given, SubDevices whose ID cannot be read are removed from `subdevices` and
+    /// quarantined.
+    async fn read_explicit_device_ids<const MAX_SUBDEVICES: usize>(
+        &self,
+        subdevices: &mut heapless::Deque<SubDevice, MAX_SUBDEVICES>,
+        mut report: Option<&mut InitReport<MAX_SUBDEVICES>>,
+    ) -> Result<(), Error> {
+        if !self.needs_explicit_device_ids() {
+            return Ok(());
+        }
+
+        // Each SubDevice is taken off the front and put back on the end if its ID was read, so the
+        // order is unchanged.
+        for _ in 0..subdevices.len() {
+            let mut subdevice = fmt::unwrap_opt!(subdevices.pop_front());
+
+            let configured_address = subdevice.configured_address;
+
+            let res = SubDeviceRef::new(self, configured_address, ())
+                .explicit_device_id()
+                .await;
+
+            match (res, report.as_deref_mut()) {
+                (Ok(id), _) => {
+                    subdevice.explicit_device_id = id;
+
+                    // Can't fail: the SubDevice was just taken out of the deque
+                    let _ = subdevices.push_back(subdevice);
+                }
+                (Err(e), Some(report)) => {
+                    let quarantined = report.quarantine(
+                        subdevice.index,
+                        configured_address,
+                        Some(subdevice),
+                        InitStage::Discovery,
+                        e,
+                    )?;
+
+                    self.emit(InitEvent::Warning(InitWarning::Quarantined(quarantined)));
+                }
+                (Err(e), None) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Readdress discovered SubDevices using the configured [`AddressingStrategy`].
+    ///
+    /// SubDevices that failed discovery keep their position-based address, which is also checked
+    /// for duplicates, as are the `reserved` addresses of SubDevices in hot-connect segments.
+    ///
+    /// [`AddressingStrategy`]: crate::AddressingStrategy
+    async fn assign_addresses<const MAX_SUBDEVICES: usize>(
+        &self,
+        subdevices: &mut heapless::Deque<SubDevice, MAX_SUBDEVICES>,
+        reserved: &[u16],
+        report: Option<&InitReport<MAX_SUBDEVICES>>,
+    ) -> Result<(), Error> {
+        let strategy = self.config.addressing;
+
+        if strategy == AddressingStrategy::Position {
+            return Ok(());
+        }
+
+        let mut addresses = heapless::Vec::<u16, MAX_SUBDEVICES>::new();
+
+        for subdevice in subdevices.iter() {
+            let address = strategy.address(
+                subdevice.identity,
+                subdevice.alias_address,
+                subdevice.explicit_device_id,
+                subdevice.index,
+            );
+
+            addresses
+                .push(address)
+                .map_err(|_| Error::Capacity(Item::SubDevice))?;
+        }
+
+        let quarantined = report
+            .map(|report| report.quarantined())
+            .unwrap_or_default()
+            .iter()
+            .map(|failed| failed.configured_address);
+
+        let all = addresses.iter().chain(reserved).copied().chain(quarantined);
+
+        for (i, address) in addresses.iter().chain(reserved).enumerate() {
+            let duplicate = all.clone().skip(i + 1).any(|other| other == *address);
+
+            if duplicate {
+                fmt::error!("More than one SubDevice assigned address {:#06x}", address);
+
+                return Err(Error::DuplicateAddress {
+                    configured_address: *address,
+                });
+            }
+        }
+
+        let (front, back) = subdevices.as_mut_slices();
+
+        // Slice iterators are used as the deque's own iterator is not `Send`
+        for (subdevice, address) in front.iter_mut().chain(back).zip(addresses) {
+            if subdevice.configured_address == address {
+                continue;
+            }
+
+            fmt::debug!(
+                "Readdressing SubDevice {:#06x} at position {} to {:#06x}",
+                subdevice.configured_address,
+                subdevice.index,
+                address
+            );
+
+            // Address by position as the new address may currently belong to another SubDevice
+            Command::apwr(
+                subdevice.index,
+                RegisterAddress::ConfiguredStationAddress.into(),
+            )
+            .send(self, address)
+            .await?;
+
+            subdevice.configured_address = address;
+        }
+
+        Ok(())
+    }
+
+    /// Quarantine a SubDevice that failed discovery if `report` is given, otherwise return the
+    /// error.
+    fn quarantine_undiscovered<const MAX_SUBDEVICES: usize>(
+        &self,
+        report: Option<&mut InitReport<MAX_SUBDEVICES>>,
+        subdevice_idx: u16,
+        error: Error,
+    ) -> Result<(), Error> {
+        let Some(report) = report else {
+            return Err(error);
+        };
+
+        let quarantined = report.quarantine(
+            subdevice_idx,
+            BASE_SUBDEVICE_ADDRESS.wrapping_add(subdevice_idx),
+            None,
+            InitStage::Discovery,
+            error,
+        )?;
+
+        self.emit(InitEvent::Warning(InitWarning::Quarantined(quarantined)));
+
+        Ok(())
+    }
+
     /// Detect SubDevices, set their configured station addresses, assign to groups, configure
     /// SubDevices from EEPROM.
     ///
@@ -208,6 +646,107 @@ impl<'sto> MainDevice<'sto> {
     /// # };
     /// ```
     pub async fn init<const MAX_SUBDEVICES: usize, G>(
+        &self,
+        now: impl Fn() -> u64 + Copy,
+        groups: G,
+        group_filter: impl for<'g> FnMut(
+            &'g G,
+            &SubDevice,
+        ) -> Result<&'g dyn SubDeviceGroupHandle, Error>,
+    ) -> Result<G, Error> {
+        let fut = self.init_inner::<MAX_SUBDEVICES, G>(now, groups, group_filter, None);
+
+        // The init future is large, so keep it off the stack where possible
+        #[cfg(feature = "std")]
+        let fut = Box::pin(fut);
+
+        fut.await
+    }
+
+    /// Like [`init`](MainDevice::init), but continue initialising healthy SubDevices if some fail.
+    ///
+    /// SubDevices that cannot be discovered, are rejected by `group_filter`, or fail to configure
+    /// their mailboxes and reach PRE-OP are left in INIT and listed in the returned
+    /// [`InitReport`]. They are not added to any group. All other SubDevices are initialised as
+    /// normal.
+    ///
+    /// Distributed Clocks configuration errors are recorded in the report instead of aborting
+    /// initialisation. DC is not configured at all if any SubDevice failed discovery, as the
+    /// network topology cannot be determined.
+    ///
+    /// Errors not attributable to a single SubDevice, e.g. a failure to count or reset SubDevices,
+    /// are still returned as an `Err`.
+    ///
+    /// Tolerance ends once the returned groups are in PRE-OP. Later group transitions, e.g.
+    /// [`into_op`](crate::SubDeviceGroup::into_op), still fail as a whole if any SubDevice in the
+    /// group refuses them. Such SubDevices can be handled individually with
+    /// [`SubDeviceRef::request_state`](crate::SubDeviceRef::request_state).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ethercrab::{
+    ///     error::Error, std::ethercat_now, MainDevice, MainDeviceConfig, PduStorage,
+    ///     SubDeviceGroup, Timeouts,
+    /// };
+    ///
+    /// const MAX_SUBDEVICES: usize = 16;
+    /// const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+    /// const MAX_FRAMES: usize = 16;
+    ///
+    /// static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+    ///
+    /// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+    ///
+    /// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+    ///
+    /// # async {
+    /// let (group, report) = maindevice
+    ///     .init_tolerant::<MAX_SUBDEVICES, _>(
+    ///         ethercat_now,
+    ///         SubDeviceGroup::<MAX_SUBDEVICES, 64>::default(),
+    ///         |group, _subdevice| Ok(group),
+    ///     )
+    ///     .await
+    ///     .expect("Init");
+    ///
+    /// for failed in report.quarantined() {
+    ///     log::warn!(
+    ///         "SubDevice {:#06x} failed {}: {}",
+    ///         failed.configured_address,
+    ///         failed.stage,
+    ///         failed.error
+    ///     );
+    /// }
+    /// # };
+    /// ```
+    pub async fn init_tolerant<const MAX_SUBDEVICES: usize, G>(
+        &self,
+        now: impl Fn() -> u64 + Copy,
+        groups: G,
+        group_filter: impl for<'g> FnMut(
+            &'g G,
+            &SubDevice,
+        ) -> Result<&'g dyn SubDeviceGroupHandle, Error>,
+    ) -> Result<(G, InitReport<MAX_SUBDEVICES>), Error> {
+        let mut report = InitReport::default();
+
+        let fut =
+            self.init_inner::<MAX_SUBDEVICES, G>(now, groups, group_filter, Some(&mut report));
+
+        #[cfg(feature = "std")]
+        let fut = Box::pin(fut);
+
+        let groups = fut.await?;
+
+        Ok((groups, report))
+    }
+
+    /// Shared implementation of [`init`](MainDevice::init) and
+    /// [`init_tolerant`](MainDevice::init_tolerant).
+    ///
+    /// If `report` is `None`, any error aborts initialisation.
+    async fn init_inner<const MAX_SUBDEVICES: usize, G>(
         &self,
         now: impl Fn() -> u64 + Copy,
         groups: G,
@@ -215,18 +754,27 @@ impl<'sto> MainDevice<'sto> {
             &'g G,
             &SubDevice,
         ) -> Result<&'g dyn SubDeviceGroupHandle, Error>,
+        mut report: Option<&mut InitReport<MAX_SUBDEVICES>>,
     ) -> Result<G, Error> {
+        let start = now();
+
         // Each SubDevice increments working counter, so we can use it as a total count of
         // SubDevices
         let num_subdevices = self.count_subdevices().await?;
 
         fmt::debug!("Discovered {} SubDevices", num_subdevices);
 
+        self.emit(InitEvent::SubDevicesCounted {
+            count: num_subdevices,
+        });
+
         if num_subdevices == 0 {
             fmt::warn!(
                 "No SubDevices were discovered. Check NIC device, connections and PDU response timeouts"
             );
 
+            self.emit(InitEvent::Warning(InitWarning::NoSubDevices));
+
             return Ok(groups);
         }
 
@@ -255,28 +803,73 @@ impl<'sto> MainDevice<'sto> {
         // set address is added to the network before init. In this case, two SDs could have the
         // same address which wouldn't have been reset yet when we're half way through a single
         // configuration loop.
-        for subdevice_idx in 0..num_subdevices {
-            let configured_address = BASE_SUBDEVICE_ADDRESS.wrapping_add(subdevice_idx);
+        self.discover_subdevices(num_subdevices, &mut subdevices, report.as_deref_mut(), now)
+            .await?;
 
-            let subdevice = SubDevice::new(self, subdevice_idx, configured_address).await?;
+        self.read_explicit_device_ids(&mut subdevices, report.as_deref_mut())
+            .await?;
 
-            subdevices
-                .push_back(subdevice)
-                .map_err(|_| Error::Capacity(Item::SubDevice))?;
+        // Addresses hot-connect segment SubDevices will be given when they are connected
+        let mut reserved = heapless::Vec::<u16, MAX_SUBDEVICES>::new();
+
+        // Leave hot-connect segments to be configured by their `HotConnectGroup`s
+        let hot_connect_start = subdevices
+            .iter()
+            .find(|subdevice| self.is_hot_connect_alias(subdevice.alias_address))
+            .map(|subdevice| subdevice.index);
+
+        if let Some(position) = hot_connect_start {
+            fmt::debug!(
+                "Hot-connect segment starts at position {}, leaving remaining SubDevices in INIT",
+                position
+            );
+
+            while subdevices
+                .back()
+                .is_some_and(|subdevice| subdevice.index >= position)
+            {
+                let subdevice = fmt::unwrap_opt!(subdevices.pop_back());
+
+                reserved
+                    .push(self.config.addressing.address(
+                        subdevice.identity,
+                        subdevice.alias_address,
+                        subdevice.explicit_device_id,
+                        subdevice.index,
+                    ))
+                    .map_err(|_| Error::Capacity(Item::SubDevice))?;
+            }
+
+            self.num_subdevices.store(position, Ordering::Relaxed);
         }
 
-        fmt::debug!("Configuring topology/distributed clocks");
+        self.assign_addresses(&mut subdevices, &reserved, report.as_deref())
+            .await?;
 
-        // Configure distributed clock offsets/propagation delays, perform static drift
-        // compensation. We need the SubDevices in a single list so we can read the topology.
-        let dc_master = dc::configure_dc(self, subdevices.as_mut_slices().0, now).await?;
+        // The topology can't be determined with SubDevices missing from the list
+        let skip_dc = report
+            .as_deref()
+            .is_some_and(|report| report.has_discovery_failures());
 
-        // If there are SubDevices that support distributed clocks, run static drift compensation
-        if let Some(dc_master) = dc_master {
-            self.dc_reference_configured_address
-                .store(dc_master.configured_address(), Ordering::Relaxed);
+        if skip_dc {
+            fmt::warn!("Not configuring distributed clocks as some SubDevices failed discovery");
+        }
+
+        let dc_result = if skip_dc {
+            Err(Error::Topology)
+        } else {
+            self.configure_dc(&mut subdevices, now).await
+        };
+
+        match (dc_result, report.as_deref_mut()) {
+            (Err(e), Some(report)) => {
+                fmt::error!("Failed to configure distributed clocks: {}", e);
+
+                self.emit(InitEvent::Warning(InitWarning::DcNotConfigured(e)));
 
-            dc::run_dc_static_sync(self, dc_master, self.config.dc_static_sync_iterations).await?;
+                report.set_dc_error(e);
+            }
+            (res, _) => res?,
         }
 
         // This block is to reduce the lifetime of the groups map references
@@ -285,8 +878,69 @@ impl<'sto> MainDevice<'sto> {
             // one.
             let mut group_map = FnvIndexMap::<_, _, MAX_SUBDEVICES>::new();
 
-            while let Some(subdevice) = subdevices.pop_front() {
-                let group = group_filter(&groups, &subdevice)?;
+            while let Some(mut subdevice) = subdevices.pop_front() {
+                let Some(report) = report.as_deref_mut() else {
+                    let group = group_filter(&groups, &subdevice)?;
+
+                    // SAFETY: This mutates the internal SubDevice list, so a reference to `group` may not be
+                    // held over this line.
+                    unsafe { group.push(subdevice)? };
+
+                    group_map
+                        .insert(usize::from(group.id()), UnsafeCell::new(group))
+                        .map_err(|_| Error::Capacity(Item::Group))?;
+
+                    continue;
+                };
+
+                let configured_address = subdevice.configured_address();
+
+                let group = match group_filter(&groups, &subdevice) {
+                    Ok(group) => group,
+                    Err(e) => {
+                        let quarantined = report.quarantine(
+                            subdevice.index,
+                            configured_address,
+                            Some(subdevice),
+                            InitStage::GroupAssignment,
+                            e,
+                        )?;
+
+                        self.emit(InitEvent::Warning(InitWarning::Quarantined(quarantined)));
+
+                        continue;
+                    }
+                };
+
+                // Configure mailboxes before adding to a group so a failing SubDevice can be
+                // left out.
+                let res = SubDeviceRef::new(self, configured_address, &mut subdevice)
+                    .configure_mailboxes()
+                    .await;
+
+                if let Err(e) = res {
+                    // Best effort: the SubDevice may be unreachable
+                    let _ = SubDeviceRef::new(self, configured_address, ())
+                        .request_subdevice_state_nowait(SubDeviceState::Init)
+                        .await;
+
+                    let quarantined = report.quarantine(
+                        subdevice.index,
+                        configured_address,
+                        Some(subdevice),
+                        InitStage::Mailbox,
+                        e,
+                    )?;
+
+                    self.emit(InitEvent::Warning(InitWarning::Quarantined(quarantined)));
+
+                    continue;
+                }
+
+                self.emit(InitEvent::StateChanged {
+                    configured_address,
+                    state: SubDeviceState::PreOp,
+                });
 
                 // SAFETY: This mutates the internal SubDevice list, so a reference to `group` may not be
                 // held over this line.
@@ -302,20 +956,75 @@ impl<'sto> MainDevice<'sto> {
             for (id, group) in group_map.into_iter() {
                 let group = unsafe { *group.get() };
 
-                offset = group.as_ref().into_pre_op(offset, self).await?;
+                // Mailboxes were already configured for each SubDevice in tolerant mode
+                let group_start = offset.start_address;
+
+                offset = group
+                    .as_ref()
+                    .into_pre_op(offset, self, report.is_none())
+                    .await?;
+
+                self.emit(InitEvent::PdiAllocated {
+                    start_address: group_start,
+                    len: (offset.start_address - group_start) as usize,
+                });
 
                 fmt::debug!("After group ID {} offset: {:?}", id, offset);
             }
 
             fmt::debug!("Total PDI {} bytes", offset.start_address);
+
+            self.pdi_end.store(offset.start_address, Ordering::Relaxed);
         }
 
-        // Check that all SubDevices reached PRE-OP
-        self.wait_for_state(SubDeviceState::PreOp).await?;
+        // Check that all SubDevices reached PRE-OP. Quarantined and hot-connect SubDevices are left
+        // in INIT, so each healthy SubDevice has already been checked individually instead.
+        if report.is_none() && hot_connect_start.is_none() {
+            self.wait_for_state(SubDeviceState::PreOp).await?;
+        }
+
+        self.emit(InitEvent::Finished {
+            duration: init_observer::elapsed(start, now()),
+        });
 
         Ok(groups)
     }
 
+    /// Configure distributed clock offsets/propagation delays and perform static drift
+    /// compensation.
+    ///
+    /// We need the SubDevices in a single list so we can read the topology.
+    async fn configure_dc<const MAX_SUBDEVICES: usize>(
+        &self,
+        subdevices: &mut heapless::Deque<SubDevice, MAX_SUBDEVICES>,
+        now: impl Fn() -> u64 + Copy,
+    ) -> Result<(), Error> {
+        fmt::debug!("Configuring topology/distributed clocks");
+
+        let dc_master = dc::configure_dc(self, subdevices.as_mut_slices().0, now).await?;
+
+        let reference = dc_master
+            .as_ref()
+            .map(|dc_master| dc_master.configured_address());
+
+        // If there are SubDevices that support distributed clocks, run static drift compensation
+        if let Some(dc_master) = dc_master {
+            self.dc_reference_configured_address
+                .store(dc_master.configured_address(), Ordering::Relaxed);
+
+            dc::run_dc_static_sync(
+                self,
+                dc_master.configured_address(),
+                self.config.dc_static_sync_iterations,
+            )
+            .await?;
+        }
+
+        self.emit(InitEvent::DcConfigured { reference });
+
+        Ok(())
+    }
+
     /// A convenience method to allow the quicker creation of a single group containing all
     /// discovered SubDevices.
     ///
@@ -347,7 +1056,7 @@ impl<'sto> MainDevice<'sto> {
     /// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
     ///
     /// # async {
-    /// let group = maindevice
+    /// let mut group = maindevice
     ///     .init_single_group::<MAX_SUBDEVICES, MAX_PDI>(ethercat_now)
     ///     .await
     ///     .expect("Init");
@@ -408,8 +1117,229 @@ impl<'sto> MainDevice<'sto> {
             .await
     }
 
+    /// Bring SubDevices back after a cable break or power loss without re-initialising the whole
+    /// network.
+    ///
+    /// SubDevices are counted with a broadcast and each one is checked against the records held
+    /// in `groups`. Any SubDevice that has lost its configured address or is no longer in the
+    /// same state as its group is matched to its record by identity and serial number, then has
+    /// its configured address, mailboxes, FMMUs and Distributed Clock configuration restored
+    /// before being moved back into the group's current state.
+    ///
+    /// Returns the number of SubDevices that were recovered.
+    ///
+    /// The network must have the same SubDevices in the same order as when it was initialised.
+    /// If fewer SubDevices are present than were discovered by [`init`](MainDevice::init), an
+    /// [`Error::WorkingCounter`] is returned and nothing is changed. The groups are borrowed
+    /// mutably, so they cannot be used for process data exchange while recovery is in progress.
+    ///
+    /// A restored SubDevice's DC system time is aligned with the reference SubDevice using receive
+    /// times latched by one frame, followed by static drift compensation of
+    /// [`dc_static_sync_iterations`](crate::MainDeviceConfig::dc_static_sync_iterations) frames
+    /// before SYNC0 is enabled again.
+    ///
+    /// A SubDevice that matches no record, or only the record of another SubDevice that is still
+    /// healthy, fails recovery with [`Error::UnknownSubDevice`]. If its configured address is
+    /// already used by another SubDevice, [`Error::DuplicateAddress`] is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ethercrab::{
+    ///     error::Error, std::ethercat_now, MainDevice, MainDeviceConfig, PduStorage, Timeouts,
+    /// };
+    ///
+    /// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+    ///
+    /// # async fn case() -> Result<(), Error> {
+    /// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+    ///
+    /// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+    ///
+    /// let mut group = maindevice
+    ///     .init_single_group::<16, 64>(ethercat_now)
+    ///     .await?
+    ///     .into_op(&maindevice)
+    ///     .await?;
+    ///
+    /// loop {
+    ///     if let Err(Error::WorkingCounter { .. }) = group.tx_rx(&maindevice).await {
+    ///         match maindevice.recover(&mut [&mut group]).await {
+    ///             Ok(recovered) => log::info!("Recovered {} SubDevices", recovered),
+    ///             // Not all SubDevices are back yet
+    ///             Err(Error::WorkingCounter { .. }) => (),
+    ///             Err(e) => return Err(e),
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn recover(
+        &self,
+        groups: &mut [&mut dyn SubDeviceGroupHandle],
+    ) -> Result<usize, Error> {
+        let expected = self.num_subdevices.load(Ordering::Relaxed);
+        let present = self.count_subdevices().await?;
+
+        if present < expected {
+            return Err(Error::WorkingCounter {
+                expected,
+                received: present,
+            });
+        }
+
+        let mut recovered = 0;
+
+        for position in 0..expected {
+            if self.is_healthy(groups, position).await? {
+                continue;
+            }
+
+            fmt::info!("Recovering SubDevice at position {}", position);
+
+            // Give the SubDevice a temporary address so its identity can be read from EEPROM
+            let temporary_address = RECOVERY_ADDRESS.wrapping_add(position);
+
+            Command::apwr(position, RegisterAddress::ConfiguredStationAddress.into())
+                .send(self, temporary_address)
+                .await?;
+
+            let subdevice_ref = SubDeviceRef::new(self, temporary_address, ());
+
+            subdevice_ref.set_eeprom_mode(SiiOwner::Master).await?;
+
+            let identity = subdevice_ref.eeprom().identity().await?;
+
+            // SubDevices with the same identity are told apart by their Explicit Device ID, if
+            // it was read during init
+            let explicit_device_id = if groups.iter().any(|group| {
+                group.subdevices().iter().any(|subdevice| {
+                    subdevice.identity() == identity && subdevice.explicit_device_id.is_some()
+                })
+            }) {
+                subdevice_ref.explicit_device_id().await?
+            } else {
+                None
+            };
+
+            // Prefer the record at the same position in case several SubDevices share an identity.
+            // A record at another position is only taken over if the SubDevice there is gone or
+            // unhealthy, otherwise a live SubDevice's address would be given to this one.
+            let mut found = None;
+
+            'search: for (group_index, group) in groups.iter().enumerate() {
+                for (index, subdevice) in group.subdevices().iter().enumerate() {
+                    if subdevice.identity() != identity
+                        || subdevice.explicit_device_id != explicit_device_id
+                    {
+                        continue;
+                    }
+
+                    if subdevice.index == position {
+                        found = Some((group_index, index));
+
+                        break 'search;
+                    }
+
+                    if found.is_none()
+                        && (subdevice.index >= expected
+                            || !self.is_healthy(groups, subdevice.index).await?)
+                    {
+                        found = Some((group_index, index));
+                    }
+                }
+            }
+
+            let Some((group_index, index)) = found else {
+                fmt::error!(
+                    "SubDevice at position {} with identity {} does not match any known SubDevice",
+                    position,
+                    identity
+                );
+
+                return Err(Error::UnknownSubDevice);
+            };
+
+            let mut group = groups[group_index].as_mut();
+
+            let subdevice = &mut group.subdevices()[index];
+
+            if self.address_in_use(subdevice.configured_address()).await? {
+                fmt::error!(
+                    "Address {:#06x} for SubDevice at position {} is already in use",
+                    subdevice.configured_address(),
+                    position
+                );
+
+                return Err(Error::DuplicateAddress {
+                    configured_address: subdevice.configured_address(),
+                });
+            }
+
+            subdevice.index = position;
+
+            Command::apwr(position, RegisterAddress::ConfiguredStationAddress.into())
+                .send(self, subdevice.configured_address())
+                .await?;
+
+            if let Some(reference) = self.dc_ref_address().filter(|reference| {
+                subdevice.dc_support().any() && *reference != subdevice.configured_address()
+            }) {
+                dc::restore_dc_parameters(self, subdevice, reference).await?;
+            }
+
+            group.restore_subdevice(self, index).await?;
+
+            recovered += 1;
+        }
+
+        Ok(recovered)
+    }
+
+    /// Whether the SubDevice at `position` has the configured address of the record for that
+    /// position in `groups`, and is in its group's state without an error.
+    async fn is_healthy(
+        &self,
+        groups: &[&mut dyn SubDeviceGroupHandle],
+        position: u16,
+    ) -> Result<bool, Error> {
+        let configured_address =
+            Command::aprd(position, RegisterAddress::ConfiguredStationAddress.into())
+                .receive::<u16>(self)
+                .await?;
+
+        let status = Command::aprd(position, RegisterAddress::AlStatus.into())
+            .receive::<AlControl>(self)
+            .await?;
+
+        Ok(groups.iter().any(|group| {
+            let state = group.as_ref().state();
+
+            group.subdevices().iter().any(|subdevice| {
+                subdevice.configured_address() == configured_address
+                    && subdevice.index == position
+                    && status.state == state
+                    && !status.error
+            })
+        }))
+    }
+
+    /// Work out where the network is broken by comparing it to a previously captured topology.
+    ///
+    /// The DL status of every SubDevice in `topology` is read to find ports that have lost their
+    /// link, and SubDevices that can no longer be reached are traced back to the connection they
+    /// hang off. This is useful after a working counter error to tell a technician which
+    /// SubDevice and port to check. See [`LineBreakMonitor`](crate::LineBreakMonitor) to run it
+    /// automatically.
+    pub async fn locate_line_break<const MAX_SUBDEVICES: usize>(
+        &self,
+        topology: &NetworkTopology<MAX_SUBDEVICES>,
+    ) -> Result<LineBreakReport<MAX_SUBDEVICES>, Error> {
+        LineBreakReport::diagnose(self, topology).await
+    }
+
     /// Count the number of SubDevices on the network.
-    async fn count_subdevices(&self) -> Result<u16, Error> {
+    pub(crate) async fn count_subdevices(&self) -> Result<u16, Error> {
         Command::brd(RegisterAddress::Type.into())
             .receive_wkc::<u8>(self)
             .await
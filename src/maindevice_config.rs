@@ -1,5 +1,7 @@
 //! Configuration passed to [`MainDevice`](crate::MainDevice).
 
+use crate::{BASE_SUBDEVICE_ADDRESS, SubDeviceIdentity};
+
 /// Configuration passed to [`MainDevice`](crate::MainDevice).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct MainDeviceConfig {
@@ -13,6 +15,43 @@ pub struct MainDeviceConfig {
 
     /// EtherCAT packet (PDU) network retry behaviour.
     pub retry_behaviour: RetryBehaviour,
+
+    /// Read PDO entry names and data types from each SubDevice's EEPROM when configuring the PDI,
+    /// so entries can be accessed by name with e.g. [`SubDeviceRef::input`](crate::SubDeviceRef::input).
+    ///
+    /// Names given in a [`PdoMapping`](crate::PdoMapping) are always available, regardless of
+    /// this option.
+    ///
+    /// Defaults to `false` as this requires extra EEPROM reads, increasing startup time. Has no
+    /// effect if the `pdi-symbols` feature is disabled.
+    pub pdi_symbol_names: bool,
+
+    /// What to do if a SubDevice's EEPROM configuration area checksum is invalid when the
+    /// SubDevice is discovered.
+    ///
+    /// Defaults to [`EepromChecksumBehaviour::Ignore`].
+    pub eeprom_checksum: EepromChecksumBehaviour,
+
+    /// The maximum number of SubDevices to read identity, name and other EEPROM data from at the
+    /// same time during [`MainDevice::init`](crate::MainDevice::init).
+    ///
+    /// Reading many SubDevices concurrently can significantly reduce startup time for large
+    /// networks, but requires at least as many free PDU frames in the
+    /// [`PduStorage`](crate::PduStorage) as this value.
+    ///
+    /// This option has no effect without the `std` feature, as in-flight reads are heap allocated.
+    /// SubDevices are always read one after the other in `no_std` builds, and a warning is logged
+    /// if this is set to more than `1`.
+    ///
+    /// Defaults to `1`, reading SubDevices one after the other.
+    pub discovery_concurrency: usize,
+
+    /// How to choose each SubDevice's configured station address during
+    /// [`MainDevice::init`](crate::MainDevice::init), and when a
+    /// [`HotConnectGroup`](crate::HotConnectGroup) segment is connected.
+    ///
+    /// Defaults to [`AddressingStrategy::Position`].
+    pub addressing: AddressingStrategy,
 }
 
 impl Default for MainDeviceConfig {
@@ -20,6 +59,10 @@ impl Default for MainDeviceConfig {
         Self {
             dc_static_sync_iterations: 10_000,
             retry_behaviour: RetryBehaviour::default(),
+            pdi_symbol_names: false,
+            eeprom_checksum: EepromChecksumBehaviour::default(),
+            discovery_concurrency: 1,
+            addressing: AddressingStrategy::default(),
         }
     }
 }
@@ -50,6 +93,95 @@ pub enum RetryBehaviour {
     Forever,
 }
 
+/// How to handle an invalid EEPROM configuration area checksum.
+///
+/// The checksum covers the first 7 words of the EEPROM, which the ESC loads into its registers on
+/// power up. A mismatch usually means the EEPROM is corrupt, which can lead to confusing errors
+/// later on such as incorrect PDI sizes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum EepromChecksumBehaviour {
+    /// Do not read or check the checksum (default).
+    #[default]
+    Ignore,
+
+    /// Log a warning if the checksum is invalid, then continue initialisation.
+    Warn,
+
+    /// Fail initialisation with
+    /// [`EepromError::Checksum`](crate::error::EepromError::Checksum) if the checksum is invalid.
+    Error,
+}
+
+/// How to assign configured station addresses to SubDevices.
+///
+/// Every SubDevice is first given a position-based address so its identity and alias can be read.
+/// Other strategies then readdress each SubDevice before any further configuration. If two
+/// SubDevices end up with the same address, [`MainDevice::init`](crate::MainDevice::init) fails
+/// with [`Error::DuplicateAddress`](crate::error::Error::DuplicateAddress).
+#[derive(Debug, Copy, Clone, Default)]
+pub enum AddressingStrategy {
+    /// Address SubDevices by their position in the network, starting from `0x1000` (default).
+    ///
+    /// Addresses change if SubDevices are added or removed upstream.
+    #[default]
+    Position,
+
+    /// Use each SubDevice's station alias, read from its EEPROM, as its configured address.
+    ///
+    /// SubDevices with no alias (an alias of zero) keep their position-based address.
+    Alias,
+
+    /// Use each SubDevice's Explicit Device ID, e.g. set with rotary switches, as its configured
+    /// address.
+    ///
+    /// SubDevices that do not support Explicit Device ID, or report an ID of zero, keep their
+    /// position-based address. See
+    /// [`SubDeviceRef::explicit_device_id`](crate::SubDeviceRef::explicit_device_id) for how the
+    /// ID is read.
+    ExplicitDeviceId,
+
+    /// Compute each SubDevice's address from its identity and position in the network, starting
+    /// from zero.
+    Custom(fn(SubDeviceIdentity, u16) -> u16),
+}
+
+impl PartialEq for AddressingStrategy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Position, Self::Position)
+            | (Self::Alias, Self::Alias)
+            | (Self::ExplicitDeviceId, Self::ExplicitDeviceId) => true,
+            (Self::Custom(a), Self::Custom(b)) => core::ptr::fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for AddressingStrategy {}
+
+impl AddressingStrategy {
+    /// Compute the configured address for the SubDevice at `position`.
+    pub(crate) fn address(
+        &self,
+        identity: SubDeviceIdentity,
+        alias: u16,
+        explicit_device_id: Option<u16>,
+        position: u16,
+    ) -> u16 {
+        let positional = BASE_SUBDEVICE_ADDRESS.wrapping_add(position);
+
+        match self {
+            AddressingStrategy::Position => positional,
+            AddressingStrategy::Alias if alias != 0 => alias,
+            AddressingStrategy::Alias => positional,
+            AddressingStrategy::ExplicitDeviceId => explicit_device_id
+                .filter(|id| *id != 0)
+                .unwrap_or(positional),
+            AddressingStrategy::Custom(f) => f(identity, position),
+        }
+    }
+}
+
 impl RetryBehaviour {
     pub(crate) const fn retry_count(&self) -> usize {
         match self {
@@ -65,6 +197,48 @@ impl RetryBehaviour {
 mod tests {
     use super::*;
 
+    #[test]
+    fn addressing() {
+        let identity = SubDeviceIdentity {
+            vendor_id: 2,
+            product_id: 0x0b0c3052,
+            revision: 0x00110000,
+            serial: 0,
+        };
+
+        assert_eq!(
+            AddressingStrategy::Position.address(identity, 0x0005, Some(7), 2),
+            0x1002
+        );
+        assert_eq!(
+            AddressingStrategy::Alias.address(identity, 0x0005, Some(7), 2),
+            0x0005
+        );
+        assert_eq!(
+            AddressingStrategy::Alias.address(identity, 0, None, 2),
+            0x1002
+        );
+        assert_eq!(
+            AddressingStrategy::ExplicitDeviceId.address(identity, 0x0005, Some(7), 2),
+            0x0007
+        );
+        assert_eq!(
+            AddressingStrategy::ExplicitDeviceId.address(identity, 0x0005, Some(0), 2),
+            0x1002
+        );
+        assert_eq!(
+            AddressingStrategy::ExplicitDeviceId.address(identity, 0x0005, None, 2),
+            0x1002
+        );
+        assert_eq!(
+            AddressingStrategy::Custom(
+                |identity, position| identity.vendor_id as u16 * 0x100 + position
+            )
+            .address(identity, 0x0005, None, 2),
+            0x0202
+        );
+    }
+
     #[test]
     fn retry_count_sanity_check() {
         assert_eq!(RetryBehaviour::None.retry_count(), 0);
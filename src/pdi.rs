@@ -3,13 +3,14 @@ use core::ops::Range;
 /// An accumulator that stores the bit and byte offsets in the PDI so SubDevice IO data can be mapped
 /// to/from the PDI using FMMUs.
 ///
-/// PDI mappings are byte-aligned per each SubDevice.
+/// PDI mappings are byte-aligned per each SubDevice unless the group uses
+/// [`PdiLayout::BitPacked`](crate::subdevice_group::PdiLayout::BitPacked).
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PdiOffset {
     pub start_address: u32,
-    // // Unused, but will become useful if we support bit-packed PDI mappings in the future.
-    // start_bit: u8,
+    /// Start bit 0-7 in the byte at `start_address`. Always zero for byte-aligned PDIs.
+    pub start_bit: u8,
 }
 
 impl PdiOffset {
@@ -25,57 +26,106 @@ impl PdiOffset {
     }
 
     /// Common code shared between byte and bit aligned public methods.
-    fn increment_inner(self, _inc_bits: u16, inc_bytes: u16) -> Self {
-        // // Bit count overflows a byte, so move into the next byte's bits by incrementing the byte
-        // // index one more.
-        // let start_bit = if u16::from(self.start_bit) + inc_bits >= 8 {
-        //     inc_bytes += 1;
+    fn increment_inner(self, inc_bits: u16, mut inc_bytes: u16) -> Self {
+        // Bit count overflows a byte, so move into the next byte's bits by incrementing the byte
+        // index one more.
+        let start_bit = if u16::from(self.start_bit) + inc_bits >= 8 {
+            inc_bytes += 1;
 
-        //     ((u16::from(self.start_bit) + inc_bits) % 8) as u8
-        // } else {
-        //     self.start_bit + inc_bits as u8
-        // };
+            ((u16::from(self.start_bit) + inc_bits) % 8) as u8
+        } else {
+            self.start_bit + inc_bits as u8
+        };
 
         Self {
             start_address: self.start_address + u32::from(inc_bytes),
-            // start_bit,
+            start_bit,
         }
     }
 
-    // /// Compute end bit 0-7 in the final byte of the mapped PDI section.
-    // fn end_bit(self, bits: u16) -> u8 {
-    //     // SAFETY: The modulos here and in `increment` mean that all value can comfortably fit in a
-    //     // u8, so all the `as` and non-checked `+` here are fine.
+    /// Compute end bit 0-7 in the final byte of the mapped PDI section.
+    pub fn end_bit(self, bits: u16) -> u8 {
+        // SAFETY: The modulos here and in `increment` mean that all value can comfortably fit in a
+        // u8, so all the `as` and non-checked `+` here are fine.
 
-    //     let bits = (bits.saturating_sub(1) % 8) as u8;
+        let bits = (bits.saturating_sub(1) % 8) as u8;
 
-    //     self.start_bit + bits % 8
-    // }
+        (self.start_bit + bits) % 8
+    }
+
+    /// Move to the start of the next byte if this offset is not already byte-aligned.
+    pub fn align_to_byte(self) -> Self {
+        if self.start_bit > 0 {
+            Self {
+                start_address: self.start_address + 1,
+                start_bit: 0,
+            }
+        } else {
+            self
+        }
+    }
 
     /// Compute an index range between this offset (inclusive) and another (exclusive).
+    ///
+    /// If `other` is not byte-aligned, the range includes the partially used final byte.
     pub fn up_to(self, other: Self) -> Range<usize> {
-        self.start_address as usize..other.start_address as usize
+        self.start_address as usize..other.align_to_byte().start_address as usize
     }
 
-    // Maybe one day we support packed PDIs. In that instance, uncomment this and the tests below.
-    // DO NOT DELETE as part of cleanup
-    // /// Increment, calculating values for _next_ mapping when the struct is read after increment.
-    // fn increment(self, bits: u16) -> Self {
-    //     let inc_bytes = bits / 8;
-    //     let inc_bits = bits % 8;
+    /// The number of bits between this offset and another, later offset.
+    pub fn bits_to(self, other: Self) -> u32 {
+        (other.start_address * 8 + u32::from(other.start_bit))
+            - (self.start_address * 8 + u32::from(self.start_bit))
+    }
 
-    //     self.increment_inner(inc_bits, inc_bytes)
-    // }
+    /// Increment, calculating values for _next_ mapping when the struct is read after increment.
+    pub fn increment_bits(self, bits: u16) -> Self {
+        let inc_bytes = bits / 8;
+        let inc_bits = bits % 8;
+
+        self.increment_inner(inc_bits, inc_bytes)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PdiSegment {
     pub bytes: Range<usize>,
-    // pub bit_len: usize,
+    /// Start bit 0-7 of this segment's data in the first byte of `bytes`.
+    pub bit_offset: u8,
+    /// Length of this segment's data in bits.
+    pub bit_len: usize,
 }
 
 impl PdiSegment {
+    /// Create a byte-aligned segment covering the given bytes.
+    #[cfg(test)]
+    pub fn from_bytes(bytes: Range<usize>) -> Self {
+        Self {
+            bit_len: bytes.len() * 8,
+            bytes,
+            bit_offset: 0,
+        }
+    }
+
+    /// Create a segment covering all bits between two offsets.
+    pub fn between(start: PdiOffset, end: PdiOffset) -> Self {
+        Self {
+            bytes: start.up_to(end),
+            bit_offset: start.start_bit,
+            bit_len: start.bits_to(end) as usize,
+        }
+    }
+
+    /// Move this segment's byte range so it is relative to the given PDI start address.
+    pub fn relative_to(self, start_address: u32) -> Self {
+        Self {
+            bytes: (self.bytes.start - start_address as usize)
+                ..(self.bytes.end - start_address as usize),
+            ..self
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.bytes.len()
     }
@@ -95,6 +145,83 @@ impl core::fmt::Display for PdiSegment {
     }
 }
 
+/// Copy `bit_len` bits starting at `bit_offset` in `src` into the start of `dst`.
+///
+/// Bits are numbered LSB first, as they are on the wire. `dst` is not cleared first.
+pub fn read_bits(
+    src: &[u8],
+    bit_offset: usize,
+    bit_len: usize,
+    dst: &mut [u8],
+) -> Result<(), ethercrab_wire::WireError> {
+    if (bit_offset + bit_len).div_ceil(8) > src.len() {
+        return Err(ethercrab_wire::WireError::ReadBufferTooShort);
+    }
+
+    if bit_len.div_ceil(8) > dst.len() {
+        return Err(ethercrab_wire::WireError::WriteBufferTooShort);
+    }
+
+    // Fast path for whole, byte-aligned values
+    if bit_offset % 8 == 0 && bit_len % 8 == 0 {
+        let start = bit_offset / 8;
+        let len = bit_len / 8;
+
+        dst[0..len].copy_from_slice(&src[start..(start + len)]);
+
+        return Ok(());
+    }
+
+    for i in 0..bit_len {
+        let src_bit = bit_offset + i;
+
+        let bit = (src[src_bit / 8] >> (src_bit % 8)) & 1;
+
+        dst[i / 8] = (dst[i / 8] & !(1 << (i % 8))) | (bit << (i % 8));
+    }
+
+    Ok(())
+}
+
+/// Copy the first `bit_len` bits of `src` into `dst` starting at `bit_offset`, leaving all other
+/// bits in `dst` untouched.
+///
+/// Bits are numbered LSB first, as they are on the wire.
+pub fn write_bits(
+    dst: &mut [u8],
+    bit_offset: usize,
+    bit_len: usize,
+    src: &[u8],
+) -> Result<(), ethercrab_wire::WireError> {
+    if bit_len.div_ceil(8) > src.len() {
+        return Err(ethercrab_wire::WireError::ReadBufferTooShort);
+    }
+
+    if (bit_offset + bit_len).div_ceil(8) > dst.len() {
+        return Err(ethercrab_wire::WireError::WriteBufferTooShort);
+    }
+
+    // Fast path for whole, byte-aligned values
+    if bit_offset % 8 == 0 && bit_len % 8 == 0 {
+        let start = bit_offset / 8;
+        let len = bit_len / 8;
+
+        dst[start..(start + len)].copy_from_slice(&src[0..len]);
+
+        return Ok(());
+    }
+
+    for i in 0..bit_len {
+        let dst_bit = bit_offset + i;
+
+        let bit = (src[i / 8] >> (i % 8)) & 1;
+
+        dst[dst_bit / 8] = (dst[dst_bit / 8] & !(1 << (dst_bit % 8))) | (bit << (dst_bit % 8));
+    }
+
+    Ok(())
+}
+
 // impl PdiSegment {
 //     /// If this segment contains less than 8 bits, this method will calculate the bit mask for the
 //     /// used bits.
@@ -113,18 +240,61 @@ mod tests {
 
         let input = input.increment_byte_aligned(4);
 
-        assert_eq!(input, PdiOffset { start_address: 1 }, "first increment");
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 1,
+                start_bit: 0
+            },
+            "first increment"
+        );
 
         let input = input.increment_byte_aligned(4);
 
-        assert_eq!(input, PdiOffset { start_address: 2 }, "second increment");
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 2,
+                start_bit: 0
+            },
+            "second increment"
+        );
+    }
+
+    #[test]
+    fn bits_roundtrip() {
+        let mut pdi = [0xffu8, 0x00, 0xff];
+
+        // Straddle the first two bytes
+        write_bits(&mut pdi, 6, 4, &[0b1010]).unwrap();
+
+        assert_eq!(pdi, [0b1011_1111, 0b0000_0010, 0xff]);
+
+        let mut value = [0u8; 1];
+
+        read_bits(&pdi, 6, 4, &mut value).unwrap();
+
+        assert_eq!(value, [0b1010]);
+
+        // Byte aligned
+        write_bits(&mut pdi, 8, 16, &[0xaa, 0xbb]).unwrap();
+
+        assert_eq!(pdi, [0b1011_1111, 0xaa, 0xbb]);
+
+        assert_eq!(
+            read_bits(&pdi, 20, 8, &mut value),
+            Err(ethercrab_wire::WireError::ReadBufferTooShort)
+        );
     }
 
     #[test]
     #[cfg_attr(miri, ignore)]
     fn fuzz_pdi_segment() {
         heckcheck::check(|(start_address, incr_bits): (u32, u16)| {
-            let offset = PdiOffset { start_address };
+            let offset = PdiOffset {
+                start_address,
+                start_bit: 0,
+            };
 
             let new = offset.increment_byte_aligned(incr_bits);
 
@@ -142,91 +312,99 @@ mod tests {
         });
     }
 
-    // Maybe one day we support packed PDIs. DO NOT DELETE as part of cleanup.
-    // #[test]
-    // fn size_bytes() {
-    //     // E.g. 2x EL2004, 1x EL1004
-    //     let input = MappingOffset::default()
-    //         .increment(4)
-    //         .increment(4)
-    //         .increment(4);
+    #[test]
+    fn size_bytes() {
+        // E.g. 2x EL2004, 1x EL1004
+        let input = PdiOffset::default()
+            .increment_bits(4)
+            .increment_bits(4)
+            .increment_bits(4);
+
+        assert_eq!(PdiOffset::default().up_to(input), 0..2);
+        assert_eq!(PdiOffset::default().bits_to(input), 12);
+    }
 
-    //     assert_eq!(input.size_bytes(), 2);
-    // }
+    #[test]
+    fn simulate_2_el2004() {
+        let input = PdiOffset::default();
 
-    // #[test]
-    // fn simulate_2_el2004() {
-    //     let input = MappingOffset::default();
-
-    //     let input = input.increment(4);
-
-    //     assert_eq!(
-    //         input,
-    //         MappingOffset {
-    //             start_address: 0,
-    //             start_bit: 4
-    //         }
-    //     );
-
-    //     let input = input.increment(4);
-
-    //     assert_eq!(
-    //         input,
-    //         MappingOffset {
-    //             start_address: 1,
-    //             start_bit: 0
-    //         }
-    //     );
-    // }
+        let input = input.increment_bits(4);
+
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 0,
+                start_bit: 4
+            }
+        );
+
+        let input = input.increment_bits(4);
+
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 1,
+                start_bit: 0
+            }
+        );
+    }
 
-    // #[test]
-    // fn end_bit() {
-    //     let input = MappingOffset::default();
+    #[test]
+    fn end_bit() {
+        let input = PdiOffset::default();
 
-    //     assert_eq!(input.end_bit(4), 3);
+        assert_eq!(input.end_bit(4), 3);
 
-    //     let input = input.increment(4);
+        let input = input.increment_bits(4);
 
-    //     assert_eq!(input.end_bit(4), 7);
+        assert_eq!(input.end_bit(4), 7);
 
-    //     let input = input.increment(4);
+        let input = input.increment_bits(4);
 
-    //     assert_eq!(input.end_bit(4), 3);
-    // }
+        assert_eq!(input.end_bit(4), 3);
+    }
 
-    // #[test]
-    // fn zero_length_end_bit() {
-    //     let input = MappingOffset::default();
+    #[test]
+    fn zero_length_end_bit() {
+        let input = PdiOffset::default();
 
-    //     assert_eq!(input.end_bit(0), 0);
+        assert_eq!(input.end_bit(0), 0);
 
-    //     let input = input.increment(4);
+        let input = input.increment_bits(4);
 
-    //     assert_eq!(input.end_bit(0), 4);
-    // }
+        assert_eq!(input.end_bit(0), 4);
+    }
 
-    // #[test]
-    // fn cross_boundary() {
-    //     let input = MappingOffset::default();
-
-    //     let input = input.increment(6);
-
-    //     assert_eq!(
-    //         input,
-    //         MappingOffset {
-    //             start_address: 0,
-    //             start_bit: 6
-    //         }
-    //     );
-
-    //     let input = input.increment(6);
-
-    //     assert_eq!(
-    //         input,
-    //         MappingOffset {
-    //             start_address: 1,
-    //             start_bit: 4
-    //         }
-    //     );
-    // }
+    #[test]
+    fn cross_boundary() {
+        let input = PdiOffset::default();
+
+        let input = input.increment_bits(6);
+
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 0,
+                start_bit: 6
+            }
+        );
+
+        let input = input.increment_bits(6);
+
+        assert_eq!(
+            input,
+            PdiOffset {
+                start_address: 1,
+                start_bit: 4
+            }
+        );
+
+        assert_eq!(
+            input.align_to_byte(),
+            PdiOffset {
+                start_address: 2,
+                start_bit: 0
+            }
+        );
+    }
 }
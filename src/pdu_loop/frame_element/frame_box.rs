@@ -148,6 +148,11 @@ impl<'sto> FrameBox<'sto> {
         }
     }
 
+    /// Set the source MAC address of the Ethernet frame.
+    pub fn set_src_addr(&mut self, addr: EthernetAddress) {
+        self.ethernet_frame_mut().set_src_addr(addr);
+    }
+
     pub fn ethernet_frame(&self) -> EthernetFrame<&[u8]> {
         unsafe {
             EthernetFrame::new_unchecked(core::slice::from_raw_parts(
@@ -46,6 +46,9 @@ pub enum FrameState {
     RxDone = 6,
     /// The frame TX/RX is complete, but the frame memory is still held by calling code.
     RxProcessing = 7,
+    /// With cable redundancy, one half of a frame that was looped back at a ring break has been
+    /// merged into the frame. The frame is waiting for the other half.
+    RxPartial = 8,
 }
 
 /// An individual frame state, PDU header config, and data buffer.
@@ -196,8 +199,18 @@ impl<const N: usize> FrameElement<N> {
         unsafe { Self::swap_state(this, FrameState::Sendable, FrameState::Sending) }.ok()
     }
 
-    unsafe fn claim_receiving(this: NonNull<FrameElement<N>>) -> Option<NonNull<FrameElement<N>>> {
+    /// Claim a frame for receiving.
+    ///
+    /// Returns `true` alongside the frame if it already holds one half of a redundant response.
+    unsafe fn claim_receiving(
+        this: NonNull<FrameElement<N>>,
+    ) -> Option<(NonNull<FrameElement<N>>, bool)> {
         unsafe { Self::swap_state(this, FrameState::Sent, FrameState::RxBusy) }
+            .map(|this| (this, false))
+            .or_else(|_| {
+                unsafe { Self::swap_state(this, FrameState::RxPartial, FrameState::RxBusy) }
+                    .map(|this| (this, true))
+            })
             .map_err(|actual_state| {
                 fmt::error!(
                     "Failed to claim receiving frame {}: expected state {:?}, but got {:?}",
@@ -13,6 +13,8 @@ use futures_lite::FutureExt;
 #[derive(Debug)]
 pub struct ReceivingFrame<'sto> {
     inner: FrameBox<'sto>,
+    /// Whether one half of a redundant response has already been merged into the frame.
+    partial: bool,
 }
 
 impl<'sto> ReceivingFrame<'sto> {
@@ -21,13 +23,35 @@ impl<'sto> ReceivingFrame<'sto> {
         pdu_idx: &'sto AtomicU8,
         frame_data_len: usize,
     ) -> Option<Self> {
-        let frame = unsafe { FrameElement::claim_receiving(frame)? };
+        let (frame, partial) = unsafe { FrameElement::claim_receiving(frame)? };
 
         Some(Self {
             inner: FrameBox::new(frame, pdu_idx, frame_data_len),
+            partial,
         })
     }
 
+    /// Whether one half of a redundant response has already been merged into the frame.
+    pub(in crate::pdu_loop) fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    /// Mark the frame as holding one half of a redundant response, ready for the other half to be
+    /// merged in.
+    pub(in crate::pdu_loop) fn mark_partial(&self) -> Result<(), PduError> {
+        self.inner
+            .swap_state(FrameState::RxBusy, FrameState::RxPartial)
+            .map_err(|bad| {
+                fmt::error!(
+                    "Failed to set frame {:#04x} state from RxBusy -> RxPartial, got {:?}",
+                    self.storage_slot_index(),
+                    bad
+                );
+
+                PduError::InvalidFrameState
+            })
+    }
+
     /// Mark the frame as fully received.
     ///
     /// This method may only be called once the frame response (header and data) has been validated
@@ -151,7 +175,8 @@ impl<'sto> Future for ReceiveFrameFut<'sto> {
                     self.retries_left
                 );
 
-                if self.retries_left == 0 {
+                // The frame now holds merged response data, so it can't be sent again
+                if self.retries_left == 0 || was == FrameState::RxPartial {
                     // Release frame and PDU slots for reuse
                     Self::release(rxin);
 
@@ -181,7 +206,11 @@ impl<'sto> Future for ReceiveFrameFut<'sto> {
         }
 
         match was {
-            FrameState::Sendable | FrameState::Sending | FrameState::Sent | FrameState::RxBusy => {
+            FrameState::Sendable
+            | FrameState::Sending
+            | FrameState::Sent
+            | FrameState::RxBusy
+            | FrameState::RxPartial => {
                 self.frame = Some(rxin);
 
                 Poll::Pending
@@ -1,10 +1,12 @@
 use crate::{
+    MAINDEVICE_ADDR, REDUNDANT_MAINDEVICE_ADDR, RedundantPort,
     error::Error,
     ethernet::EthernetFrame,
     fmt,
     pdu_loop::{
         frame_element::{FrameBox, FrameElement, FrameState},
         frame_header::EthercatFrameHeader,
+        pdu_header::any_position_addressed,
     },
 };
 use core::{ptr::NonNull, sync::atomic::AtomicU8};
@@ -131,4 +133,70 @@ impl<'sto> SendableFrame<'sto> {
             }
         }
     }
+
+    /// Send the frame on both interfaces of a redundant ring using a blocking callback.
+    ///
+    /// The closure is called once for each [`RedundantPort`] and must return the number of bytes
+    /// sent over that interface. The copy passed for [`RedundantPort::Redundant`] has a different
+    /// source address so responses can be told apart by
+    /// [`PduRx::receive_frame_redundant`](crate::PduRx::receive_frame_redundant).
+    ///
+    /// The frame is marked as sent if it was completely sent on at least one interface, so an
+    /// interface without a link may return an error. If neither send succeeds, the error from the
+    /// main interface is returned.
+    ///
+    /// Frames containing position addressed PDUs, e.g. `APRD` or `APWR`, are only sent on the main
+    /// interface. Positions are counted from the interface a frame enters the ring, so a copy sent
+    /// from the redundant interface of a broken ring would address different SubDevices.
+    pub fn send_blocking_redundant(
+        mut self,
+        mut send: impl FnMut(RedundantPort, &[u8]) -> Result<usize, Error>,
+    ) -> Result<usize, Error> {
+        let len = self.as_bytes().len();
+
+        let check = |res: Result<usize, Error>| match res {
+            Ok(bytes_sent) if bytes_sent == len => Ok(bytes_sent),
+            Ok(bytes_sent) => Err(Error::PartialSend {
+                len,
+                sent: bytes_sent,
+            }),
+            Err(e) => Err(e),
+        };
+
+        let main = check(send(RedundantPort::Main, self.as_bytes()));
+
+        if any_position_addressed(self.inner.pdu_buf()) {
+            return match main {
+                Ok(bytes_sent) => {
+                    self.mark_sent();
+
+                    Ok(bytes_sent)
+                }
+                Err(e) => {
+                    self.release_sending_claim();
+
+                    Err(e)
+                }
+            };
+        }
+
+        self.inner.set_src_addr(REDUNDANT_MAINDEVICE_ADDR);
+
+        let redundant = check(send(RedundantPort::Redundant, self.as_bytes()));
+
+        self.inner.set_src_addr(MAINDEVICE_ADDR);
+
+        match (main, redundant) {
+            (Ok(bytes_sent), _) | (_, Ok(bytes_sent)) => {
+                self.mark_sent();
+
+                Ok(bytes_sent)
+            }
+            (Err(e), Err(_)) => {
+                self.release_sending_claim();
+
+                Err(e)
+            }
+        }
+    }
 }
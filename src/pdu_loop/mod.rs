@@ -9,7 +9,7 @@ pub mod storage;
 
 use crate::{command::Command, error::Error, pdu_loop::storage::PduStorageRef};
 use core::sync::atomic::Ordering;
-pub use pdu_rx::PduRx;
+pub use pdu_rx::{PduRx, RedundantPort};
 // NOTE: Allowing unused because `ReceiveAction` isn't used when `xdp` is not enabled.
 #[allow(unused)]
 pub use pdu_rx::ReceiveAction;
@@ -301,6 +301,208 @@ mod tests {
         cassette::block_on(poller);
     }
 
+    /// Send `data` with `command` on a redundant ring with a break, returning the merged working
+    /// counter and data.
+    ///
+    /// Each half sets one byte of the data to the given value and adds the given working counter.
+    fn merge_broken_ring(
+        command: Command,
+        data: [u8; 4],
+        main_response: (usize, u8, u16),
+        redundant_response: (usize, u8, u16),
+    ) -> (u16, Vec<u8>) {
+        crate::test_logger();
+
+        const FRAME_OVERHEAD: usize = 28;
+
+        let storage = PduStorage::<1, 128>::new();
+
+        let (mut tx, mut rx, pdu_loop) = storage.try_split().unwrap();
+
+        let mut result = None;
+
+        let poller = poll_fn(|ctx| {
+            let mut frame = pdu_loop.storage.alloc_frame().expect("Frame alloc");
+
+            let handle = frame.push_pdu(command, data, None).expect("Push PDU");
+
+            let mut frame_fut = pin!(frame.mark_sendable(&pdu_loop, MAX_TIMEOUT, usize::MAX));
+
+            assert!(frame_fut.as_mut().poll(ctx).is_pending());
+
+            let mut sent = Vec::new();
+
+            tx.next_sendable_frame()
+                .expect("need a frame")
+                .send_blocking_redundant(|port, bytes| {
+                    sent.push((port, bytes.to_vec()));
+
+                    Ok(bytes.len())
+                })
+                .expect("send");
+
+            let [(_, main), (_, redundant)] = sent.as_slice() else {
+                panic!("expected two copies, got {:?}", sent);
+            };
+
+            assert_eq!(main.len(), FRAME_OVERHEAD + data.len());
+            assert_eq!(main[6..12], [0x10, 0x10, 0x10, 0x10, 0x10, 0x10]);
+            assert_eq!(redundant[6..12], [0x10, 0x10, 0x10, 0x10, 0x10, 0x11]);
+
+            // Each copy is looped back at the break, processed by the SubDevices on its side
+            let respond = |sent: &[u8], (index, value, wkc): (usize, u8, u16)| {
+                let mut frame = EthernetFrame::new_checked(sent.to_vec()).unwrap();
+                let mut src = frame.src_addr();
+                src.0[0] |= 0x02;
+                frame.set_src_addr(src);
+                let mut packet = frame.into_inner();
+                let data_start = FRAME_OVERHEAD - 2;
+                packet[data_start + index] = value;
+                packet[data_start + data.len()..].copy_from_slice(&wkc.to_le_bytes());
+                packet
+            };
+
+            let main_half = respond(main, main_response);
+            let redundant_half = respond(redundant, redundant_response);
+
+            // Unprocessed copy passing through the ring to the other interface
+            assert_eq!(
+                rx.receive_frame_redundant(redundant, crate::RedundantPort::Main),
+                Ok(crate::ReceiveAction::Ignored)
+            );
+
+            assert_eq!(
+                rx.receive_frame_redundant(&main_half, crate::RedundantPort::Main),
+                Ok(crate::ReceiveAction::Processed)
+            );
+
+            assert!(frame_fut.as_mut().poll(ctx).is_pending());
+
+            assert_eq!(
+                rx.receive_frame_redundant(&redundant_half, crate::RedundantPort::Redundant),
+                Ok(crate::ReceiveAction::Processed)
+            );
+
+            match frame_fut.poll(ctx) {
+                Poll::Ready(Ok(frame)) => {
+                    let response = frame.first_pdu(handle).expect("Handle");
+
+                    result = Some((response.working_counter, response.deref().to_vec()));
+                }
+                Poll::Ready(other) => panic!("Expected Ready(Ok()), got {:?}", other),
+                Poll::Pending => panic!("frame future still pending"),
+            }
+
+            Poll::Ready(())
+        });
+
+        cassette::block_on(poller);
+
+        result.expect("no response")
+    }
+
+    #[test]
+    fn redundant_broken_ring_merge() {
+        let (wkc, data) = merge_broken_ring(
+            Command::lrw(0x1000).into(),
+            [0xaa, 0x00, 0x00, 0x00],
+            (1, 0x11, 1),
+            (2, 0x22, 2),
+        );
+
+        assert_eq!(wkc, 3);
+        assert_eq!(data, &[0xaa, 0x11, 0x22, 0x00]);
+    }
+
+    #[test]
+    fn redundant_broken_ring_broadcast_read() {
+        // Both halves hold bits in common, e.g. every SubDevice is in SAFE-OP
+        let (wkc, data) = merge_broken_ring(
+            Command::brd(0x0130).into(),
+            [0x00; 4],
+            (0, 0x04, 2),
+            (0, 0x14, 3),
+        );
+
+        assert_eq!(wkc, 5);
+        assert_eq!(data, &[0x14, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn redundant_broken_ring_position_addressed() {
+        crate::test_logger();
+
+        const FRAME_OVERHEAD: usize = 28;
+
+        let storage = PduStorage::<1, 128>::new();
+
+        let (mut tx, mut rx, pdu_loop) = storage.try_split().unwrap();
+
+        let poller = poll_fn(|ctx| {
+            let mut frame = pdu_loop.storage.alloc_frame().expect("Frame alloc");
+
+            let handle = frame
+                .push_pdu(Command::aprd(1, 0x0010).into(), [0u8; 2], None)
+                .expect("Push PDU");
+
+            let mut frame_fut = pin!(frame.mark_sendable(&pdu_loop, MAX_TIMEOUT, usize::MAX));
+
+            assert!(frame_fut.as_mut().poll(ctx).is_pending());
+
+            let mut sent = Vec::new();
+
+            tx.next_sendable_frame()
+                .expect("need a frame")
+                .send_blocking_redundant(|port, bytes| {
+                    sent.push((port, bytes.to_vec()));
+
+                    Ok(bytes.len())
+                })
+                .expect("send");
+
+            // Would address a different SubDevice if sent from the redundant interface
+            let [(crate::RedundantPort::Main, main)] = sent.as_slice() else {
+                panic!("expected only the main copy, got {:?}", sent);
+            };
+
+            // Looped back at the break after being processed by the second SubDevice
+            let mut frame = EthernetFrame::new_checked(main.clone()).unwrap();
+            let mut src = frame.src_addr();
+            src.0[0] |= 0x02;
+            frame.set_src_addr(src);
+            let mut packet = frame.into_inner();
+            let data_start = FRAME_OVERHEAD - 2;
+            packet[data_start..data_start + 2].copy_from_slice(&0x1001u16.to_le_bytes());
+            packet[data_start + 2..].copy_from_slice(&1u16.to_le_bytes());
+
+            // Unprocessed echo of a frame sent from the redundant interface
+            let mut echo = main.clone();
+            echo[11] = 0x11;
+
+            assert_eq!(rx.receive_frame(&echo), Ok(crate::ReceiveAction::Ignored));
+
+            assert_eq!(
+                rx.receive_frame_redundant(&packet, crate::RedundantPort::Main),
+                Ok(crate::ReceiveAction::Processed)
+            );
+
+            match frame_fut.poll(ctx) {
+                Poll::Ready(Ok(frame)) => {
+                    let response = frame.first_pdu(handle).expect("Handle");
+
+                    assert_eq!(response.working_counter, 1);
+                    assert_eq!(response.deref(), &[0x01, 0x10]);
+                }
+                Poll::Ready(other) => panic!("Expected Ready(Ok()), got {:?}", other),
+                Poll::Pending => panic!("frame future still pending"),
+            }
+
+            Poll::Ready(())
+        });
+
+        cassette::block_on(poller);
+    }
+
     #[test]
     fn write_multiple_frame() {
         static STORAGE: PduStorage<1, 128> = PduStorage::<1, 128>::new();
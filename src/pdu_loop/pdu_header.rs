@@ -1,4 +1,5 @@
-use crate::pdu_loop::pdu_flags::PduFlags;
+use crate::{command::is_position_addressed, pdu_loop::pdu_flags::PduFlags};
+use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireSized};
 
 /// A single PDU header, command, index, flags and IRQ.
 #[derive(Debug, Copy, Clone, ethercrab_wire::EtherCrabWireReadWrite)]
@@ -74,6 +75,27 @@ impl PduHeader {
     }
 }
 
+/// Whether any PDU in a frame's PDU payload addresses SubDevices by position.
+///
+/// Auto increment addresses count SubDevices from the interface a frame was sent from, so such
+/// PDUs can't be sent from both ends of a redundant ring.
+pub(in crate::pdu_loop) fn any_position_addressed(mut pdus: &[u8]) -> bool {
+    while let Ok(header) = PduHeader::unpack_from_slice(pdus) {
+        if is_position_addressed(header.command_code) {
+            return true;
+        }
+
+        let next = PduHeader::PACKED_LEN + usize::from(header.flags.len()) + 2;
+
+        match pdus.get(next..) {
+            Some(rest) if header.flags.more_follows => pdus = rest,
+            _ => break,
+        }
+    }
+
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
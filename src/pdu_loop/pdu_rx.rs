@@ -1,10 +1,14 @@
 use super::storage::PduStorageRef;
 use crate::ethernet::{EthernetAddress, EthernetFrame};
 use crate::{
-    ETHERCAT_ETHERTYPE, MAINDEVICE_ADDR,
+    ETHERCAT_ETHERTYPE, MAINDEVICE_ADDR, REDUNDANT_MAINDEVICE_ADDR,
+    command::{BRD, BRW},
     error::{Error, PduError},
     fmt,
-    pdu_loop::frame_header::EthercatFrameHeader,
+    pdu_loop::{
+        frame_header::EthercatFrameHeader,
+        pdu_header::{PduHeader, any_position_addressed},
+    },
 };
 use core::sync::atomic::Ordering;
 use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireSized};
@@ -22,10 +26,33 @@ pub enum ReceiveAction {
     Processed,
 }
 
+/// One of the two network interfaces at either end of a redundant EtherCAT ring.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum RedundantPort {
+    /// The interface connected to the input port of the first SubDevice.
+    Main,
+    /// The interface connected to the free port of the last SubDevice.
+    Redundant,
+}
+
+/// How a frame received from a redundant ring should be handled.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum RedundantResponse {
+    /// The frame passed through every SubDevice.
+    Complete,
+    /// The frame was looped back at a break in the ring and only passed through the SubDevices on
+    /// one side of it.
+    Partial,
+}
+
 /// EtherCAT frame receive adapter.
 pub struct PduRx<'sto> {
     storage: PduStorageRef<'sto>,
     source_mac: EthernetAddress,
+    /// Whether both interfaces of a redundant ring have a link.
+    both_links_up: bool,
+    /// Whether the redundant ring was intact when the last frame was received.
+    ring_intact: bool,
 }
 
 impl<'sto> PduRx<'sto> {
@@ -33,6 +60,8 @@ impl<'sto> PduRx<'sto> {
         Self {
             storage,
             source_mac: MAINDEVICE_ADDR,
+            both_links_up: true,
+            ring_intact: true,
         }
     }
 
@@ -49,6 +78,84 @@ impl<'sto> PduRx<'sto> {
     /// sent the frame.
     // NOTE: &mut self so this struct can only be used in one place.
     pub fn receive_frame(&mut self, ethernet_frame: &[u8]) -> Result<ReceiveAction, Error> {
+        self.receive_frame_inner(ethernet_frame, None)
+    }
+
+    /// Like [`receive_frame`](PduRx::receive_frame), but for frames received on one of the two
+    /// interfaces of a redundant ring.
+    ///
+    /// Every frame must have been sent on both interfaces with
+    /// [`SendableFrame::send_blocking_redundant`](crate::SendableFrame::send_blocking_redundant).
+    /// While the ring is intact, the copy sent from the main interface is processed by every
+    /// SubDevice and received on the redundant interface. If the ring is broken, each copy is
+    /// looped back at the break and the two halves are merged, combining their working counters.
+    ///
+    /// Frames with position addressed PDUs are only sent from the main interface, so while the
+    /// ring is broken they only reach the SubDevices before the break. PDUs addressed to
+    /// SubDevices after it are returned with a working counter of zero.
+    ///
+    /// Call [`set_link_status`](PduRx::set_link_status) whenever the link state of either
+    /// interface changes.
+    pub fn receive_frame_redundant(
+        &mut self,
+        ethernet_frame: &[u8],
+        port: RedundantPort,
+    ) -> Result<ReceiveAction, Error> {
+        self.receive_frame_inner(ethernet_frame, Some(port))
+    }
+
+    /// Set whether each interface of a redundant ring has a link.
+    ///
+    /// If either interface is down, the frame looped back to the other interface has passed
+    /// through every reachable SubDevice, so it is not merged with another half.
+    pub fn set_link_status(&mut self, main: bool, redundant: bool) {
+        self.both_links_up = main && redundant;
+    }
+
+    /// Work out how to handle a frame received on `port` from its source address.
+    fn classify_redundant(
+        &mut self,
+        port: RedundantPort,
+        source: EthernetAddress,
+    ) -> Option<RedundantResponse> {
+        // The first SubDevice may set the U/L bit in the first byte, so ignore it
+        let from_main = source.0[1..] == MAINDEVICE_ADDR.0[1..];
+        let from_redundant = source.0[1..] == REDUNDANT_MAINDEVICE_ADDR.0[1..];
+
+        let (response, intact) = match (port, from_main, from_redundant) {
+            // Copy from the main interface made it all the way round the ring
+            (RedundantPort::Redundant, true, _) => (RedundantResponse::Complete, true),
+            // Copy from the redundant interface passed through the ring without being processed
+            (RedundantPort::Main, false, true) => return None,
+            // Looped back at a break in the ring
+            (RedundantPort::Main, true, _) | (RedundantPort::Redundant, false, true) => {
+                if self.both_links_up {
+                    (RedundantResponse::Partial, false)
+                } else {
+                    (RedundantResponse::Complete, false)
+                }
+            }
+            _ => return None,
+        };
+
+        if intact != self.ring_intact {
+            if intact {
+                fmt::info!("Redundant ring is closed");
+            } else {
+                fmt::warn!("Redundant ring is broken");
+            }
+
+            self.ring_intact = intact;
+        }
+
+        Some(response)
+    }
+
+    fn receive_frame_inner(
+        &mut self,
+        ethernet_frame: &[u8],
+        port: Option<RedundantPort>,
+    ) -> Result<ReceiveAction, Error> {
         if self.should_exit() {
             return Ok(ReceiveAction::Ignored);
         }
@@ -60,13 +167,27 @@ impl<'sto> PduRx<'sto> {
         // first SubDevice will set the second bit of the MSB of the MAC address (U/L bit). This means
         // if we send e.g. 10:10:10:10:10:10, we receive 12:10:10:10:10:10 which passes through this
         // filter.
-        if raw_packet.ethertype() != ETHERCAT_ETHERTYPE || raw_packet.src_addr() == self.source_mac
+        if raw_packet.ethertype() != ETHERCAT_ETHERTYPE
+            || raw_packet.src_addr() == self.source_mac
+            || raw_packet.src_addr() == REDUNDANT_MAINDEVICE_ADDR
         {
             fmt::trace!("Ignore frame");
 
             return Ok(ReceiveAction::Ignored);
         }
 
+        let response = match port {
+            Some(port) => match self.classify_redundant(port, raw_packet.src_addr()) {
+                Some(response) => response,
+                None => {
+                    fmt::trace!("Ignore frame passed through redundant ring");
+
+                    return Ok(ReceiveAction::Ignored);
+                }
+            },
+            None => RedundantResponse::Complete,
+        };
+
         let i = raw_packet.payload();
 
         let frame_header = EthercatFrameHeader::unpack_from_slice(i).inspect_err(|&e| {
@@ -94,6 +215,14 @@ impl<'sto> PduRx<'sto> {
         // `i` now contains the EtherCAT frame payload, consisting of one or more PDUs including
         // their headers and payloads.
 
+        // Frames with position addressed PDUs are only sent from the main interface, so there is
+        // no other half to merge
+        let response = if response == RedundantResponse::Partial && any_position_addressed(i) {
+            RedundantResponse::Complete
+        } else {
+            response
+        };
+
         // Second byte of first PDU header is the index
         let pdu_idx = *i.get(1).ok_or(Error::Internal)?;
 
@@ -117,6 +246,20 @@ impl<'sto> PduRx<'sto> {
             .claim_receiving(frame_index)
             .ok_or(PduError::InvalidIndex(frame_index))?;
 
+        if response == RedundantResponse::Partial {
+            let first = !frame.is_partial();
+
+            merge_partial(frame.buf_mut(), i, first)?;
+
+            if first {
+                frame.mark_partial()?;
+            } else {
+                frame.mark_received()?;
+            }
+
+            return Ok(ReceiveAction::Processed);
+        }
+
         let frame_data = frame.buf_mut();
 
         frame_data
@@ -146,3 +289,70 @@ impl<'sto> PduRx<'sto> {
         self
     }
 }
+
+/// Merge one half of a frame looped back at a break in a redundant ring into `buf`, which holds
+/// the PDUs as they were sent.
+///
+/// Each half only holds data written by the SubDevices on its side of the break. Broadcast reads
+/// are combined with OR, as each SubDevice ORs its register into the PDU data. All other PDU data
+/// is combined with XOR: once both halves are merged, every byte holds the value from whichever
+/// half changed it. Working counters are summed, and the PDU headers are taken from the second
+/// half.
+fn merge_partial(buf: &mut [u8], response: &[u8], first: bool) -> Result<(), Error> {
+    let mut offset = 0;
+
+    loop {
+        let rest = response.get(offset..).ok_or(Error::Internal)?;
+
+        let header = PduHeader::unpack_from_slice(rest)?;
+
+        let data_start = offset + PduHeader::PACKED_LEN;
+        let data_end = data_start + usize::from(header.flags.len());
+        let wkc_end = data_end + 2;
+
+        let response_data = response
+            .get(data_start..data_end)
+            .ok_or(Error::Pdu(PduError::TooLong))?;
+        let buf_data = buf
+            .get_mut(data_start..data_end)
+            .ok_or(Error::Pdu(PduError::TooLong))?;
+
+        if matches!(header.command_code, BRD | BRW) {
+            for (b, r) in buf_data.iter_mut().zip(response_data) {
+                *b |= r;
+            }
+        } else {
+            for (b, r) in buf_data.iter_mut().zip(response_data) {
+                *b ^= r;
+            }
+        }
+
+        let response_wkc = u16::unpack_from_slice(
+            response
+                .get(data_end..wkc_end)
+                .ok_or(Error::Pdu(PduError::TooLong))?,
+        )?;
+
+        let buf_wkc = buf.get_mut(data_end..wkc_end).ok_or(Error::Internal)?;
+
+        let wkc = if first {
+            response_wkc
+        } else {
+            u16::unpack_from_slice(buf_wkc)?.wrapping_add(response_wkc)
+        };
+
+        buf_wkc.copy_from_slice(&wkc.to_le_bytes());
+
+        if !first {
+            buf.get_mut(offset..data_start)
+                .ok_or(Error::Internal)?
+                .copy_from_slice(&rest[0..PduHeader::PACKED_LEN]);
+        }
+
+        if !header.flags.more_follows {
+            break Ok(());
+        }
+
+        offset = wkc_end;
+    }
+}
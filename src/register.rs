@@ -27,6 +27,11 @@ pub enum RegisterAddress {
     /// The SubDevice's address alias, `u16`.
     ConfiguredStationAlias = 0x0012,
 
+    /// Defined in ETG1000.4 Table 32 - DL control, `u32`.
+    ///
+    /// Holds the loop setting of each port in the second byte.
+    DlControl = 0x0100,
+
     /// Defined in ETG1000.4 Table 34 - DL status, `u16`.
     DlStatus = 0x0110,
 
@@ -40,6 +45,34 @@ pub enum RegisterAddress {
     /// Application Layer (AL) status code register.
     AlStatusCode = 0x0134,
 
+    /// Port 0 invalid frame counter (`u8`) followed by RX error counter (`u8`).
+    ///
+    /// Error counters are defined in ETG1000.4 Table 38. Writing any value to
+    /// [`RegisterAddress::RxErrorCounterPort0`] clears all RX and forwarded RX error counters.
+    RxErrorCounterPort0 = 0x0300,
+    /// Port 1 invalid frame counter (`u8`) followed by RX error counter (`u8`).
+    RxErrorCounterPort1 = 0x0302,
+    /// Port 2 invalid frame counter (`u8`) followed by RX error counter (`u8`).
+    RxErrorCounterPort2 = 0x0304,
+    /// Port 3 invalid frame counter (`u8`) followed by RX error counter (`u8`).
+    RxErrorCounterPort3 = 0x0306,
+    /// Forwarded RX error counters for ports 0-3, `[u8; 4]`.
+    ForwardedRxErrorCounter = 0x0308,
+    /// EtherCAT processing unit error counter, `u8`.
+    EcatProcessingUnitErrorCounter = 0x030C,
+    /// PDI error counter, `u8`.
+    PdiErrorCounter = 0x030D,
+    /// Port 0 lost link counter, `u8`.
+    ///
+    /// Writing any value to one of the lost link counters clears all of them.
+    LostLinkCounterPort0 = 0x0310,
+    /// Port 1 lost link counter, `u8`.
+    LostLinkCounterPort1 = 0x0311,
+    /// Port 2 lost link counter, `u8`.
+    LostLinkCounterPort2 = 0x0312,
+    /// Port 3 lost link counter, `u8`.
+    LostLinkCounterPort3 = 0x0313,
+
     /// Watchdog divider, `u16`.
     ///
     /// See ETG1000.4 section 6.3 Watchdogs.
@@ -0,0 +1,699 @@
+use super::{
+    BOOTSTRAP_MAILBOX_POSITION, FmmuUsage, HEADER_LEN, MailboxProtocols, SIZE_POSITION,
+    SiiConfigArea, SiiDcOpMode, SiiGeneralCategory, SiiMailbox, SiiPdo, SiiSyncManager,
+};
+use crate::{
+    SubDeviceIdentity,
+    eeprom::{CHECKSUM_POSITION, STATION_ALIAS_CRC, types::CategoryType},
+    error::{EepromError, Error},
+};
+use ethercrab_wire::EtherCrabWireWriteSized;
+
+/// SII version, ETG2010 Table 2 word `0x003f`.
+const SII_VERSION: u16 = 1;
+
+/// Build a complete SII EEPROM image from a structured description.
+///
+/// The image contains the fixed header fields defined in ETG2010 Table 2, with the configuration
+/// area checksum calculated automatically, followed by the Strings, General, FMMU, SyncM, TXPDO,
+/// RXPDO and DC categories and an end marker. Empty categories are omitted, except for General
+/// which is always written.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::{
+///     SubDeviceIdentity,
+///     sii::{SiiGeneralCategory, SiiImageBuilder},
+/// };
+///
+/// let builder = SiiImageBuilder::new(SubDeviceIdentity {
+///     vendor_id: 0x0000_1234,
+///     product_id: 0x0001_0001,
+///     revision: 1,
+///     serial: 0,
+/// })
+/// .with_strings(&["MyDevice", "My custom SubDevice"])
+/// .with_general(SiiGeneralCategory {
+///     order_string_idx: 1,
+///     name_string_idx: 2,
+///     ..SiiGeneralCategory::default()
+/// });
+///
+/// let mut image = [0xffu8; 2048];
+///
+/// let len = builder.build(&mut image).expect("build image");
+///
+/// // Write `&image[0..len]` to a SubDevice, or save it to a file.
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SiiImageBuilder<'a> {
+    config: SiiConfigArea,
+    identity: SubDeviceIdentity,
+    bootstrap_mailbox: SiiMailbox,
+    mailbox: SiiMailbox,
+    mailbox_protocols: MailboxProtocols,
+    eeprom_size: usize,
+    strings: &'a [&'a str],
+    general: SiiGeneralCategory,
+    fmmus: &'a [FmmuUsage],
+    sync_managers: &'a [SiiSyncManager],
+    tx_pdos: &'a [SiiPdo<'a>],
+    rx_pdos: &'a [SiiPdo<'a>],
+    dc_op_modes: &'a [SiiDcOpMode],
+}
+
+impl<'a> SiiImageBuilder<'a> {
+    /// Create a new builder for a SubDevice with the given identity.
+    ///
+    /// The EEPROM size defaults to 2048 bytes (16 Kbit).
+    pub const fn new(identity: SubDeviceIdentity) -> Self {
+        Self {
+            config: SiiConfigArea {
+                pdi_control: 0,
+                pdi_config: 0,
+                sync_impulse_len: 0,
+                pdi_config2: 0,
+                station_alias: 0,
+            },
+            identity,
+            bootstrap_mailbox: SiiMailbox {
+                receive_offset: 0,
+                receive_size: 0,
+                send_offset: 0,
+                send_size: 0,
+            },
+            mailbox: SiiMailbox {
+                receive_offset: 0,
+                receive_size: 0,
+                send_offset: 0,
+                send_size: 0,
+            },
+            mailbox_protocols: MailboxProtocols::empty(),
+            eeprom_size: 2048,
+            strings: &[],
+            general: SiiGeneralCategory {
+                group_string_idx: 0,
+                image_string_idx: 0,
+                order_string_idx: 0,
+                name_string_idx: 0,
+                coe_details: 0,
+                foe_details: 0,
+                eoe_details: 0,
+                soe_channels: 0,
+                ds402_channels: 0,
+                sysman_class: 0,
+                flags: 0,
+                ebus_current: 0,
+                physical_ports: 0,
+                physical_memory_addr: 0,
+            },
+            fmmus: &[],
+            sync_managers: &[],
+            tx_pdos: &[],
+            rx_pdos: &[],
+            dc_op_modes: &[],
+        }
+    }
+
+    /// Set the configuration area, including the station alias.
+    pub const fn with_config_area(mut self, config: SiiConfigArea) -> Self {
+        self.config = config;
+
+        self
+    }
+
+    /// Set the bootstrap mailbox offsets and sizes.
+    pub const fn with_bootstrap_mailbox(mut self, mailbox: SiiMailbox) -> Self {
+        self.bootstrap_mailbox = mailbox;
+
+        self
+    }
+
+    /// Set the standard mailbox offsets and sizes, and the mailbox protocols supported by the
+    /// SubDevice.
+    pub const fn with_mailbox(mut self, mailbox: SiiMailbox, protocols: MailboxProtocols) -> Self {
+        self.mailbox = mailbox;
+        self.mailbox_protocols = protocols;
+
+        self
+    }
+
+    /// Set the total EEPROM size in bytes. This must be a multiple of 128 bytes (1 Kbit).
+    pub const fn with_eeprom_size(mut self, size_bytes: usize) -> Self {
+        self.eeprom_size = size_bytes;
+
+        self
+    }
+
+    /// Set the strings to store in the Strings category.
+    ///
+    /// Strings are referenced elsewhere in the image by their 1-based index in this list. SII
+    /// strings are not UTF-8, so these should only contain ASCII characters.
+    pub const fn with_strings(mut self, strings: &'a [&'a str]) -> Self {
+        self.strings = strings;
+
+        self
+    }
+
+    /// Set the General category.
+    pub const fn with_general(mut self, general: SiiGeneralCategory) -> Self {
+        self.general = general;
+
+        self
+    }
+
+    /// Set the usage of each FMMU.
+    ///
+    /// [`FmmuUsage::Unused`] is written as `0xff`, matching most SubDevice EEPROMs.
+    pub const fn with_fmmus(mut self, fmmus: &'a [FmmuUsage]) -> Self {
+        self.fmmus = fmmus;
+
+        self
+    }
+
+    /// Set the sync manager configuration.
+    pub const fn with_sync_managers(mut self, sync_managers: &'a [SiiSyncManager]) -> Self {
+        self.sync_managers = sync_managers;
+
+        self
+    }
+
+    /// Set the TxPDOs (SubDevice to MainDevice).
+    pub const fn with_tx_pdos(mut self, pdos: &'a [SiiPdo<'a>]) -> Self {
+        self.tx_pdos = pdos;
+
+        self
+    }
+
+    /// Set the RxPDOs (MainDevice to SubDevice).
+    pub const fn with_rx_pdos(mut self, pdos: &'a [SiiPdo<'a>]) -> Self {
+        self.rx_pdos = pdos;
+
+        self
+    }
+
+    /// Set the Distributed Clocks operation modes.
+    pub const fn with_dc_op_modes(mut self, op_modes: &'a [SiiDcOpMode]) -> Self {
+        self.dc_op_modes = op_modes;
+
+        self
+    }
+
+    /// Serialise the image into `buf`, returning the number of bytes written.
+    ///
+    /// Bytes in `buf` after the end marker are left untouched. Fill `buf` with `0xff` beforehand
+    /// to match the contents of an erased EEPROM.
+    ///
+    /// # Errors
+    ///
+    /// - [`EepromError::InvalidImage`] if the EEPROM size is not a multiple of 128 bytes.
+    /// - [`EepromError::SectionOverrun`] if `buf` or the EEPROM is too small to hold the image, or
+    ///   a category contains more than 255 items.
+    /// - [`Error::StringTooLong`] if a string is longer than 255 bytes.
+    pub fn build(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.eeprom_size < 128 || self.eeprom_size % 128 != 0 {
+            return Err(Error::Eeprom(EepromError::InvalidImage));
+        }
+
+        let size_word = u16::try_from(self.eeprom_size / 128 - 1)
+            .map_err(|_| Error::Eeprom(EepromError::InvalidImage))?;
+
+        let mut w = ImageWriter { buf, pos: 0 };
+
+        w.push(self.config.pack().as_ref())?;
+        // Checksum is calculated once the whole header has been written
+        w.push(&[0u8; 2])?;
+        w.push(self.identity.pack().as_ref())?;
+        w.fill_to(BOOTSTRAP_MAILBOX_POSITION, 0x00)?;
+        w.push(self.bootstrap_mailbox.pack().as_ref())?;
+        w.push(self.mailbox.pack().as_ref())?;
+        w.push(&u16::from(self.mailbox_protocols.bits()).to_le_bytes())?;
+        w.fill_to(SIZE_POSITION, 0x00)?;
+        w.push(&size_word.to_le_bytes())?;
+        w.push(&SII_VERSION.to_le_bytes())?;
+
+        debug_assert_eq!(w.pos, HEADER_LEN);
+
+        let checksum = u16::from(STATION_ALIAS_CRC.checksum(&w.buf[0..CHECKSUM_POSITION.start]));
+
+        w.buf[CHECKSUM_POSITION].copy_from_slice(&checksum.to_le_bytes());
+
+        if !self.strings.is_empty() {
+            w.category(CategoryType::Strings, |w| {
+                w.push(&[count(self.strings.len())?])?;
+
+                for s in self.strings {
+                    let len = u8::try_from(s.len()).map_err(|_| Error::StringTooLong {
+                        max_length: usize::from(u8::MAX),
+                        string_length: s.len(),
+                    })?;
+
+                    w.push(&[len])?;
+                    w.push(s.as_bytes())?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        w.category(CategoryType::General, |w| {
+            w.push(self.general.pack().as_ref())
+        })?;
+
+        if !self.fmmus.is_empty() {
+            w.category(CategoryType::Fmmu, |w| {
+                for fmmu in self.fmmus {
+                    let raw = match fmmu {
+                        FmmuUsage::Unused => 0xff,
+                        other => u8::from(*other),
+                    };
+
+                    w.push(&[raw])?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        if !self.sync_managers.is_empty() {
+            w.category(CategoryType::SyncManager, |w| {
+                for sm in self.sync_managers {
+                    w.push(&sm.start_addr.to_le_bytes())?;
+                    w.push(&sm.length.to_le_bytes())?;
+                    w.push(&[sm.control, 0x00, sm.enable.bits(), u8::from(sm.usage_type)])?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        for (category, pdos) in [
+            (CategoryType::TxPdo, self.tx_pdos),
+            (CategoryType::RxPdo, self.rx_pdos),
+        ] {
+            if pdos.is_empty() {
+                continue;
+            }
+
+            w.category(category, |w| {
+                for pdo in pdos {
+                    w.push(&pdo.index.to_le_bytes())?;
+                    w.push(&[
+                        count(pdo.entries.len())?,
+                        pdo.sync_manager,
+                        pdo.dc_sync,
+                        pdo.name_string_idx,
+                    ])?;
+                    w.push(&pdo.flags.bits().to_le_bytes())?;
+
+                    for entry in pdo.entries {
+                        w.push(entry.pack().as_ref())?;
+                    }
+                }
+
+                Ok(())
+            })?;
+        }
+
+        if !self.dc_op_modes.is_empty() {
+            w.category(CategoryType::DistributedClock, |w| {
+                for op_mode in self.dc_op_modes {
+                    w.push(op_mode.pack().as_ref())?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        // End marker
+        w.push(&u16::from(CategoryType::End).to_le_bytes())?;
+        w.push(&[0xff, 0xff])?;
+
+        if w.pos > self.eeprom_size {
+            return Err(Error::Eeprom(EepromError::SectionOverrun));
+        }
+
+        Ok(w.pos)
+    }
+}
+
+/// Convert an item count into the single byte used to store it in the SII.
+fn count(len: usize) -> Result<u8, Error> {
+    u8::try_from(len).map_err(|_| Error::Eeprom(EepromError::SectionOverrun))
+}
+
+struct ImageWriter<'buf> {
+    buf: &'buf mut [u8],
+    pos: usize,
+}
+
+impl ImageWriter<'_> {
+    fn push(&mut self, data: &[u8]) -> Result<(), Error> {
+        let end = self.pos + data.len();
+
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(Error::Eeprom(EepromError::SectionOverrun))?
+            .copy_from_slice(data);
+
+        self.pos = end;
+
+        Ok(())
+    }
+
+    fn fill_to(&mut self, end: usize, value: u8) -> Result<(), Error> {
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(Error::Eeprom(EepromError::SectionOverrun))?
+            .fill(value);
+
+        self.pos = end;
+
+        Ok(())
+    }
+
+    /// Write a category header followed by the data written by `body`, padding the data to a whole
+    /// number of words with `0xff`.
+    fn category(
+        &mut self,
+        category: CategoryType,
+        body: impl FnOnce(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let header = self.pos;
+
+        self.push(&[0u8; 4])?;
+
+        body(self)?;
+
+        if (self.pos - header) % 2 != 0 {
+            self.push(&[0xff])?;
+        }
+
+        let len_words = u16::try_from((self.pos - header - 4) / 2)
+            .map_err(|_| Error::Eeprom(EepromError::SectionOverrun))?;
+
+        self.buf[header..header + 2].copy_from_slice(&u16::from(category).to_le_bytes());
+        self.buf[header + 2..header + 4].copy_from_slice(&len_words.to_le_bytes());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eeprom::file_provider::EepromFile,
+        sii::{PdoFlags, SiiPdoEntry, SyncManagerEnable, SyncManagerType},
+        subdevice::eeprom::SubDeviceEeprom,
+    };
+    use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireSized};
+
+    /// Split an image into its categories, returning the raw type and data of each one.
+    fn categories(image: &[u8]) -> Vec<(u16, &[u8])> {
+        let mut categories = Vec::new();
+        let mut pos = HEADER_LEN;
+
+        loop {
+            let ty = u16::from_le_bytes([image[pos], image[pos + 1]]);
+            let len = usize::from(u16::from_le_bytes([image[pos + 2], image[pos + 3]])) * 2;
+
+            if ty == 0xffff {
+                break;
+            }
+
+            categories.push((ty, &image[pos + 4..pos + 4 + len]));
+
+            pos += 4 + len;
+        }
+
+        categories
+    }
+
+    fn category(image: &[u8], ty: CategoryType) -> Option<&[u8]> {
+        categories(image)
+            .into_iter()
+            .find(|(t, _)| *t == u16::from(ty))
+            .map(|(_, data)| data)
+    }
+
+    fn decode_pdos(data: &[u8]) -> Vec<(SiiPdo<'static>, Vec<SiiPdoEntry>)> {
+        let mut pdos = Vec::new();
+        let mut chunks = data.chunks_exact(8);
+
+        while let Some(header) = chunks.next() {
+            let num_entries = header[2];
+
+            let entries = (0..num_entries)
+                .map(|_| SiiPdoEntry::unpack_from_slice(chunks.next().unwrap()).unwrap())
+                .collect::<Vec<_>>();
+
+            pdos.push((
+                SiiPdo {
+                    index: u16::from_le_bytes([header[0], header[1]]),
+                    sync_manager: header[3],
+                    dc_sync: header[4],
+                    name_string_idx: header[5],
+                    flags: PdoFlags::from_bits_retain(u16::from_le_bytes([header[6], header[7]])),
+                    entries: &[],
+                },
+                entries,
+            ));
+        }
+
+        pdos
+    }
+
+    /// Decode a device EEPROM dump, rebuild it and check that every category the builder
+    /// supports is identical.
+    fn round_trip(image: &[u8]) {
+        crate::test_logger();
+
+        let strings = category(image, CategoryType::Strings)
+            .map(|data| {
+                let mut strings = Vec::new();
+                let mut rest = &data[1..];
+
+                for _ in 0..data[0] {
+                    let (len, tail) = rest.split_first().unwrap();
+                    let (s, tail) = tail.split_at(usize::from(*len));
+
+                    strings.push(core::str::from_utf8(s).unwrap());
+
+                    rest = tail;
+                }
+
+                strings
+            })
+            .unwrap_or_default();
+
+        let general =
+            SiiGeneralCategory::unpack_from_slice(category(image, CategoryType::General).unwrap())
+                .unwrap();
+
+        let fmmus = category(image, CategoryType::Fmmu)
+            .unwrap_or_default()
+            .iter()
+            .map(|raw| FmmuUsage::try_from(*raw).unwrap())
+            .collect::<Vec<_>>();
+
+        let sync_managers = category(image, CategoryType::SyncManager)
+            .unwrap_or_default()
+            .chunks_exact(8)
+            .map(|raw| SiiSyncManager {
+                start_addr: u16::from_le_bytes([raw[0], raw[1]]),
+                length: u16::from_le_bytes([raw[2], raw[3]]),
+                control: raw[4],
+                enable: SyncManagerEnable::from_bits_retain(raw[6]),
+                usage_type: SyncManagerType::from(raw[7]),
+            })
+            .collect::<Vec<_>>();
+
+        let tx = decode_pdos(category(image, CategoryType::TxPdo).unwrap_or_default());
+        let rx = decode_pdos(category(image, CategoryType::RxPdo).unwrap_or_default());
+
+        let tx_pdos = tx
+            .iter()
+            .map(|(pdo, entries)| SiiPdo {
+                entries: entries.as_slice(),
+                ..*pdo
+            })
+            .collect::<Vec<_>>();
+        let rx_pdos = rx
+            .iter()
+            .map(|(pdo, entries)| SiiPdo {
+                entries: entries.as_slice(),
+                ..*pdo
+            })
+            .collect::<Vec<_>>();
+
+        let dc_op_modes = category(image, CategoryType::DistributedClock)
+            .unwrap_or_default()
+            .chunks_exact(SiiDcOpMode::PACKED_LEN)
+            .map(|raw| SiiDcOpMode::unpack_from_slice(raw).unwrap())
+            .collect::<Vec<_>>();
+
+        let size_bytes = (usize::from(u16::from_le_bytes([image[0x7c], image[0x7d]])) + 1) * 128;
+
+        let builder =
+            SiiImageBuilder::new(SubDeviceIdentity::unpack_from_slice(&image[0x10..0x20]).unwrap())
+                .with_config_area(SiiConfigArea::unpack_from_slice(&image[0..14]).unwrap())
+                .with_bootstrap_mailbox(SiiMailbox::unpack_from_slice(&image[0x28..0x30]).unwrap())
+                .with_mailbox(
+                    SiiMailbox::unpack_from_slice(&image[0x30..0x38]).unwrap(),
+                    MailboxProtocols::from_bits_retain(image[0x38]),
+                )
+                .with_eeprom_size(size_bytes)
+                .with_strings(&strings)
+                .with_general(general)
+                .with_fmmus(&fmmus)
+                .with_sync_managers(&sync_managers)
+                .with_tx_pdos(&tx_pdos)
+                .with_rx_pdos(&rx_pdos)
+                .with_dc_op_modes(&dc_op_modes);
+
+        let mut built = vec![0xffu8; size_bytes];
+
+        let len = builder.build(&mut built).unwrap();
+
+        // Header, including checksum. Words 0x0010-0x0013 and 0x001d-0x003d are reserved and may
+        // contain vendor data.
+        assert_eq!(built[0..0x20], image[0..0x20], "config area and identity");
+        assert_eq!(built[0x28..0x3a], image[0x28..0x3a], "mailboxes");
+        assert_eq!(built[0x7c..0x80], image[0x7c..0x80], "size and version");
+
+        for ty in [
+            CategoryType::Strings,
+            CategoryType::Fmmu,
+            CategoryType::SyncManager,
+            CategoryType::TxPdo,
+            CategoryType::RxPdo,
+            CategoryType::DistributedClock,
+        ] {
+            assert_eq!(
+                category(&built[0..len], ty),
+                category(image, ty),
+                "category {:?}",
+                ty
+            );
+        }
+
+        // Reserved bytes in the General category are not reproduced
+        let mut expected_general = category(image, CategoryType::General).unwrap().to_vec();
+        expected_general[4] = 0;
+        expected_general[14..16].fill(0);
+
+        assert_eq!(
+            category(&built[0..len], CategoryType::General),
+            Some(expected_general.as_slice())
+        );
+
+        assert_eq!(built[len - 4..len], [0xff; 4], "end marker");
+    }
+
+    #[test]
+    fn round_trip_akd() {
+        round_trip(include_bytes!("../../dumps/eeprom/akd.hex"));
+    }
+
+    #[test]
+    fn round_trip_ek1100() {
+        round_trip(include_bytes!("../../dumps/eeprom/ek1100.hex"));
+    }
+
+    #[test]
+    fn round_trip_el2828() {
+        round_trip(include_bytes!("../../dumps/eeprom/el2828.hex"));
+    }
+
+    #[test]
+    fn round_trip_el2889() {
+        round_trip(include_bytes!("../../dumps/eeprom/el2889.hex"));
+    }
+
+    #[tokio::test]
+    async fn read_built_image() {
+        crate::test_logger();
+
+        let identity = SubDeviceIdentity {
+            vendor_id: 0x1234,
+            product_id: 0x5678,
+            revision: 3,
+            serial: 4,
+        };
+
+        let op_modes = [SiiDcOpMode {
+            cycle_time_sync0: 0,
+            shift_time_sync0: 0,
+            shift_time_sync1: 0,
+            sync1_cycle_factor: 0,
+            assign_activate: 0x0300,
+            sync0_cycle_factor: 1,
+            name_string_idx: 2,
+            description_string_idx: 0,
+        }];
+
+        let mut image = vec![0xffu8; 2048];
+
+        SiiImageBuilder::new(identity)
+            .with_strings(&["EC-CUSTOM", "DC"])
+            .with_general(SiiGeneralCategory {
+                order_string_idx: 1,
+                ..SiiGeneralCategory::default()
+            })
+            .with_fmmus(&[FmmuUsage::Outputs, FmmuUsage::Inputs, FmmuUsage::Unused])
+            .with_dc_op_modes(&op_modes)
+            .build(&mut image)
+            .unwrap();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(image.leak()));
+
+        assert_eq!(e.identity().await, Ok(identity));
+        assert_eq!(
+            e.device_name::<16>().await,
+            Ok(Some("EC-CUSTOM".try_into().unwrap()))
+        );
+        assert_eq!(
+            e.fmmus().await,
+            Ok([
+                FmmuUsage::Outputs,
+                FmmuUsage::Inputs,
+                FmmuUsage::Unused,
+                FmmuUsage::Unused
+            ]
+            .into_iter()
+            .collect())
+        );
+        assert_eq!(e.dc_op_modes().await, Ok(op_modes.into_iter().collect()));
+        assert_eq!(e.size().await, Ok(2048));
+    }
+
+    #[test]
+    fn string_too_long() {
+        let long = [b'a'; 256];
+        let strings = [core::str::from_utf8(&long).unwrap()];
+
+        let mut buf = [0u8; 2048];
+
+        assert_eq!(
+            SiiImageBuilder::new(SubDeviceIdentity::default())
+                .with_strings(&strings)
+                .build(&mut buf),
+            Err(Error::StringTooLong {
+                max_length: 255,
+                string_length: 256
+            })
+        );
+    }
+
+    #[test]
+    fn buffer_too_short() {
+        let mut buf = [0u8; 64];
+
+        assert_eq!(
+            SiiImageBuilder::new(SubDeviceIdentity::default()).build(&mut buf),
+            Err(Error::Eeprom(EepromError::SectionOverrun))
+        );
+    }
+}
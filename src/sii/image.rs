@@ -0,0 +1,789 @@
+use super::{
+    BOOTSTRAP_MAILBOX_POSITION, FmmuUsage, HEADER_LEN, MailboxProtocols, PdoFlags, SIZE_POSITION,
+    SiiCategory, SiiConfigArea, SiiDcOpMode, SiiGeneralCategory, SiiMailbox, SiiPdoEntry,
+    SiiSyncManager, SyncManagerEnable, SyncManagerType,
+};
+use crate::{
+    SubDeviceIdentity,
+    eeprom::{CHECKSUM_POSITION, STATION_ALIAS_CRC, types::CategoryType},
+    error::{EepromError, Error},
+};
+use core::iter::FusedIterator;
+use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireSized};
+
+/// Byte offset of the identity, ETG2010 Table 2 word `0x0008`.
+const IDENTITY_POSITION: usize = 0x10;
+/// Byte offset of the standard mailbox config, ETG2010 Table 2 word `0x0018`.
+const MAILBOX_POSITION: usize = 0x30;
+/// Byte offset of the supported mailbox protocols, ETG2010 Table 2 word `0x001c`.
+const MAILBOX_PROTOCOLS_POSITION: usize = 0x38;
+/// Length of the fixed part of a PDO before its entries.
+const PDO_HEADER_LEN: usize = 8;
+
+/// A read-only view over an SII EEPROM image, e.g. one read from a file or generated by
+/// [`SiiImageBuilder`](super::SiiImageBuilder).
+///
+/// Decoding is done lazily and without allocation. With the `std` feature,
+/// [`decode`](SiiImage::decode) can be used to decode the entire image at once.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::sii::SiiImage;
+///
+/// # fn main() -> Result<(), ethercrab::error::Error> {
+/// # let mut bytes = [0xffu8; 256];
+/// # let len = ethercrab::sii::SiiImageBuilder::new(Default::default()).build(&mut bytes)?;
+/// # let bytes = &bytes[0..len];
+/// let image = SiiImage::new(bytes)?;
+///
+/// image.validate_checksum()?;
+///
+/// println!("Identity {}", image.identity()?);
+///
+/// for pdo in image.tx_pdos() {
+///     let pdo = pdo?;
+///
+///     println!("TxPDO {:#06x} with {} entries", pdo.index, pdo.entries.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SiiImage<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SiiImage<'a> {
+    /// Create a new image from raw EEPROM contents.
+    ///
+    /// Returns [`EepromError::InvalidImage`] if the image is shorter than the fixed header or is
+    /// not a whole number of words long.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN || bytes.len() % 2 != 0 {
+            return Err(Error::Eeprom(EepromError::InvalidImage));
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// The raw image bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    fn unpack_at<T: EtherCrabWireRead + EtherCrabWireSized>(
+        &self,
+        position: usize,
+    ) -> Result<T, Error> {
+        Ok(T::unpack_from_slice(&self.bytes[position..])?)
+    }
+
+    /// The configuration area loaded into the ESC on power up.
+    pub fn config_area(&self) -> Result<SiiConfigArea, Error> {
+        self.unpack_at(0)
+    }
+
+    /// Check the configuration area checksum stored in word `0x0007` against one calculated over
+    /// the first 7 words of the image.
+    ///
+    /// Returns [`EepromError::Checksum`] if they differ.
+    pub fn validate_checksum(&self) -> Result<(), Error> {
+        let stored = self.bytes[CHECKSUM_POSITION.start];
+        let calculated = STATION_ALIAS_CRC.checksum(&self.bytes[0..CHECKSUM_POSITION.start]);
+
+        if stored != calculated {
+            return Err(Error::Eeprom(EepromError::Checksum { stored, calculated }));
+        }
+
+        Ok(())
+    }
+
+    /// Vendor ID, product ID, revision and serial number.
+    pub fn identity(&self) -> Result<SubDeviceIdentity, Error> {
+        self.unpack_at(IDENTITY_POSITION)
+    }
+
+    /// Bootstrap mailbox offsets and sizes.
+    pub fn bootstrap_mailbox(&self) -> Result<SiiMailbox, Error> {
+        self.unpack_at(BOOTSTRAP_MAILBOX_POSITION)
+    }
+
+    /// Standard mailbox offsets and sizes.
+    pub fn mailbox(&self) -> Result<SiiMailbox, Error> {
+        self.unpack_at(MAILBOX_POSITION)
+    }
+
+    /// Mailbox protocols supported by the SubDevice.
+    pub fn mailbox_protocols(&self) -> Result<MailboxProtocols, Error> {
+        self.unpack_at(MAILBOX_PROTOCOLS_POSITION)
+    }
+
+    /// Total EEPROM size in bytes as stored in the image. This may be larger than the image
+    /// itself.
+    pub fn eeprom_size(&self) -> Result<usize, Error> {
+        let size = u16::unpack_from_slice(&self.bytes[SIZE_POSITION..])?;
+
+        Ok((usize::from(size) + 1) * 128)
+    }
+
+    /// Iterate over the header of every category in the image, stopping at the end marker.
+    pub fn categories(&self) -> SiiCategories<'a> {
+        SiiCategories {
+            bytes: self.bytes,
+            pos: HEADER_LEN,
+        }
+    }
+
+    /// Get the data contained in the first category of the given type, if present.
+    ///
+    /// `category` is the raw category type, e.g. `10` for Strings. See ETG1000.6 Table 19.
+    pub fn category_data(&self, category: u16) -> Result<Option<&'a [u8]>, Error> {
+        for header in self.categories() {
+            let header = header?;
+
+            if header.category == category {
+                return self.data(&header).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the data contained in a category returned by [`categories`](SiiImage::categories).
+    pub fn data(&self, category: &SiiCategory) -> Result<&'a [u8], Error> {
+        let start = usize::from(category.start_word) * 2;
+        let end = start + usize::from(category.len_words) * 2;
+
+        self.bytes
+            .get(start..end)
+            .ok_or(Error::Eeprom(EepromError::SectionUnderrun))
+    }
+
+    fn category_of(&self, category: CategoryType) -> Result<&'a [u8], Error> {
+        self.category_data(u16::from(category))
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Iterate over the raw bytes of each string in the Strings category.
+    ///
+    /// Strings are referenced by a 1-based index, so the first item of this iterator has index
+    /// `1`. EtherCAT strings should be ASCII, but some SubDevices use other encodings like
+    /// ISO-8859-1. Any trailing null terminators are removed.
+    pub fn strings(&self) -> Result<SiiStrings<'a>, Error> {
+        let data = self.category_of(CategoryType::Strings)?;
+
+        let (remaining, data) = data.split_first().map_or((0, data), |(n, d)| (*n, d));
+
+        Ok(SiiStrings { data, remaining })
+    }
+
+    /// Get a string by its 1-based index. An index of zero means no string and always returns
+    /// `Ok(None)`.
+    pub fn string(&self, index: u8) -> Result<Option<&'a [u8]>, Error> {
+        let Some(index) = index.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        self.strings()?.nth(usize::from(index)).transpose()
+    }
+
+    /// The General category.
+    pub fn general(&self) -> Result<Option<SiiGeneralCategory>, Error> {
+        self.category_data(u16::from(CategoryType::General))?
+            .map(|data| Ok(SiiGeneralCategory::unpack_from_slice(data)?))
+            .transpose()
+    }
+
+    /// Iterate over the usage of each FMMU in the FMMU category.
+    pub fn fmmus(&self) -> Result<impl Iterator<Item = Result<FmmuUsage, Error>> + 'a, Error> {
+        let data = self.category_of(CategoryType::Fmmu)?;
+
+        Ok(data
+            .iter()
+            .map(|raw| Ok(FmmuUsage::unpack_from_slice(&[*raw])?)))
+    }
+
+    /// Iterate over the sync managers in the SyncM category.
+    pub fn sync_managers(&self) -> Result<impl Iterator<Item = SiiSyncManager> + 'a, Error> {
+        let data = self.category_of(CategoryType::SyncManager)?;
+
+        Ok(data.chunks_exact(8).map(|sm| SiiSyncManager {
+            start_addr: u16::from_le_bytes([sm[0], sm[1]]),
+            length: u16::from_le_bytes([sm[2], sm[3]]),
+            control: sm[4],
+            enable: SyncManagerEnable::from_bits_retain(sm[6]),
+            usage_type: SyncManagerType::from(sm[7]),
+        }))
+    }
+
+    /// Iterate over the PDOs in the TXPDO category (SubDevice to MainDevice).
+    pub fn tx_pdos(&self) -> SiiPdos<'a> {
+        self.pdos(CategoryType::TxPdo)
+    }
+
+    /// Iterate over the PDOs in the RXPDO category (MainDevice to SubDevice).
+    pub fn rx_pdos(&self) -> SiiPdos<'a> {
+        self.pdos(CategoryType::RxPdo)
+    }
+
+    fn pdos(&self, category: CategoryType) -> SiiPdos<'a> {
+        match self.category_of(category) {
+            Ok(data) => SiiPdos { data, error: None },
+            Err(e) => SiiPdos {
+                data: &[],
+                error: Some(e),
+            },
+        }
+    }
+
+    /// Decode the entire image, resolving PDO and PDO entry names from the Strings category.
+    ///
+    /// Non-ASCII characters in strings are replaced with `'?'`.
+    #[cfg(feature = "std")]
+    pub fn decode(&self) -> Result<DecodedSiiImage, Error> {
+        let strings = self
+            .strings()?
+            .map(|s| s.map(lossy_string))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let name = |index: u8| {
+            usize::from(index)
+                .checked_sub(1)
+                .and_then(|index| strings.get(index))
+                .cloned()
+        };
+
+        let decode_pdos = |pdos: SiiPdos<'_>| {
+            pdos.map(|pdo| {
+                let pdo = pdo?;
+
+                Ok(DecodedSiiPdo {
+                    index: pdo.index,
+                    sync_manager: pdo.sync_manager,
+                    dc_sync: pdo.dc_sync,
+                    name: name(pdo.name_string_idx),
+                    flags: pdo.flags,
+                    entries: pdo
+                        .entries
+                        .map(|entry| {
+                            entry.map(|entry| DecodedSiiPdoEntry {
+                                name: name(entry.name_string_idx),
+                                entry,
+                            })
+                        })
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+        };
+
+        Ok(DecodedSiiImage {
+            config_area: self.config_area()?,
+            identity: self.identity()?,
+            bootstrap_mailbox: self.bootstrap_mailbox()?,
+            mailbox: self.mailbox()?,
+            mailbox_protocols: self.mailbox_protocols()?,
+            eeprom_size: self.eeprom_size()?,
+            general: self.general()?,
+            fmmus: self.fmmus()?.collect::<Result<_, _>>()?,
+            sync_managers: self.sync_managers()?.collect(),
+            tx_pdos: decode_pdos(self.tx_pdos())?,
+            rx_pdos: decode_pdos(self.rx_pdos())?,
+            dc_op_modes: self.dc_op_modes()?.collect::<Result<_, _>>()?,
+            strings,
+        })
+    }
+
+    /// Iterate over the Distributed Clocks operation modes in the DC category.
+    pub fn dc_op_modes(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<SiiDcOpMode, Error>> + 'a, Error> {
+        let data = self.category_of(CategoryType::DistributedClock)?;
+
+        Ok(data
+            .chunks_exact(SiiDcOpMode::PACKED_LEN)
+            .map(|chunk| Ok(SiiDcOpMode::unpack_from_slice(chunk)?)))
+    }
+}
+
+/// Iterator over category headers in an [`SiiImage`].
+#[derive(Debug, Clone)]
+pub struct SiiCategories<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Iterator for SiiCategories<'_> {
+    type Item = Result<SiiCategory, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(header) = self.bytes.get(self.pos..self.pos + 4) else {
+            // Image ended without an end marker
+            self.pos = usize::MAX - 4;
+
+            return None;
+        };
+
+        let category = u16::from_le_bytes([header[0], header[1]]);
+        let len_words = u16::from_le_bytes([header[2], header[3]]);
+
+        if category == u16::from(CategoryType::End) {
+            self.pos = usize::MAX - 4;
+
+            return None;
+        }
+
+        let start = self.pos + 4;
+
+        let Ok(start_word) = u16::try_from(start / 2) else {
+            self.pos = usize::MAX - 4;
+
+            return Some(Err(Error::Eeprom(EepromError::SectionOverrun)));
+        };
+
+        self.pos = start + usize::from(len_words) * 2;
+
+        Some(Ok(SiiCategory {
+            category,
+            start_word,
+            len_words,
+        }))
+    }
+}
+
+impl FusedIterator for SiiCategories<'_> {}
+
+/// Iterator over the raw bytes of strings in an [`SiiImage`].
+#[derive(Debug, Clone)]
+pub struct SiiStrings<'a> {
+    data: &'a [u8],
+    remaining: u8,
+}
+
+impl<'a> Iterator for SiiStrings<'a> {
+    type Item = Result<&'a [u8], Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        let Some((len, rest)) = self.data.split_first() else {
+            self.remaining = 0;
+
+            return Some(Err(Error::Eeprom(EepromError::SectionUnderrun)));
+        };
+
+        let Some((s, rest)) = rest.split_at_checked(usize::from(*len)) else {
+            self.remaining = 0;
+
+            return Some(Err(Error::Eeprom(EepromError::SectionUnderrun)));
+        };
+
+        self.data = rest;
+
+        // Get rid of any C null terminators
+        let s = match s.iter().position(|char| *char == 0x00) {
+            Some(end) => &s[0..end],
+            None => s,
+        };
+
+        Some(Ok(s))
+    }
+}
+
+/// A PDO decoded from an [`SiiImage`].
+#[derive(Debug, Clone)]
+pub struct SiiPdoView<'a> {
+    /// PDO index, e.g. `0x1a00`.
+    pub index: u16,
+    /// Sync manager this PDO is assigned to, or `0xff` if it is not assigned by default.
+    pub sync_manager: u8,
+    /// DC sync mode.
+    pub dc_sync: u8,
+    /// PDO name string index.
+    pub name_string_idx: u8,
+    /// PDO flags.
+    pub flags: PdoFlags,
+    /// Entries mapped into this PDO.
+    pub entries: SiiPdoEntries<'a>,
+}
+
+/// Iterator over the PDOs in an [`SiiImage`] category.
+#[derive(Debug, Clone)]
+pub struct SiiPdos<'a> {
+    data: &'a [u8],
+    error: Option<Error>,
+}
+
+impl<'a> Iterator for SiiPdos<'a> {
+    type Item = Result<SiiPdoView<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let Some((header, rest)) = self.data.split_at_checked(PDO_HEADER_LEN) else {
+            self.data = &[];
+
+            return Some(Err(Error::Eeprom(EepromError::SectionUnderrun)));
+        };
+
+        let num_entries = usize::from(header[2]);
+
+        let Some((entries, rest)) = rest.split_at_checked(num_entries * SiiPdoEntry::PACKED_LEN)
+        else {
+            self.data = &[];
+
+            return Some(Err(Error::Eeprom(EepromError::SectionUnderrun)));
+        };
+
+        self.data = rest;
+
+        Some(Ok(SiiPdoView {
+            index: u16::from_le_bytes([header[0], header[1]]),
+            sync_manager: header[3],
+            dc_sync: header[4],
+            name_string_idx: header[5],
+            flags: PdoFlags::from_bits_retain(u16::from_le_bytes([header[6], header[7]])),
+            entries: SiiPdoEntries { data: entries },
+        }))
+    }
+}
+
+impl FusedIterator for SiiPdos<'_> {}
+
+/// Iterator over the entries of an [`SiiPdoView`].
+#[derive(Debug, Clone)]
+pub struct SiiPdoEntries<'a> {
+    data: &'a [u8],
+}
+
+impl Iterator for SiiPdoEntries<'_> {
+    type Item = Result<SiiPdoEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (entry, rest) = self.data.split_at_checked(SiiPdoEntry::PACKED_LEN)?;
+
+        self.data = rest;
+
+        Some(SiiPdoEntry::unpack_from_slice(entry).map_err(Error::from))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.data.len() / SiiPdoEntry::PACKED_LEN;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SiiPdoEntries<'_> {}
+
+/// Convert raw SII string bytes to a `String`, replacing any non-ASCII characters with `'?'`.
+#[cfg(feature = "std")]
+fn lossy_string(s: &[u8]) -> String {
+    s.iter()
+        .map(|c| if c.is_ascii() { char::from(*c) } else { '?' })
+        .collect()
+}
+
+/// A fully decoded SII EEPROM image, returned by [`SiiImage::decode`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSiiImage {
+    /// Configuration area loaded into the ESC on power up.
+    pub config_area: SiiConfigArea,
+    /// Vendor ID, product ID, revision and serial number.
+    pub identity: SubDeviceIdentity,
+    /// Bootstrap mailbox offsets and sizes.
+    pub bootstrap_mailbox: SiiMailbox,
+    /// Standard mailbox offsets and sizes.
+    pub mailbox: SiiMailbox,
+    /// Supported mailbox protocols.
+    pub mailbox_protocols: MailboxProtocols,
+    /// Total EEPROM size in bytes.
+    pub eeprom_size: usize,
+    /// Strings, with non-ASCII characters replaced by `'?'`. String index `1` is the first item.
+    pub strings: Vec<String>,
+    /// General category.
+    pub general: Option<SiiGeneralCategory>,
+    /// FMMU usage.
+    pub fmmus: Vec<FmmuUsage>,
+    /// Sync managers.
+    pub sync_managers: Vec<SiiSyncManager>,
+    /// TxPDOs (SubDevice to MainDevice).
+    pub tx_pdos: Vec<DecodedSiiPdo>,
+    /// RxPDOs (MainDevice to SubDevice).
+    pub rx_pdos: Vec<DecodedSiiPdo>,
+    /// Distributed Clocks operation modes.
+    pub dc_op_modes: Vec<SiiDcOpMode>,
+}
+
+#[cfg(feature = "std")]
+impl DecodedSiiImage {
+    /// Read and decode an SII image from a binary file.
+    ///
+    /// Decoding errors are returned as [`std::io::ErrorKind::InvalidData`].
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+
+        SiiImage::new(&bytes)
+            .and_then(|image| image.decode())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// A PDO in a [`DecodedSiiImage`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSiiPdo {
+    /// PDO index, e.g. `0x1a00`.
+    pub index: u16,
+    /// Sync manager this PDO is assigned to, or `0xff` if it is not assigned by default.
+    pub sync_manager: u8,
+    /// DC sync mode.
+    pub dc_sync: u8,
+    /// PDO name.
+    pub name: Option<String>,
+    /// PDO flags.
+    pub flags: PdoFlags,
+    /// Entries mapped into this PDO.
+    pub entries: Vec<DecodedSiiPdoEntry>,
+}
+
+/// A PDO entry in a [`DecodedSiiPdo`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSiiPdoEntry {
+    /// Raw entry.
+    pub entry: SiiPdoEntry,
+    /// Entry name.
+    pub name: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eeprom::file_provider::EepromFile,
+        sii::{SiiImageBuilder, SiiPdo},
+        subdevice::eeprom::SubDeviceEeprom,
+    };
+
+    const IMAGES: [&[u8]; 5] = [
+        include_bytes!("../../dumps/eeprom/akd.hex"),
+        include_bytes!("../../dumps/eeprom/ek1100.hex"),
+        include_bytes!("../../dumps/eeprom/el2262.bin"),
+        include_bytes!("../../dumps/eeprom/el2828.hex"),
+        include_bytes!("../../dumps/eeprom/el2889.hex"),
+    ];
+
+    /// Check the decoder against the EEPROM reader used with real SubDevices.
+    #[tokio::test]
+    async fn matches_device_reader() {
+        crate::test_logger();
+
+        for bytes in IMAGES {
+            let image = SiiImage::new(bytes).unwrap();
+            let e = SubDeviceEeprom::new(EepromFile::new(bytes));
+
+            assert_eq!(image.validate_checksum(), Ok(()));
+            assert_eq!(image.identity(), e.identity().await);
+
+            let mailbox = image.mailbox().unwrap();
+            let expected = e.mailbox_config().await.unwrap();
+
+            assert_eq!(mailbox.receive_offset, expected.subdevice_receive_offset);
+            assert_eq!(mailbox.receive_size, expected.subdevice_receive_size);
+            assert_eq!(mailbox.send_offset, expected.subdevice_send_offset);
+            assert_eq!(mailbox.send_size, expected.subdevice_send_size);
+            assert_eq!(image.mailbox_protocols(), Ok(expected.supported_protocols));
+
+            assert_eq!(image.eeprom_size(), e.size().await);
+
+            assert_eq!(
+                image
+                    .fmmus()
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap(),
+                e.fmmus().await.unwrap().to_vec()
+            );
+
+            let sync_managers = image.sync_managers().unwrap().collect::<Vec<_>>();
+            let expected = e.sync_managers().await.unwrap();
+
+            assert_eq!(sync_managers.len(), expected.len());
+
+            for (sm, expected) in sync_managers.iter().zip(expected.iter()) {
+                assert_eq!(sm.start_addr, expected.start_addr);
+                assert_eq!(sm.length, expected.length);
+                assert_eq!(sm.enable, expected.enable);
+                assert_eq!(sm.usage_type, expected.usage_type);
+            }
+
+            for (pdos, expected) in [
+                (image.tx_pdos(), e.maindevice_read_pdos().await.unwrap()),
+                (image.rx_pdos(), e.maindevice_write_pdos().await.unwrap()),
+            ] {
+                let pdos = pdos.collect::<Result<Vec<_>, _>>().unwrap();
+
+                assert_eq!(pdos.len(), expected.len());
+
+                for (pdo, expected) in pdos.iter().zip(expected.iter()) {
+                    assert_eq!(pdo.index, expected.index);
+                    assert_eq!(pdo.sync_manager, expected.sync_manager);
+                    assert_eq!(pdo.name_string_idx, expected.name_string_idx);
+                    assert_eq!(pdo.entries.len(), usize::from(expected.num_entries));
+                    assert_eq!(
+                        pdo.entries
+                            .clone()
+                            .map(|entry| u16::from(entry.unwrap().bit_len))
+                            .sum::<u16>(),
+                        expected.bit_len
+                    );
+                }
+            }
+
+            assert_eq!(
+                image
+                    .dc_op_modes()
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap(),
+                e.dc_op_modes().await.unwrap().to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn strings() {
+        let image = SiiImage::new(include_bytes!("../../dumps/eeprom/el2828.hex")).unwrap();
+
+        let general = image.general().unwrap().unwrap();
+
+        assert_eq!(
+            image.string(general.name_string_idx),
+            Ok(Some(b"EL2828 8K. Dig. Ausgang 24V, 2A".as_slice()))
+        );
+        assert_eq!(image.string(0), Ok(None));
+        assert_eq!(image.string(255), Ok(None));
+    }
+
+    #[test]
+    fn decode() {
+        let decoded = SiiImage::new(include_bytes!("../../dumps/eeprom/el2828.hex"))
+            .unwrap()
+            .decode()
+            .unwrap();
+
+        assert_eq!(decoded.identity.product_id, 0x0b0c3052);
+        assert_eq!(decoded.rx_pdos.len(), 8);
+        assert_eq!(decoded.rx_pdos[0].name.as_deref(), Some("Channel 1"));
+        assert_eq!(decoded.rx_pdos[0].entries.len(), 1);
+        assert_eq!(
+            decoded.rx_pdos[0].entries[0].name.as_deref(),
+            Some("Output")
+        );
+    }
+
+    #[test]
+    fn decode_latin1() {
+        let decoded = SiiImage::new(include_bytes!("../../dumps/eeprom/el2262.bin"))
+            .unwrap()
+            .decode()
+            .unwrap();
+
+        assert!(decoded.strings.iter().any(|s| s.contains('?')));
+    }
+
+    #[test]
+    fn built_image() {
+        let entries = [SiiPdoEntry {
+            index: 0x7000,
+            sub_index: 1,
+            name_string_idx: 2,
+            data_type: 1,
+            bit_len: 1,
+            flags: 0,
+        }];
+
+        let pdos = [SiiPdo {
+            index: 0x1600,
+            sync_manager: 2,
+            dc_sync: 0,
+            name_string_idx: 1,
+            flags: PdoFlags::PDO_MANDATORY,
+            entries: &entries,
+        }];
+
+        let mut buf = [0xffu8; 512];
+
+        let len = SiiImageBuilder::new(SubDeviceIdentity {
+            vendor_id: 0x1234,
+            product_id: 0x5678,
+            revision: 1,
+            serial: 2,
+        })
+        .with_strings(&["Outputs", "Output 1"])
+        .with_rx_pdos(&pdos)
+        .build(&mut buf)
+        .unwrap();
+
+        let decoded = SiiImage::new(&buf[0..len]).unwrap().decode().unwrap();
+
+        assert_eq!(decoded.strings, ["Outputs", "Output 1"]);
+        assert_eq!(
+            decoded.rx_pdos,
+            [DecodedSiiPdo {
+                index: 0x1600,
+                sync_manager: 2,
+                dc_sync: 0,
+                name: Some("Outputs".into()),
+                flags: PdoFlags::PDO_MANDATORY,
+                entries: vec![DecodedSiiPdoEntry {
+                    entry: entries[0],
+                    name: Some("Output 1".into()),
+                }],
+            }]
+        );
+        assert!(decoded.tx_pdos.is_empty());
+    }
+
+    #[test]
+    fn invalid_image() {
+        assert_eq!(
+            SiiImage::new(&[0u8; 64]).map(|_| ()),
+            Err(Error::Eeprom(EepromError::InvalidImage))
+        );
+        assert_eq!(
+            SiiImage::new(&[0u8; 129]).map(|_| ()),
+            Err(Error::Eeprom(EepromError::InvalidImage))
+        );
+    }
+
+    #[test]
+    fn truncated_category() {
+        let mut image = include_bytes!("../../dumps/eeprom/el2828.hex")[0..0x100].to_vec();
+
+        // Make sure there's no end marker
+        image[0xfc..].fill(0x00);
+
+        let image = SiiImage::new(&image).unwrap();
+
+        assert!(
+            image
+                .categories()
+                .map(|c| image.data(&c?))
+                .any(|data| data == Err(Error::Eeprom(EepromError::SectionUnderrun)))
+        );
+    }
+}
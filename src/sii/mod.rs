@@ -0,0 +1,180 @@
+//! SubDevice Information Interface (SII) EEPROM image types.
+//!
+//! These types describe the contents of a SubDevice's EEPROM as defined in ETG2010, and can be used
+//! with [`SiiImageBuilder`] to generate a complete EEPROM image, e.g. for custom SubDevice
+//! firmware. Existing images can be inspected with [`SiiImage`].
+
+mod builder;
+mod image;
+
+pub use crate::eeprom::types::{
+    FmmuUsage, MailboxProtocols, PdoFlags, SiiCategory, SiiDcOpMode, SiiSyncUnit,
+    SyncManagerEnable, SyncManagerType,
+};
+pub use builder::SiiImageBuilder;
+#[cfg(feature = "std")]
+pub use image::{DecodedSiiImage, DecodedSiiPdo, DecodedSiiPdoEntry};
+pub use image::{SiiCategories, SiiImage, SiiPdoEntries, SiiPdoView, SiiPdos, SiiStrings};
+
+/// Byte offset of the bootstrap mailbox config, ETG2010 Table 2 word `0x0014`.
+const BOOTSTRAP_MAILBOX_POSITION: usize = 0x28;
+/// Byte offset of the EEPROM size word, ETG2010 Table 2 word `0x003e`.
+const SIZE_POSITION: usize = 0x7c;
+/// Length of the fixed header before the first category.
+const HEADER_LEN: usize = 0x80;
+
+/// The first 7 words of the EEPROM, defined in ETG2010 Table 2.
+///
+/// These values are loaded into the ESC registers on power up. The checksum in word `0x0007` is
+/// calculated when the image is built.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 14)]
+pub struct SiiConfigArea {
+    /// Initial value of the PDI control register (`0x0140`).
+    #[wire(bytes = 2)]
+    pub pdi_control: u16,
+    /// Initial value of the PDI configuration register (`0x0150`).
+    #[wire(bytes = 2)]
+    pub pdi_config: u16,
+    /// Initial value of the sync impulse length register (`0x0982`), in multiples of 10ns.
+    #[wire(bytes = 2)]
+    pub sync_impulse_len: u16,
+    /// Initial value of the extended PDI configuration register (`0x0152`).
+    #[wire(bytes = 2)]
+    pub pdi_config2: u16,
+    /// Configured station alias, loaded into register `0x0012`.
+    #[wire(bytes = 2, post_skip_bytes = 4)]
+    pub station_alias: u16,
+}
+
+/// Mailbox sync manager offsets and sizes, defined in ETG2010 Table 2.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 8)]
+pub struct SiiMailbox {
+    /// MainDevice to SubDevice receive mailbox address offset.
+    #[wire(bytes = 2)]
+    pub receive_offset: u16,
+    /// MainDevice to SubDevice receive mailbox size.
+    #[wire(bytes = 2)]
+    pub receive_size: u16,
+    /// SubDevice to MainDevice send mailbox address offset.
+    #[wire(bytes = 2)]
+    pub send_offset: u16,
+    /// SubDevice to MainDevice send mailbox size.
+    #[wire(bytes = 2)]
+    pub send_size: u16,
+}
+
+/// SII "General" category, defined in ETG1000.6 Table 21.
+///
+/// String indices are 1-based indices into the strings passed to
+/// [`SiiImageBuilder::with_strings`]. An index of zero means no string.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 32)]
+pub struct SiiGeneralCategory {
+    /// Group name string index.
+    #[wire(bytes = 1)]
+    pub group_string_idx: u8,
+    /// Image name string index.
+    #[wire(bytes = 1)]
+    pub image_string_idx: u8,
+    /// Order code string index.
+    #[wire(bytes = 1)]
+    pub order_string_idx: u8,
+    /// Device name string index.
+    #[wire(bytes = 1, post_skip_bytes = 1)]
+    pub name_string_idx: u8,
+    /// CoE details bit field, e.g. `Enable SDO` and `Enable PDO Assign`.
+    #[wire(bytes = 1)]
+    pub coe_details: u8,
+    /// FoE details. Non-zero if FoE is supported.
+    #[wire(bytes = 1)]
+    pub foe_details: u8,
+    /// EoE details. Non-zero if EoE is supported.
+    #[wire(bytes = 1)]
+    pub eoe_details: u8,
+    /// Number of SoE channels.
+    #[wire(bytes = 1)]
+    pub soe_channels: u8,
+    /// Number of DS402 channels.
+    #[wire(bytes = 1)]
+    pub ds402_channels: u8,
+    /// SysmanClass.
+    #[wire(bytes = 1)]
+    pub sysman_class: u8,
+    /// General flags, e.g. `Enable SafeOp` and `Enable notLRW`.
+    #[wire(bytes = 1)]
+    pub flags: u8,
+    /// E-Bus current consumption in mA. Negative values mean the device feeds current into the
+    /// E-Bus.
+    #[wire(bytes = 2, post_skip_bytes = 2)]
+    pub ebus_current: i16,
+    /// Physical port types, one nibble per port starting with port 0.
+    #[wire(bytes = 2)]
+    pub physical_ports: u16,
+    /// ESC memory address of the identification ID.
+    #[wire(bytes = 2, post_skip_bytes = 12)]
+    pub physical_memory_addr: u16,
+}
+
+/// An entry in the SII "SyncM" category, defined in ETG2010.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SiiSyncManager {
+    /// Physical start address.
+    pub start_addr: u16,
+    /// Length in bytes.
+    pub length: u16,
+    /// Initial value of the sync manager control register.
+    pub control: u8,
+    /// Enable flags.
+    pub enable: SyncManagerEnable,
+    /// Usage type.
+    pub usage_type: SyncManagerType,
+}
+
+/// A PDO in the SII "TXPDO" or "RXPDO" categories, defined in ETG2010 Table 14.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SiiPdo<'a> {
+    /// PDO index, e.g. `0x1a00`.
+    pub index: u16,
+    /// Sync manager this PDO is assigned to, or `0xff` if it is not assigned by default.
+    pub sync_manager: u8,
+    /// DC sync mode.
+    pub dc_sync: u8,
+    /// PDO name string index.
+    pub name_string_idx: u8,
+    /// PDO flags.
+    pub flags: PdoFlags,
+    /// Entries mapped into this PDO.
+    pub entries: &'a [SiiPdoEntry],
+}
+
+/// A PDO entry in the SII "TXPDO" or "RXPDO" categories, defined in ETG2010.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ethercrab_wire::EtherCrabWireReadWrite)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[wire(bytes = 8)]
+pub struct SiiPdoEntry {
+    /// Object index, e.g. `0x6000`.
+    #[wire(bytes = 2)]
+    pub index: u16,
+    /// Object sub-index.
+    #[wire(bytes = 1)]
+    pub sub_index: u8,
+    /// Entry name string index.
+    #[wire(bytes = 1)]
+    pub name_string_idx: u8,
+    /// Raw data type. See page 103 of ETG2000.
+    #[wire(bytes = 1)]
+    pub data_type: u8,
+    /// Length of the entry in bits.
+    #[wire(bytes = 1)]
+    pub bit_len: u8,
+    /// Entry flags.
+    #[wire(bytes = 2)]
+    pub flags: u16,
+}
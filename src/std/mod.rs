@@ -17,6 +17,8 @@ use std::{
 
 #[cfg(target_os = "windows")]
 pub use self::windows::{TxRxTaskConfig, ethercat_now, tx_rx_task_blocking};
+#[cfg(target_os = "linux")]
+pub use unix::tx_rx_task_redundant;
 #[cfg(unix)]
 pub use unix::{ethercat_now, tx_rx_task};
 // io_uring is Linux-only
@@ -78,6 +78,17 @@ impl RawSocketDesc {
     pub fn interface_mtu(&mut self) -> io::Result<usize> {
         ifreq_ioctl(self.lower, &mut self.ifreq, libc::SIOCGIFMTU).map(|mtu| mtu as usize)
     }
+
+    /// Whether the interface has a link.
+    pub fn link_up(&self) -> io::Result<bool> {
+        let mut ifreq = ifreq {
+            ifr_name: self.ifreq.ifr_name,
+            ifr_data: 0,
+        };
+
+        ifreq_ioctl(self.lower, &mut ifreq, libc::SIOCGIFFLAGS)
+            .map(|flags| flags & libc::IFF_RUNNING != 0)
+    }
 }
 
 impl AsRawFd for RawSocketDesc {
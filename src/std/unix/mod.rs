@@ -10,6 +10,8 @@ use self::bpf::BpfDevice as RawSocketDesc;
 #[cfg(target_os = "linux")]
 pub(in crate::std) use self::linux::RawSocketDesc;
 
+#[cfg(target_os = "linux")]
+use crate::RedundantPort;
 use crate::{
     error::Error,
     fmt,
@@ -49,29 +51,7 @@ impl<'a> Future for TxRxFut<'a> {
 
         while let Some(frame) = unsafe { self.tx.as_mut().unwrap_unchecked() }.next_sendable_frame()
         {
-            let res = frame.send_blocking(|data| {
-                match Pin::new(&mut self.socket).poll_write(ctx, data) {
-                    Poll::Ready(Ok(bytes_written)) => {
-                        if bytes_written != data.len() {
-                            fmt::error!("Only wrote {} of {} bytes", bytes_written, data.len());
-
-                            Err(Error::PartialSend {
-                                len: data.len(),
-                                sent: bytes_written,
-                            })
-                        } else {
-                            Ok(bytes_written)
-                        }
-                    }
-
-                    Poll::Ready(Err(e)) => {
-                        fmt::error!("Send PDU failed: {}", e);
-
-                        Err(Error::SendFrame)
-                    }
-                    Poll::Pending => Ok(0),
-                }
-            });
+            let res = frame.send_blocking(|data| write_frame(&mut self.socket, ctx, data));
 
             if let Err(e) = res {
                 fmt::error!("Send PDU failed: {}", e);
@@ -114,6 +94,165 @@ impl<'a> Future for TxRxFut<'a> {
     }
 }
 
+/// Write a frame to a socket.
+fn write_frame(
+    socket: &mut Async<RawSocketDesc>,
+    ctx: &mut core::task::Context<'_>,
+    data: &[u8],
+) -> Result<usize, Error> {
+    match Pin::new(socket).poll_write(ctx, data) {
+        Poll::Ready(Ok(bytes_written)) => {
+            if bytes_written != data.len() {
+                fmt::error!("Only wrote {} of {} bytes", bytes_written, data.len());
+
+                Err(Error::PartialSend {
+                    len: data.len(),
+                    sent: bytes_written,
+                })
+            } else {
+                Ok(bytes_written)
+            }
+        }
+
+        Poll::Ready(Err(e)) => {
+            fmt::error!("Send PDU failed: {}", e);
+
+            Err(Error::SendFrame)
+        }
+        Poll::Pending => Ok(0),
+    }
+}
+
+/// How often to check the link state of each interface in a redundant ring.
+#[cfg(target_os = "linux")]
+const LINK_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+#[cfg(target_os = "linux")]
+struct TxRxRedundantFut<'a> {
+    main: Async<RawSocketDesc>,
+    redundant: Async<RawSocketDesc>,
+    mtu: usize,
+    /// Link state of the main and redundant interfaces.
+    links: (bool, bool),
+    last_link_check: Option<std::time::Instant>,
+    tx: Option<PduTx<'a>>,
+    rx: Option<PduRx<'a>>,
+}
+
+#[cfg(target_os = "linux")]
+impl TxRxRedundantFut<'_> {
+    fn check_links(&mut self) {
+        if self
+            .last_link_check
+            .is_some_and(|last| last.elapsed() < LINK_CHECK_INTERVAL)
+        {
+            return;
+        }
+
+        self.last_link_check = Some(std::time::Instant::now());
+
+        // Assume the link is up if its state can't be read
+        let links = (
+            self.main.get_ref().link_up().unwrap_or(true),
+            self.redundant.get_ref().link_up().unwrap_or(true),
+        );
+
+        if links != self.links {
+            fmt::info!(
+                "Redundant ring link state changed: main {}, redundant {}",
+                links.0,
+                links.1
+            );
+
+            self.links = links;
+
+            if let Some(rx) = self.rx.as_mut() {
+                rx.set_link_status(links.0, links.1);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl<'a> Future for TxRxRedundantFut<'a> {
+    type Output = Result<(PduTx<'a>, PduRx<'a>), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let Some(tx) = this.tx.as_mut() else {
+            return Poll::Ready(Err(Error::Internal));
+        };
+
+        // Re-register waker to make sure this future is polled again
+        tx.replace_waker(ctx.waker());
+
+        if tx.should_exit() {
+            fmt::debug!("Redundant TX/RX future was asked to exit");
+
+            return match (this.tx.take(), this.rx.take()) {
+                (Some(tx), Some(rx)) => Poll::Ready(Ok((tx.release(), rx.release()))),
+                _ => Poll::Ready(Err(Error::Internal)),
+            };
+        }
+
+        this.check_links();
+
+        let (Some(tx), Some(rx)) = (this.tx.as_mut(), this.rx.as_mut()) else {
+            return Poll::Ready(Err(Error::Internal));
+        };
+
+        while let Some(frame) = tx.next_sendable_frame() {
+            let res = frame.send_blocking_redundant(|port, data| {
+                let (socket, link_up) = match port {
+                    RedundantPort::Main => (&mut this.main, this.links.0),
+                    RedundantPort::Redundant => (&mut this.redundant, this.links.1),
+                };
+
+                if !link_up {
+                    return Err(Error::SendFrame);
+                }
+
+                write_frame(socket, ctx, data)
+            });
+
+            if let Err(e) = res {
+                fmt::error!("Send PDU failed: {}", e);
+
+                return Poll::Ready(Err(e));
+            }
+        }
+
+        let mut buf = vec![0; this.mtu];
+
+        for (socket, port) in [
+            (&mut this.main, RedundantPort::Main),
+            (&mut this.redundant, RedundantPort::Redundant),
+        ] {
+            match Pin::new(socket).poll_read(ctx, &mut buf) {
+                Poll::Ready(Ok(n)) => {
+                    // Wake again in case there are more frames to consume
+                    ctx.waker().wake_by_ref();
+
+                    let packet = buf.get(0..n).ok_or(Error::Internal)?;
+
+                    if let Err(e) = rx.receive_frame_redundant(packet, port) {
+                        fmt::error!("Failed to receive frame: {}", e);
+
+                        return Poll::Ready(Err(Error::ReceiveFrame));
+                    }
+                }
+                Poll::Ready(Err(e)) => {
+                    fmt::error!("Receive PDU failed: {}", e);
+                }
+                Poll::Pending => (),
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
 /// Spawn a TX and RX task.
 pub fn tx_rx_task<'sto>(
     interface: &str,
@@ -148,6 +287,62 @@ pub fn tx_rx_task<'sto>(
     Ok(task)
 }
 
+/// Spawn a TX and RX task for a redundant ring driven by two network interfaces.
+///
+/// `main_interface` must be connected to the input port of the first SubDevice and
+/// `redundant_interface` to the free port of the last SubDevice. Every frame is sent on both
+/// interfaces so process data keeps flowing if the ring is broken at any point, including either
+/// of the cables to the MainDevice. Responses are merged by
+/// [`PduRx::receive_frame_redundant`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, Timeouts, std::tx_rx_task_redundant};
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+///
+/// std::thread::spawn(move || {
+///     smol::block_on(tx_rx_task_redundant("eth0", "eth1", tx, rx).expect("spawn TX/RX task"))
+/// });
+/// ```
+#[cfg(target_os = "linux")]
+pub fn tx_rx_task_redundant<'sto>(
+    main_interface: &str,
+    redundant_interface: &str,
+    pdu_tx: PduTx<'sto>,
+    pdu_rx: PduRx<'sto>,
+) -> Result<impl Future<Output = Result<(PduTx<'sto>, PduRx<'sto>), Error>> + 'sto, std::io::Error>
+{
+    let mut main = RawSocketDesc::new(main_interface)?;
+    let mut redundant = RawSocketDesc::new(redundant_interface)?;
+
+    let mtu = main.interface_mtu()?.min(redundant.interface_mtu()?);
+
+    fmt::debug!(
+        "Opening redundant ring {} and {} with MTU {}",
+        main_interface,
+        redundant_interface,
+        mtu
+    );
+
+    let task = TxRxRedundantFut {
+        main: Async::new(main)?,
+        redundant: Async::new(redundant)?,
+        mtu,
+        links: (true, true),
+        last_link_check: None,
+        tx: Some(pdu_tx),
+        rx: Some(pdu_rx),
+    };
+
+    Ok(task)
+}
+
 /// Get the current time in nanoseconds from the EtherCAT epoch, 2000-01-01.
 ///
 /// On POSIX systems, this function uses the monotonic clock provided by the system.
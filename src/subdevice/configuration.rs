@@ -1,4 +1,6 @@
-use super::{SubDevice, SubDeviceRef};
+#[cfg(feature = "pdi-symbols")]
+use super::symbols::PendingSymbols;
+use super::{Pdo, SubDevice, SubDeviceRef};
 use crate::{
     SubIndex,
     eeprom::types::{
@@ -12,6 +14,8 @@ use crate::{
     pdi::{PdiOffset, PdiSegment},
     register::RegisterAddress,
     subdevice::types::{Mailbox, MailboxConfig},
+    subdevice_config::{ConfigAction, SdoValue, SdoWrite},
+    subdevice_group::PdiLayout,
     subdevice_state::SubDeviceState,
     sync_manager_channel::{Enable, SM_BASE_ADDRESS, Status, SyncManagerChannel},
 };
@@ -32,7 +36,10 @@ where
         // to master mode here, now that the transition is complete.
         self.set_eeprom_mode(SiiOwner::Master).await?;
 
-        let sync_managers = self.eeprom().sync_managers().await?;
+        let sync_managers = self
+            .eeprom_with_cache(self.identity)
+            .sync_managers()
+            .await?;
 
         // Mailboxes must be configured in INIT state
         self.configure_mailbox_sms(&sync_managers).await?;
@@ -53,6 +60,100 @@ where
         Ok(())
     }
 
+    /// Disable all FMMUs and SMs on this SubDevice, e.g. before configuring it again.
+    ///
+    /// [`configure_fmmus`](SubDeviceRef::configure_fmmus) extends enabled FMMUs instead of
+    /// overwriting them, so a SubDevice that kept its previous configuration would otherwise end
+    /// up with a corrupt mapping.
+    pub(crate) async fn clear_fmmus_and_sync_managers(&self) -> Result<(), Error> {
+        // See ETG1000.4 Table 57. Some devices aren't able to blank the entire region so we loop
+        // through all offsets.
+        for fmmu_idx in 0..16 {
+            self.write(RegisterAddress::fmmu(fmmu_idx))
+                .send(self.maindevice, &Fmmu::default())
+                .await?;
+        }
+
+        // See ETG1000.4 Table 59
+        for sm_idx in 0..16 {
+            self.write(RegisterAddress::sync_manager(sm_idx))
+                .send(self.maindevice, &SyncManagerChannel::default())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the PDO mapping from the MainDevice's configuration registry, if this SubDevice matches
+    /// an entry, otherwise call the registry's unconfigured hook.
+    pub(crate) fn apply_registered_pdo_mapping(&mut self) {
+        let Some(configs) = self.maindevice.subdevice_configs else {
+            return;
+        };
+
+        match configs
+            .find(&self.identity(), self.explicit_device_id)
+            .map(|entry| entry.action)
+        {
+            Some(ConfigAction::Static(config)) => {
+                if !config.pdo_mapping.is_empty() {
+                    self.set_pdo_mapping(config.pdo_mapping);
+                }
+            }
+            #[cfg(feature = "std")]
+            Some(ConfigAction::Callback(_)) => (),
+            None => configs.unconfigured(self),
+        }
+    }
+
+    /// Perform SDO writes or call the configuration callback from the MainDevice's configuration
+    /// registry, if this SubDevice matches an entry.
+    pub(crate) async fn apply_registered_config(&mut self) -> Result<(), Error> {
+        let Some(entry) = self
+            .maindevice
+            .subdevice_configs
+            .and_then(|configs| configs.find(&self.identity(), self.explicit_device_id))
+        else {
+            return Ok(());
+        };
+
+        fmt::debug!(
+            "SubDevice {:#06x} applying registered configuration",
+            self.configured_address
+        );
+
+        match entry.action {
+            ConfigAction::Static(config) => {
+                for write in config.sdo_writes {
+                    let SdoWrite {
+                        index,
+                        sub_index,
+                        value,
+                    } = *write;
+
+                    match value {
+                        SdoValue::U8(value) => self.sdo_write(index, sub_index, value).await?,
+                        SdoValue::U16(value) => self.sdo_write(index, sub_index, value).await?,
+                        SdoValue::U32(value) => self.sdo_write(index, sub_index, value).await?,
+                        SdoValue::U64(value) => self.sdo_write(index, sub_index, value).await?,
+                        SdoValue::Bytes(value) => self.sdo_write(index, sub_index, value).await?,
+                    }
+                }
+            }
+            #[cfg(feature = "std")]
+            ConfigAction::Callback(callback) => {
+                callback(SubDeviceRef::new(
+                    self.maindevice,
+                    self.configured_address,
+                    &mut *self.state,
+                ))
+                .await?
+            }
+        }
+
+        Ok(())
+    }
+
     /// Second state configuration (PRE-OP -> SAFE-OP).
     ///
     /// PDOs must be configured in the PRE-OP state.
@@ -61,8 +162,9 @@ where
         mut global_offset: PdiOffset,
         group_start_address: u32,
         direction: PdoDirection,
+        layout: PdiLayout,
     ) -> Result<PdiOffset, Error> {
-        let eeprom = self.eeprom();
+        let eeprom = self.eeprom_with_cache(self.identity);
 
         let sync_managers = eeprom.sync_managers().await?;
         let fmmu_usage = eeprom.fmmus().await?;
@@ -92,26 +194,48 @@ where
             has_coe
         );
 
+        #[cfg(feature = "pdi-symbols")]
+        let mut symbols =
+            PendingSymbols::new(&eeprom, direction, self.maindevice.config.pdi_symbol_names).await;
+        #[cfg(not(feature = "pdi-symbols"))]
+        let mut symbols = PendingSymbols;
+
         let range = if has_coe {
-            self.configure_pdos_coe(&sync_managers, &fmmu_usage, direction, &mut global_offset)
-                .await?
+            self.configure_pdos_coe(
+                &sync_managers,
+                &fmmu_usage,
+                direction,
+                &mut global_offset,
+                layout,
+                &mut symbols,
+            )
+            .await?
         } else {
-            self.configure_pdos_eeprom(&sync_managers, direction, &mut global_offset)
-                .await?
+            self.configure_pdos_eeprom(
+                &sync_managers,
+                direction,
+                &mut global_offset,
+                layout,
+                &mut symbols,
+            )
+            .await?
         };
 
+        #[cfg(feature = "pdi-symbols")]
+        {
+            let mut pdi_symbols = self.state.pdi_symbols.clone();
+
+            pdi_symbols.replace(direction, &symbols, &eeprom).await?;
+
+            self.state.pdi_symbols = pdi_symbols;
+        }
+
         match direction {
             PdoDirection::MasterRead => {
-                self.state.config.io.input = PdiSegment {
-                    bytes: (range.bytes.start - group_start_address as usize)
-                        ..(range.bytes.end - group_start_address as usize),
-                };
+                self.state.config.io.input = range.relative_to(group_start_address);
             }
             PdoDirection::MasterWrite => {
-                self.state.config.io.output = PdiSegment {
-                    bytes: (range.bytes.start - group_start_address as usize)
-                        ..(range.bytes.end - group_start_address as usize),
-                };
+                self.state.config.io.output = range.relative_to(group_start_address);
             }
         };
 
@@ -163,7 +287,7 @@ where
 
     /// Configure SM0 and SM1 for mailbox communication.
     async fn configure_mailbox_sms(&mut self, sync_managers: &[SyncManager]) -> Result<(), Error> {
-        let eeprom = self.eeprom();
+        let eeprom = self.eeprom_with_cache(self.identity);
 
         // Read default mailbox configuration from SubDevice information area
         let mailbox_config = eeprom
@@ -263,6 +387,53 @@ where
         Ok(())
     }
 
+    /// Write a custom PDO mapping and assignment to the given sync manager assignment object.
+    ///
+    /// The assignment is cleared first so that PDO mappings can be changed, then written back with
+    /// its length last, as required by ETG1000.6 section 5.6.7.4.
+    async fn write_pdo_assignment(&self, sm_address: u16, pdos: &[Pdo]) -> Result<(), Error> {
+        let num_pdos = u8::try_from(pdos.len()).map_err(|_| Error::Capacity(Item::Pdo))?;
+
+        fmt::debug!(
+            "SubDevice {:#06x} custom PDO assignment {:#06x}: {} PDOs",
+            self.configured_address(),
+            sm_address,
+            num_pdos
+        );
+
+        self.sdo_write(sm_address, 0, 0u8).await?;
+
+        for pdo in pdos.iter().filter(|pdo| !pdo.entries.is_empty()) {
+            let num_entries =
+                u8::try_from(pdo.entries.len()).map_err(|_| Error::Capacity(Item::PdoEntry))?;
+
+            self.sdo_write(pdo.index, 0, 0u8).await?;
+
+            for (i, entry) in pdo.entries.iter().enumerate() {
+                fmt::trace!(
+                    "--> {:#06x}:{} = {:#06x}:{}, {} bits",
+                    pdo.index,
+                    i + 1,
+                    entry.index,
+                    entry.sub_index,
+                    entry.bit_len
+                );
+
+                self.sdo_write(pdo.index, i as u8 + 1, entry.raw()).await?;
+            }
+
+            self.sdo_write(pdo.index, 0, num_entries).await?;
+        }
+
+        for (i, pdo) in pdos.iter().enumerate() {
+            self.sdo_write(sm_address, i as u8 + 1, pdo.index).await?;
+        }
+
+        self.sdo_write(sm_address, 0, num_pdos).await?;
+
+        Ok(())
+    }
+
     /// Configure PDOs from CoE registers.
     async fn configure_pdos_coe(
         &self,
@@ -270,6 +441,8 @@ where
         fmmu_usage: &[FmmuUsage],
         direction: PdoDirection,
         global_offset: &mut PdiOffset,
+        layout: PdiLayout,
+        symbols: &mut PendingSymbols,
     ) -> Result<PdiSegment, Error> {
         if !self.state.config.mailbox.has_coe {
             fmt::warn!("Invariant: attempting to configure PDOs from COE with no SOE support");
@@ -289,6 +462,13 @@ where
         let start_offset = *global_offset;
         // let mut total_bit_len = 0;
 
+        // Sync managers are contiguous in the SubDevice's memory, so each one starts on a whole byte
+        // relative to this SubDevice's first mapped bit, even in a bit-packed PDI.
+        let mut sm_offset = start_offset;
+
+        // Custom assignment is only written to the first SM of the desired type.
+        let mut pdo_assignment = self.pdo_mapping.assignment(direction);
+
         for (sync_manager_index, sync_manager) in sync_managers.iter().enumerate() {
             let sync_manager_index = sync_manager_index as u8;
 
@@ -298,6 +478,12 @@ where
                 continue;
             }
 
+            if let Some(pdos) = pdo_assignment.take() {
+                self.write_pdo_assignment(sm_address, pdos).await?;
+            }
+
+            let sm_start_bit = start_offset.bits_to(sm_offset);
+
             // Total number of PDO assignments for this sync manager
             let num_sm_assignments = self
                 .sdo_read_expedited::<u8>(sm_address, SubIndex::Index(0))
@@ -330,6 +516,8 @@ where
 
                 let mut pdo_bit_len = 0u16;
 
+                let custom_pdo = self.pdo_mapping.find_pdo(direction, pdo);
+
                 for i in 1..=num_mappings {
                     /// Defined in ETG1000.6 Table 74/Table 75 Receive PDO Mapping.
                     ///
@@ -363,14 +551,24 @@ where
                         mapping_bit_len,
                     );
 
+                    let custom_entry = custom_pdo
+                        .and_then(|custom| custom.entries.get(usize::from(i - 1)))
+                        .filter(|entry| entry.index == index && entry.sub_index == sub_index);
+
+                    symbols.push(
+                        pdo,
+                        index,
+                        sub_index,
+                        mapping_bit_len,
+                        sm_start_bit + u32::from(sm_bit_len + pdo_bit_len),
+                        custom_entry.and_then(|entry| entry.name),
+                        custom_pdo.and_then(|custom| custom.name),
+                    )?;
+
                     pdo_bit_len += u16::from(mapping_bit_len);
                 }
 
-                let oversampling = self
-                    .oversampling_config
-                    .iter()
-                    .find_map(|(pdo_id, mul)| if *pdo_id == pdo { Some(*mul) } else { None })
-                    .unwrap_or(1);
+                let oversampling = self.pdo_mapping.oversampling_for(pdo);
 
                 let pdo_bit_len = pdo_bit_len * oversampling;
 
@@ -403,23 +601,25 @@ where
                         index: None,
                     })?;
 
+                *global_offset = sm_offset;
+
                 self.write_fmmu_config(
                     sm_bit_len,
                     fmmu_index,
                     global_offset,
+                    layout,
                     desired_sm_type,
                     &sm_config,
                 )
                 .await?;
             }
 
+            sm_offset = sm_offset.increment((sm_bit_len + 7) / 8);
+
             // total_bit_len += sm_bit_len;
         }
 
-        Ok(PdiSegment {
-            // bit_len: total_bit_len.into(),
-            bytes: start_offset.up_to(*global_offset),
-        })
+        Ok(PdiSegment::between(start_offset, *global_offset))
     }
 
     async fn write_fmmu_config(
@@ -427,9 +627,15 @@ where
         sm_bit_len: u16,
         fmmu_index: usize,
         global_offset: &mut PdiOffset,
+        layout: PdiLayout,
         desired_sm_type: SyncManagerType,
         sm_config: &SyncManagerChannel,
     ) -> Result<(), Error> {
+        let end_offset = match layout {
+            PdiLayout::ByteAligned => global_offset.increment_byte_aligned(sm_bit_len),
+            PdiLayout::BitPacked => global_offset.increment_bits(sm_bit_len),
+        };
+
         // Multiple SMs may use the same FMMU, so we'll read the existing config from the SubDevice
         let mut fmmu_config = self
             .read(RegisterAddress::fmmu(fmmu_index as u8))
@@ -438,15 +644,25 @@ where
 
         // We can use the enable flag as a sentinel for existing config because EtherCrab inits
         // FMMUs to all zeroes on startup.
-        let fmmu_config = if fmmu_config.enable {
-            fmmu_config.length_bytes += sm_config.length_bytes;
+        let fmmu_config = match (fmmu_config.enable, layout) {
+            (true, PdiLayout::ByteAligned) => {
+                fmmu_config.length_bytes += sm_config.length_bytes;
 
-            fmmu_config
-        } else {
-            Fmmu {
+                fmmu_config
+            }
+            // The logical area of a bit-packed FMMU may start and end part way through a byte, so
+            // extend it up to the end of this SM's bits instead of adding whole bytes.
+            (true, PdiLayout::BitPacked) => {
+                fmmu_config.length_bytes = (end_offset.align_to_byte().start_address
+                    - fmmu_config.logical_start_address)
+                    as u16;
+                fmmu_config.logical_end_bit = global_offset.end_bit(sm_bit_len);
+
+                fmmu_config
+            }
+            (false, PdiLayout::ByteAligned) => Fmmu {
                 logical_start_address: global_offset.start_address,
                 length_bytes: sm_config.length_bytes,
-                // Mapping into PDI is byte-aligned until/if we support bit-oriented SubDevices
                 logical_start_bit: 0,
                 // Always byte-aligned
                 logical_end_bit: 7,
@@ -455,7 +671,18 @@ where
                 read_enable: desired_sm_type == SyncManagerType::ProcessDataRead,
                 write_enable: desired_sm_type == SyncManagerType::ProcessDataWrite,
                 enable: true,
-            }
+            },
+            (false, PdiLayout::BitPacked) => Fmmu {
+                logical_start_address: global_offset.start_address,
+                length_bytes: global_offset.up_to(end_offset).len() as u16,
+                logical_start_bit: global_offset.start_bit,
+                logical_end_bit: global_offset.end_bit(sm_bit_len),
+                physical_start_address: sm_config.physical_start_address,
+                physical_start_bit: 0x0,
+                read_enable: desired_sm_type == SyncManagerType::ProcessDataRead,
+                write_enable: desired_sm_type == SyncManagerType::ProcessDataWrite,
+                enable: true,
+            },
         };
 
         self.write(RegisterAddress::fmmu(fmmu_index as u8))
@@ -469,7 +696,7 @@ where
             fmmu_config
         );
 
-        *global_offset = global_offset.increment_byte_aligned(sm_bit_len);
+        *global_offset = end_offset;
 
         Ok(())
     }
@@ -480,8 +707,17 @@ where
         sync_managers: &[SyncManager],
         direction: PdoDirection,
         offset: &mut PdiOffset,
+        layout: PdiLayout,
+        symbols: &mut PendingSymbols,
     ) -> Result<PdiSegment, Error> {
-        let eeprom = self.eeprom();
+        if self.pdo_mapping.assignment(direction).is_some() {
+            fmt::warn!(
+                "SubDevice {:#06x} has a custom PDO mapping but does not support CoE. Mapping will be ignored.",
+                self.configured_address()
+            );
+        }
+
+        let eeprom = self.eeprom_with_cache(self.identity);
 
         let pdos = match direction {
             PdoDirection::MasterRead => {
@@ -505,6 +741,9 @@ where
         let start_offset = *offset;
         // let mut total_bit_len = 0;
 
+        // See `configure_pdos_coe` for why this is tracked separately.
+        let mut sm_offset = start_offset;
+
         let (sm_type, _fmmu_type) = direction.filter_terms();
 
         for (sync_manager_index, sync_manager) in sync_managers
@@ -514,34 +753,29 @@ where
         {
             let sync_manager_index = sync_manager_index as u8;
 
-            let bit_len = pdos
+            let sm_start_bit = start_offset.bits_to(sm_offset);
+
+            let mut bit_len = 0u16;
+
+            for pdo in pdos
                 .iter()
                 .filter(|pdo| pdo.sync_manager == sync_manager_index)
-                .map(|pdo| {
-                    let oversampling = self
-                        .oversampling_config
-                        .iter()
-                        .find_map(|(pdo_id, mul)| {
-                            if *pdo_id == pdo.index {
-                                Some(*mul)
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or(1);
-
-                    let len = pdo.bit_len * oversampling;
+            {
+                let oversampling = self.pdo_mapping.oversampling_for(pdo.index);
 
-                    fmt::trace!(
-                        "EEPROM: {:#06x} oversampling: {}, this PDO bit len {}",
-                        pdo.index,
-                        oversampling,
-                        len
-                    );
+                let len = pdo.bit_len * oversampling;
 
+                fmt::trace!(
+                    "EEPROM: {:#06x} oversampling: {}, this PDO bit len {}",
+                    pdo.index,
+                    oversampling,
                     len
-                })
-                .sum();
+                );
+
+                symbols.push_eeprom_pdo(pdo.index, sm_start_bit + u32::from(bit_len))?;
+
+                bit_len += len;
+            }
 
             // total_bit_len += bit_len;
 
@@ -565,20 +799,24 @@ where
                 .write_sm_config(sync_manager_index, sync_manager, (bit_len + 7) / 8)
                 .await?;
 
+            if bit_len > 0 {
+                *offset = sm_offset;
+            }
+
             self.write_fmmu_config(
                 bit_len,
                 usize::from(fmmu_index),
                 offset,
+                layout,
                 sm_type,
                 &sm_config,
             )
             .await?;
+
+            sm_offset = sm_offset.increment((bit_len + 7) / 8);
         }
 
-        Ok(PdiSegment {
-            // bit_len: total_bit_len.into(),
-            bytes: start_offset.up_to(*offset),
-        })
+        Ok(PdiSegment::between(start_offset, *offset))
     }
 }
 
@@ -596,3 +834,28 @@ impl PdoDirection {
         }
     }
 }
+
+/// PDO entries are not recorded without the `pdi-symbols` feature.
+#[cfg(not(feature = "pdi-symbols"))]
+struct PendingSymbols;
+
+#[cfg(not(feature = "pdi-symbols"))]
+impl PendingSymbols {
+    #[allow(clippy::too_many_arguments)]
+    fn push(
+        &mut self,
+        _pdo_index: u16,
+        _index: u16,
+        _sub_index: u8,
+        _bit_len: u8,
+        _bit_offset: u32,
+        _name: Option<&'static str>,
+        _pdo_name: Option<&'static str>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_eeprom_pdo(&mut self, _pdo_index: u16, _bit_offset: u32) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -2,17 +2,18 @@ use crate::{
     eeprom::{
         CHECKSUM_POSITION, EepromDataProvider, EepromRange, STATION_ALIAS_CRC,
         STATION_ALIAS_POSITION,
+        cache::EepromCache,
         device_provider::SII_FIRST_CATEGORY_START,
         types::{
-            CategoryType, DefaultMailbox, FmmuEx, FmmuUsage, Pdo, PdoEntry, PdoType, SiiGeneral,
-            SyncManager,
+            CategoryType, DefaultMailbox, FmmuEx, FmmuUsage, Pdo, PdoEntry, PdoType, SiiCategory,
+            SiiDcOpMode, SiiGeneral, SiiSyncUnit, SyncManager,
         },
     },
     error::{EepromError, Error, IgnoreNoCategory, Item},
     fmt,
     subdevice::SubDeviceIdentity,
 };
-use core::marker::PhantomData;
+use core::{marker::PhantomData, ops::ControlFlow};
 use embedded_io_async::{Read, ReadExactError, Write};
 use ethercrab_wire::{EtherCrabWireRead, EtherCrabWireReadSized, EtherCrabWireSized};
 
@@ -34,9 +35,15 @@ where
         EepromRange::new(self.provider.clone(), word_addr, len_bytes / 2)
     }
 
-    /// Search for a given category and return a reader over the bytes contained within the category
-    /// if it is found.
-    async fn category(&self, category: CategoryType) -> Result<Option<EepromRange<P>>, Error> {
+    /// Walk through every category in the EEPROM, calling `visitor` with the category type, start
+    /// word address and length in words of each one.
+    ///
+    /// The walk stops when the end marker is reached or when `visitor` returns
+    /// [`ControlFlow::Break`], in which case the break value is returned.
+    async fn walk_categories<B>(
+        &self,
+        mut visitor: impl FnMut(CategoryType, u16, u16) -> ControlFlow<B>,
+    ) -> Result<Option<B>, Error> {
         let mut reader = self.provider.clone();
 
         let mut word_addr = SII_FIRST_CATEGORY_START;
@@ -48,8 +55,7 @@ where
 
             let Some(incr) = word_addr.checked_add(2) else {
                 fmt::warn!(
-                    "Could not find EEPROM category {:?} or end marker. EEPROM could be empty or corrupt.",
-                    category
+                    "Could not find EEPROM category end marker. EEPROM could be empty or corrupt."
                 );
 
                 break Ok(None);
@@ -85,16 +91,12 @@ where
                 len_words
             );
 
-            match category_type {
-                cat if cat == category => {
-                    break Ok(Some(EepromRange::new(
-                        self.provider.clone(),
-                        word_addr,
-                        len_words,
-                    )));
-                }
-                CategoryType::End => break Ok(None),
-                _ => (),
+            if category_type == CategoryType::End {
+                break Ok(None);
+            }
+
+            if let ControlFlow::Break(value) = visitor(category_type, word_addr, len_words) {
+                break Ok(Some(value));
             }
 
             // Next category starts after the current category's data. This is a WORD address.
@@ -102,6 +104,24 @@ where
         }
     }
 
+    /// Search for a given category and return a reader over the bytes contained within the category
+    /// if it is found.
+    async fn category(&self, category: CategoryType) -> Result<Option<EepromRange<P>>, Error> {
+        let found = self
+            .walk_categories(|category_type, word_addr, len_words| {
+                if category_type == category {
+                    ControlFlow::Break((word_addr, len_words))
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .await?;
+
+        Ok(found.map(|(word_addr, len_words)| {
+            EepromRange::new(self.provider.clone(), word_addr, len_words)
+        }))
+    }
+
     /// Read the configured station alias for the device from its EEPROM.
     #[allow(unused)]
     pub(crate) async fn station_alias(&self) -> Result<u16, Error> {
@@ -160,6 +180,76 @@ where
         Ok(())
     }
 
+    /// Write a complete SII image to the EEPROM, then read it back to verify it.
+    ///
+    /// The configuration area checksum is recomputed from the first 7 words of the image, so the
+    /// value in the image is ignored.
+    pub(crate) async fn write_image(&self, image: &[u8]) -> Result<(), Error> {
+        let len_bytes =
+            u16::try_from(image.len()).map_err(|_| Error::Eeprom(EepromError::SectionOverrun))?;
+
+        if image.len() < usize::from(SII_FIRST_CATEGORY_START) * 2 || image.len() % 2 != 0 {
+            return Err(Error::Eeprom(EepromError::InvalidImage));
+        }
+
+        let size = self.size().await?;
+
+        if image.len() > size {
+            fmt::error!(
+                "EEPROM image is {} bytes but device EEPROM is {} bytes",
+                image.len(),
+                size
+            );
+
+            return Err(Error::Eeprom(EepromError::SectionOverrun));
+        }
+
+        let config_area = config_area_with_checksum(image);
+
+        fmt::debug!(
+            "Writing {} byte EEPROM image with checksum {:#04x}",
+            image.len(),
+            config_area[CHECKSUM_POSITION.start]
+        );
+
+        self.provider.clear_errors().await?;
+
+        self.start_at(0x0000, config_area.len() as u16)
+            .write_all(&config_area)
+            .await?;
+
+        self.start_at(
+            (config_area.len() / 2) as u16,
+            len_bytes - config_area.len() as u16,
+        )
+        .write_all(&image[config_area.len()..])
+        .await?;
+
+        fmt::debug!("--> Verifying EEPROM image");
+
+        let mut reader = self.start_at(0x0000, len_bytes);
+
+        let mut buf = [0u8; 16];
+
+        for (i, expected) in image.chunks(buf.len()).enumerate() {
+            let expected = if i == 0 { &config_area[..] } else { expected };
+
+            let buf = &mut buf[0..expected.len()];
+
+            reader.read_exact(buf).await?;
+
+            if let Some(pos) = buf.iter().zip(expected).position(|(a, b)| a != b) {
+                let word = ((i * 16 + pos) / 2) as u16;
+
+                fmt::error!("EEPROM verification failed at word {:#06x}", word);
+
+                return Err(Error::Eeprom(EepromError::Verify { word }));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the device name.
     ///
     /// This is the `OrderIdx` field as described in ETG2010 Table 7.
@@ -196,6 +286,91 @@ where
         Ok(usize::from(len))
     }
 
+    /// Check the configuration area checksum stored in word `0x0007` against one calculated over
+    /// the first 7 words of the EEPROM.
+    pub(crate) async fn validate_checksum(&self) -> Result<(), Error> {
+        let mut reader = self.start_at(0x0000, CHECKSUM_POSITION.end as u16);
+
+        let mut config_area = [0u8; CHECKSUM_POSITION.end];
+
+        reader.read_exact(&mut config_area).await?;
+
+        // The checksum is the lower byte of the word. The upper byte is reserved.
+        let stored = config_area[CHECKSUM_POSITION.start];
+        let calculated = STATION_ALIAS_CRC.checksum(&config_area[0..CHECKSUM_POSITION.start]);
+
+        fmt::trace!(
+            "EEPROM checksum stored {:#04x}, calculated {:#04x}",
+            stored,
+            calculated
+        );
+
+        if stored != calculated {
+            return Err(Error::Eeprom(EepromError::Checksum { stored, calculated }));
+        }
+
+        Ok(())
+    }
+
+    /// Read the raw checksum word at word address `0x0007`.
+    pub(crate) async fn checksum_word(&self) -> Result<[u8; 2], Error> {
+        let mut reader = self.start_at(
+            (CHECKSUM_POSITION.start / 2) as u16,
+            CHECKSUM_POSITION.len() as u16,
+        );
+
+        let mut buf = [0u8; CHECKSUM_POSITION.end - CHECKSUM_POSITION.start];
+
+        reader.read_exact(&mut buf).await?;
+
+        Ok(buf)
+    }
+
+    /// Copy the EEPROM image, up to and including the category end marker, into `cache`.
+    ///
+    /// If the cache cannot store the image, any partially stored image is removed.
+    pub(crate) async fn populate_cache(
+        &self,
+        cache: &dyn EepromCache,
+        identity: &SubDeviceIdentity,
+    ) -> Result<(), Error> {
+        let mut end_word = SII_FIRST_CATEGORY_START;
+
+        self.walk_categories(|_category, word_addr, len_words| {
+            end_word = word_addr.saturating_add(len_words);
+
+            ControlFlow::<()>::Continue(())
+        })
+        .await?;
+
+        // Include the end marker
+        let len_bytes = (usize::from(end_word) + 2) * 2;
+
+        let mut reader = self.provider.clone();
+
+        reader.clear_errors().await?;
+
+        let mut pos = 0;
+
+        while pos < len_bytes {
+            let chunk = reader.read_chunk((pos / 2) as u16).await?;
+
+            if !cache.write(identity, pos, &chunk) {
+                cache.remove(identity);
+
+                return Ok(());
+            }
+
+            pos += chunk.len();
+        }
+
+        fmt::debug!("--> Cached {} byte EEPROM image", pos);
+
+        cache.commit(identity);
+
+        Ok(())
+    }
+
     /// Get the long name of the device.
     pub(crate) async fn device_description<const N: usize>(
         &self,
@@ -319,6 +494,97 @@ where
         Ok(mappings)
     }
 
+    /// Find the raw `DataTypes` category, if present.
+    ///
+    /// The contents of this category are reserved for future use by ETG1000.6, so only its location
+    /// is returned.
+    pub(crate) async fn data_types(&self) -> Result<Option<SiiCategory>, Error> {
+        fmt::trace!("Get data types");
+
+        self.walk_categories(|category_type, start_word, len_words| {
+            if category_type == CategoryType::DataTypes {
+                ControlFlow::Break(SiiCategory {
+                    category: category_type.into(),
+                    start_word,
+                    len_words,
+                })
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await
+    }
+
+    pub(crate) async fn sync_units(&self) -> Result<heapless::Vec<SiiSyncUnit, 8>, Error> {
+        let mut sync_units = heapless::Vec::<_, 8>::new();
+
+        fmt::trace!("Get sync units");
+
+        let mut cat = self.items::<SiiSyncUnit>(CategoryType::SyncUnit).await?;
+
+        while let Some(unit) = cat.next().await? {
+            sync_units
+                .push(unit)
+                .map_err(|_| Error::Capacity(Item::SyncUnit))?;
+        }
+
+        fmt::debug!("Discovered sync units:\n{:#?}", sync_units);
+
+        Ok(sync_units)
+    }
+
+    pub(crate) async fn dc_op_modes(&self) -> Result<heapless::Vec<SiiDcOpMode, 16>, Error> {
+        let mut op_modes = heapless::Vec::<_, 16>::new();
+
+        fmt::trace!("Get DC operation modes");
+
+        let mut cat = self
+            .items::<SiiDcOpMode>(CategoryType::DistributedClock)
+            .await?;
+
+        while let Some(op_mode) = cat.next().await? {
+            op_modes
+                .push(op_mode)
+                .map_err(|_| Error::Capacity(Item::DcOpMode))?;
+        }
+
+        fmt::debug!("Discovered DC operation modes:\n{:#?}", op_modes);
+
+        Ok(op_modes)
+    }
+
+    /// Find all vendor specific categories (`0x1000`-`0xfffe`).
+    pub(crate) async fn vendor_categories(&self) -> Result<heapless::Vec<SiiCategory, 16>, Error> {
+        let mut categories = heapless::Vec::<_, 16>::new();
+
+        fmt::trace!("Get vendor specific categories");
+
+        let overflow = self
+            .walk_categories(|category_type, start_word, len_words| {
+                if !category_type.is_vendor_specific() {
+                    return ControlFlow::Continue(());
+                }
+
+                match categories.push(SiiCategory {
+                    category: category_type.into(),
+                    start_word,
+                    len_words,
+                }) {
+                    Ok(()) => ControlFlow::Continue(()),
+                    Err(_) => ControlFlow::Break(()),
+                }
+            })
+            .await?;
+
+        if overflow.is_some() {
+            return Err(Error::Capacity(Item::Category));
+        }
+
+        fmt::debug!("Discovered vendor specific categories:\n{:#?}", categories);
+
+        Ok(categories)
+    }
+
     async fn pdos(&self, direction: PdoType) -> Result<heapless::Vec<Pdo, 64>, Error> {
         let mut pdos = heapless::Vec::new();
 
@@ -351,6 +617,35 @@ where
         Ok(pdos)
     }
 
+    /// Every PDO entry of the given PDO type, paired with the PDO it belongs to, in EEPROM order.
+    #[cfg(feature = "pdi-symbols")]
+    pub(crate) async fn pdo_entries(
+        &self,
+        direction: PdoType,
+    ) -> Result<heapless::Vec<(Pdo, PdoEntry), 64>, Error> {
+        let mut entries = heapless::Vec::new();
+
+        let mut cat = self.items::<Pdo>(CategoryType::from(direction)).await?;
+
+        while let Some(pdo) = cat.next().await? {
+            for idx in 0..pdo.num_entries {
+                let Some(entry) = cat.next_sub_item::<PdoEntry>().await? else {
+                    fmt::error!("Failed to read PDO entry {}", idx);
+
+                    return Err(Error::Eeprom(EepromError::Decode));
+                };
+
+                entries.push((pdo, entry)).map_err(|_| {
+                    fmt::error!("Too many PDO entries, max 64");
+
+                    Error::Capacity(Item::PdoEntry)
+                })?;
+            }
+        }
+
+        Ok(entries)
+    }
+
     /// Transmit PDOs (from device's perspective) - inputs
     pub(crate) async fn maindevice_read_pdos(&self) -> Result<heapless::Vec<Pdo, 64>, Error> {
         self.pdos(PdoType::Tx).await
@@ -450,6 +745,51 @@ where
         }
     }
 
+    /// Call `visitor` with the 1-based index and value of every string in the EEPROM, up to and
+    /// including `max_index`.
+    ///
+    /// Non-ASCII characters are replaced with `'?'`, as in [`find_string`](Self::find_string).
+    #[cfg(feature = "pdi-symbols")]
+    pub(crate) async fn visit_strings(
+        &self,
+        max_index: u8,
+        mut visitor: impl FnMut(u8, &str),
+    ) -> Result<(), Error> {
+        let Some(mut reader) = self.category(CategoryType::Strings).await? else {
+            return Ok(());
+        };
+
+        let num_strings = reader.read_byte().await?;
+
+        for index in 1..=num_strings.min(max_index) {
+            let string_len = usize::from(reader.read_byte().await?);
+
+            let mut buf = [0u8; 255];
+
+            let buf = &mut buf[0..string_len];
+
+            reader.read_exact(buf).await?;
+
+            // Get rid of any C null terminators
+            let buf = match buf.iter().position(|char| *char == 0x00) {
+                Some(end) => &mut buf[0..end],
+                None => buf,
+            };
+
+            buf.iter_mut().for_each(|c| {
+                if !c.is_ascii() {
+                    *c = b'?'
+                }
+            });
+
+            // SAFETY: We've checked the buffer only contains ASCII characters above, so we don't
+            // need to check for valid UTF-8.
+            visitor(index, unsafe { core::str::from_utf8_unchecked(buf) });
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn items<T>(
         &self,
         category: CategoryType,
@@ -469,6 +809,20 @@ where
     }
 }
 
+/// Copy the first 8 words of an SII image, replacing the checksum with one calculated over the
+/// first 7 words.
+fn config_area_with_checksum(image: &[u8]) -> [u8; 16] {
+    let mut config_area = [0u8; 16];
+
+    config_area.copy_from_slice(&image[0..16]);
+
+    let checksum = u16::from(STATION_ALIAS_CRC.checksum(&config_area[0..CHECKSUM_POSITION.start]));
+
+    config_area[CHECKSUM_POSITION].copy_from_slice(&checksum.to_le_bytes());
+
+    config_area
+}
+
 pub struct CategoryIterator<P, T> {
     reader: EepromRange<P>,
     item: PhantomData<T>,
@@ -780,7 +1134,7 @@ mod tests {
             "../../dumps/eeprom/el2828.hex"
         )));
 
-        fn pdo(index: u16, _name_string_idx: u8, _entry_idx: u16) -> Pdo {
+        fn pdo(index: u16, name_string_idx: u8, _entry_idx: u16) -> Pdo {
             // let entry_defaults = PdoEntry {
             //     index: 0x7000,
             //     sub_index: 1,
@@ -792,7 +1146,7 @@ mod tests {
 
             let pdo_defaults = Pdo {
                 index,
-                // name_string_idx: 5,
+                name_string_idx: 5,
                 num_entries: 1,
                 sync_manager: 0,
                 // dc_sync: 0,
@@ -807,7 +1161,7 @@ mod tests {
 
             Pdo {
                 // index,
-                // name_string_idx,
+                name_string_idx,
                 bit_len: 1,
                 // entries: heapless::Vec::from_slice(&[PdoEntry {
                 //     index: entry_idx,
@@ -1128,4 +1482,271 @@ mod tests {
 
         assert_eq!(e.size().await, Ok(2048));
     }
+
+    #[tokio::test]
+    async fn write_image() {
+        crate::test_logger();
+
+        static IMAGE: &[u8] = include_bytes!("../../dumps/eeprom/el2828.hex");
+
+        // Checksum in the dump should already be valid
+        assert_eq!(config_area_with_checksum(IMAGE), IMAGE[0..16]);
+
+        let e = SubDeviceEeprom::new(EepromFile::new(IMAGE));
+
+        assert_eq!(e.write_image(IMAGE).await, Ok(()));
+
+        assert_eq!(
+            e.write_image(&IMAGE[0..64]).await,
+            Err(Error::Eeprom(EepromError::InvalidImage))
+        );
+        assert_eq!(
+            e.write_image(&IMAGE[0..129]).await,
+            Err(Error::Eeprom(EepromError::InvalidImage))
+        );
+
+        // Checksum is recalculated when the alias changes
+        let mut aliased = [0u8; 16];
+        aliased.copy_from_slice(&IMAGE[0..16]);
+        aliased[STATION_ALIAS_POSITION].copy_from_slice(&0x1234u16.to_le_bytes());
+
+        let config_area = config_area_with_checksum(&aliased);
+
+        assert_eq!(
+            config_area[CHECKSUM_POSITION],
+            u16::from(STATION_ALIAS_CRC.checksum(&aliased[0..14])).to_le_bytes()
+        );
+        assert_ne!(config_area[CHECKSUM_POSITION], IMAGE[CHECKSUM_POSITION]);
+    }
+
+    #[tokio::test]
+    async fn akd_dc_op_modes() {
+        crate::test_logger();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/akd.hex"
+        )));
+
+        let expected = [
+            SiiDcOpMode {
+                cycle_time_sync0: 0,
+                shift_time_sync0: 0,
+                shift_time_sync1: 0,
+                sync1_cycle_factor: 0,
+                assign_activate: 0x0300,
+                sync0_cycle_factor: 1,
+                name_string_idx: 6,
+                description_string_idx: 0,
+            },
+            SiiDcOpMode {
+                cycle_time_sync0: 0,
+                shift_time_sync0: 0,
+                shift_time_sync1: 0,
+                sync1_cycle_factor: 0,
+                assign_activate: 0x0000,
+                sync0_cycle_factor: 1,
+                name_string_idx: 7,
+                description_string_idx: 0,
+            },
+        ];
+
+        assert_eq!(e.dc_op_modes().await, Ok(expected.into_iter().collect()));
+    }
+
+    #[tokio::test]
+    async fn el2262_dc_op_modes() {
+        crate::test_logger();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/el2262.bin"
+        )));
+
+        let op_modes = e.dc_op_modes().await.unwrap();
+
+        assert_eq!(op_modes.len(), 4);
+        assert!(op_modes.iter().all(|mode| mode.assign_activate == 0x0730));
+        assert_eq!(
+            op_modes
+                .iter()
+                .map(|mode| mode.sync0_cycle_factor)
+                .collect::<Vec<_>>(),
+            [-1, -8, -1, -8]
+        );
+    }
+
+    #[tokio::test]
+    async fn no_dc_op_modes() {
+        crate::test_logger();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/el2828.hex"
+        )));
+
+        assert_eq!(e.dc_op_modes().await, Ok(heapless::Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn el2262_sync_units() {
+        crate::test_logger();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/el2262.bin"
+        )));
+
+        // Both entries are 0xfff0: no flags set, reserved bits left erased
+        let unit = SiiSyncUnit {
+            separate_su: false,
+            separate_frame: false,
+            depend_on_input_state: false,
+            frame_repeat_support: false,
+        };
+
+        assert_eq!(e.sync_units().await, Ok([unit, unit].into_iter().collect()));
+    }
+
+    #[tokio::test]
+    async fn vendor_categories() {
+        crate::test_logger();
+
+        let mut image = vec![0u8; 0x80];
+
+        // Vendor specific category, 2 words long
+        image.extend_from_slice(&[0x34, 0x12, 0x02, 0x00, 0xaa, 0xbb, 0xcc, 0xdd]);
+        // DataTypes category, 1 word long
+        image.extend_from_slice(&[0x14, 0x00, 0x01, 0x00, 0x11, 0x22]);
+        // Another vendor specific category, 1 word long
+        image.extend_from_slice(&[0xfe, 0xff, 0x01, 0x00, 0x33, 0x44]);
+        // End marker
+        image.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        image.resize(0x100, 0xff);
+
+        let e = SubDeviceEeprom::new(EepromFile::new(image.leak()));
+
+        assert_eq!(
+            e.vendor_categories().await,
+            Ok([
+                SiiCategory {
+                    category: 0x1234,
+                    start_word: 0x0042,
+                    len_words: 2
+                },
+                SiiCategory {
+                    category: 0xfffe,
+                    start_word: 0x0049,
+                    len_words: 1
+                }
+            ]
+            .into_iter()
+            .collect())
+        );
+
+        assert_eq!(
+            e.data_types().await,
+            Ok(Some(SiiCategory {
+                category: 20,
+                start_word: 0x0046,
+                len_words: 1
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_checksum() {
+        crate::test_logger();
+
+        for image in [
+            include_bytes!("../../dumps/eeprom/akd.hex").as_slice(),
+            include_bytes!("../../dumps/eeprom/ek1100.hex"),
+            include_bytes!("../../dumps/eeprom/el2262.bin"),
+            include_bytes!("../../dumps/eeprom/el2828.hex"),
+            include_bytes!("../../dumps/eeprom/el2889.hex"),
+        ] {
+            let e = SubDeviceEeprom::new(EepromFile::new(image));
+
+            assert_eq!(e.validate_checksum().await, Ok(()));
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_checksum() {
+        crate::test_logger();
+
+        let mut image = include_bytes!("../../dumps/eeprom/el2828.hex").to_vec();
+
+        // Corrupt the station alias
+        image[STATION_ALIAS_POSITION.start] ^= 0x01;
+
+        let calculated = STATION_ALIAS_CRC.checksum(&image[0..CHECKSUM_POSITION.start]);
+
+        let e = SubDeviceEeprom::new(EepromFile::new(image.leak()));
+
+        assert_eq!(
+            e.validate_checksum().await,
+            Err(Error::Eeprom(EepromError::Checksum {
+                stored: 0xe2,
+                calculated,
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn populate_cache() {
+        use crate::eeprom::cache::MemoryEepromCache;
+
+        crate::test_logger();
+
+        let image = include_bytes!("../../dumps/eeprom/el2828.hex");
+
+        let e = SubDeviceEeprom::new(EepromFile::new(image));
+
+        let identity = e.identity().await.unwrap();
+
+        let cache = MemoryEepromCache::<1, 4096>::new();
+
+        e.populate_cache(&cache, &identity).await.unwrap();
+
+        // Everything up to the end marker is cached, but nothing after it
+        let mut cached = vec![0u8; 4096];
+
+        let len = (0..cached.len())
+            .step_by(2)
+            .find(|pos| !cache.read(&identity, *pos, &mut cached[*pos..*pos + 2]))
+            .unwrap();
+
+        let cached = &cached[0..len];
+
+        assert_eq!(cached, &image[0..len]);
+        assert!(
+            cached
+                .chunks_exact(2)
+                .any(|word| word == u16::from(CategoryType::End).to_le_bytes())
+        );
+
+        let cached = SubDeviceEeprom::new(EepromFile::new(cached.to_vec().leak()));
+
+        assert_eq!(
+            cached.sync_managers().await,
+            e.sync_managers().await,
+            "cached image should contain all categories"
+        );
+    }
+
+    #[tokio::test]
+    async fn populate_cache_too_small() {
+        use crate::eeprom::cache::MemoryEepromCache;
+
+        crate::test_logger();
+
+        let e = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/el2828.hex"
+        )));
+
+        let identity = e.identity().await.unwrap();
+
+        let cache = MemoryEepromCache::<1, 64>::new();
+
+        assert_eq!(e.populate_cache(&cache, &identity).await, Ok(()));
+
+        assert!(!cache.read(&identity, 0, &mut [0u8; 2]));
+    }
 }
@@ -1,19 +1,28 @@
 pub(crate) mod configuration;
 mod dc;
-mod eeprom;
+pub(crate) mod eeprom;
 pub mod pdi;
+mod pdo_mapping;
 pub mod ports;
+#[cfg(feature = "pdi-symbols")]
+mod symbols;
 mod types;
 
 use crate::{
-    WrappedRead, WrappedWrite,
+    EepromChecksumBehaviour, WrappedRead, WrappedWrite,
     al_control::AlControl,
     al_status_code::AlStatusCode,
     command::Command,
+    dl_control::{DlControl, PortLoop},
     dl_status::DlStatus,
-    eeprom::{device_provider::DeviceEeprom, types::SiiOwner},
-    error::{Error, IgnoreNoCategory},
+    eeprom::{
+        device_provider::DeviceEeprom,
+        types::{Flags, SiiCategory, SiiDcOpMode, SiiOwner, SiiSyncUnit},
+    },
+    error::{EepromError, Error, IgnoreNoCategory, Item, TimeoutError},
+    error_counters::ErrorCounters,
     fmt,
+    init_observer::{InitEvent, InitWarning},
     mailbox::coe::{self, Coe, SdoExpeditedPayload, SubIndex},
     maindevice::MainDevice,
     register::{DcSupport, RegisterAddress, SupportFlags},
@@ -28,7 +37,8 @@ use core::{
 };
 use embedded_io_async::{Read, Write as EioWrite};
 use ethercrab_wire::{
-    EtherCrabWireReadSized, EtherCrabWireReadWrite, EtherCrabWireWrite, EtherCrabWireWriteSized,
+    EtherCrabWireRead, EtherCrabWireReadSized, EtherCrabWireReadWrite, EtherCrabWireWrite,
+    EtherCrabWireWriteSized,
 };
 
 use self::eeprom::SubDeviceEeprom;
@@ -38,6 +48,11 @@ pub use self::types::Mailbox;
 pub use self::types::SubDeviceIdentity;
 pub use coe::services::{ObjectDescriptionListQuery, ObjectDescriptionListQueryCounts};
 pub use dc::DcSync;
+pub use pdo_mapping::{Pdo, PdoEntry, PdoMapping};
+#[cfg(feature = "pdi-symbols")]
+pub use symbols::PdiSymbol;
+#[cfg(feature = "pdi-symbols")]
+use symbols::PdiSymbols;
 
 /// SubDevice device metadata. See [`SubDeviceRef`] for richer behaviour.
 #[doc(alias = "Slave")]
@@ -86,8 +101,15 @@ pub struct SubDevice {
     /// DC config.
     pub(crate) dc_sync: DcSync,
 
-    /// Oversampling config, a list of tuples of `(PDO, oversampling multipler)`.
-    pub(crate) oversampling_config: &'static [(u16, u16)],
+    /// Custom PDO mapping and assignment, including oversampling config.
+    pub(crate) pdo_mapping: PdoMapping,
+
+    /// PDO entry names and their positions in the PDI.
+    #[cfg(feature = "pdi-symbols")]
+    pub(crate) pdi_symbols: PdiSymbols,
+
+    /// Explicit Device ID, only read during init if it is used for addressing or configuration.
+    pub(crate) explicit_device_id: Option<u16>,
 }
 
 // Only required for tests, also doesn't make much sense - consumers of EtherCrab should be
@@ -108,6 +130,7 @@ impl PartialEq for SubDevice {
             && self.parent_index == other.parent_index
             && self.propagation_delay == other.propagation_delay
             && self.dc_sync == other.dc_sync
+            && self.explicit_device_id == other.explicit_device_id
         // NOTE: No mailbox_counter
     }
 }
@@ -131,7 +154,10 @@ impl Clone for SubDevice {
             propagation_delay: self.propagation_delay,
             dc_sync: self.dc_sync,
             mailbox_counter: AtomicU8::new(self.mailbox_counter.load(Ordering::Acquire)),
-            oversampling_config: &[],
+            pdo_mapping: self.pdo_mapping,
+            #[cfg(feature = "pdi-symbols")]
+            pdi_symbols: self.pdi_symbols.clone(),
+            explicit_device_id: self.explicit_device_id,
         }
     }
 }
@@ -159,9 +185,33 @@ impl SubDevice {
         // Make sure master has access to SubDevice EEPROM
         subdevice_ref.set_eeprom_mode(SiiOwner::Master).await?;
 
-        let eeprom = subdevice_ref.eeprom();
+        let identity = subdevice_ref.eeprom().identity().await?;
+
+        subdevice_ref.populate_eeprom_cache(identity).await?;
+
+        let eeprom = subdevice_ref.eeprom_with_cache(identity);
 
-        let identity = eeprom.identity().await?;
+        match maindevice.config.eeprom_checksum {
+            EepromChecksumBehaviour::Ignore => (),
+            EepromChecksumBehaviour::Warn => match eeprom.validate_checksum().await {
+                Err(Error::Eeprom(EepromError::Checksum { stored, calculated })) => {
+                    fmt::warn!(
+                        "SubDevice {:#06x} EEPROM checksum {:#04x} does not match calculated {:#04x}. EEPROM may be corrupt.",
+                        configured_address,
+                        stored,
+                        calculated
+                    );
+
+                    maindevice.emit(InitEvent::Warning(InitWarning::EepromChecksum {
+                        configured_address,
+                        stored,
+                        calculated,
+                    }));
+                }
+                other => other?,
+            },
+            EepromChecksumBehaviour::Error => eeprom.validate_checksum().await?,
+        }
 
         let name = eeprom.device_name().await?.unwrap_or_else(|| {
             let mut s = heapless::String::new();
@@ -230,7 +280,10 @@ impl SubDevice {
             dc_sync: DcSync::Disabled,
             // 0 is a reserved value, so we initialise the cycle at 1. The cycle repeats 1 - 7.
             mailbox_counter: AtomicU8::new(1),
-            oversampling_config: &[],
+            pdo_mapping: PdoMapping::new(),
+            #[cfg(feature = "pdi-symbols")]
+            pdi_symbols: PdiSymbols::default(),
+            explicit_device_id: None,
         })
     }
 
@@ -248,10 +301,50 @@ impl SubDevice {
     /// ]);
     /// ```
     ///
-    /// This is a temporary(ish) solution to configure oversampling until a better one is found to
-    /// configure SubDevices from ESI files, etc.
+    /// This is shorthand for setting [`PdoMapping::with_oversampling`] on the SubDevice's current
+    /// PDO mapping.
     pub fn set_oversampling(&mut self, oversampling_config: &'static [(u16, u16)]) {
-        self.oversampling_config = oversampling_config
+        self.pdo_mapping = self.pdo_mapping.with_oversampling(oversampling_config)
+    }
+
+    /// Set a custom PDO mapping and assignment for this SubDevice.
+    ///
+    /// The mapping is written to the SubDevice over CoE when the SubDevice's group is transitioned
+    /// into SAFE-OP, before the PDI is sized. This method must therefore be called while the
+    /// SubDevice is in PRE-OP. See [`PdoMapping`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// static INPUTS: &[Pdo] = &[Pdo::new(0x1a00, &[PdoEntry::new(0x6000, 0x11, 16)])];
+    ///
+    /// subdevice.set_pdo_mapping(PdoMapping::new().with_inputs(INPUTS));
+    /// ```
+    pub fn set_pdo_mapping(&mut self, pdo_mapping: PdoMapping) {
+        self.pdo_mapping = pdo_mapping
+    }
+
+    /// Get the custom PDO mapping set for this SubDevice.
+    pub fn pdo_mapping(&self) -> &PdoMapping {
+        &self.pdo_mapping
+    }
+
+    /// Get the PDO entries mapped into this SubDevice's inputs.
+    ///
+    /// This list is populated when the SubDevice's group is transitioned into SAFE-OP, and will be
+    /// empty before then.
+    #[cfg(feature = "pdi-symbols")]
+    pub fn input_symbols(&self) -> impl Iterator<Item = PdiSymbol<'_>> {
+        self.pdi_symbols.iter(false)
+    }
+
+    /// Get the PDO entries mapped into this SubDevice's outputs.
+    ///
+    /// This list is populated when the SubDevice's group is transitioned into SAFE-OP, and will be
+    /// empty before then.
+    #[cfg(feature = "pdi-symbols")]
+    pub fn output_symbols(&self) -> impl Iterator<Item = PdiSymbol<'_>> {
+        self.pdi_symbols.iter(true)
     }
 
     /// Get the SubDevice's human readable short name.
@@ -275,7 +368,7 @@ impl SubDevice {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
         Ok(subdevice_ref
-            .eeprom()
+            .eeprom_with_cache(self.identity)
             .device_description()
             .await
             .ignore_no_category()?
@@ -286,7 +379,69 @@ impl SubDevice {
     pub async fn eeprom_size(&self, maindevice: &MainDevice<'_>) -> Result<usize, Error> {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
-        subdevice_ref.eeprom().size().await
+        subdevice_ref.eeprom_with_cache(self.identity).size().await
+    }
+
+    /// Read the Distributed Clocks operation modes from the SubDevice's EEPROM.
+    ///
+    /// This is useful to find the correct `AssignActivate` value, cycle time factors and shift times
+    /// for a SubDevice when its ESI file is not available. An empty list is returned if the EEPROM
+    /// does not contain a DC category.
+    pub async fn eeprom_dc_op_modes(
+        &self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<heapless::Vec<SiiDcOpMode, 16>, Error> {
+        let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
+
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .dc_op_modes()
+            .await
+    }
+
+    /// Read the sync unit entries from the SubDevice's EEPROM.
+    pub async fn eeprom_sync_units(
+        &self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<heapless::Vec<SiiSyncUnit, 8>, Error> {
+        let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
+
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .sync_units()
+            .await
+    }
+
+    /// Find the location of the `DataTypes` category in the SubDevice's EEPROM, if present.
+    ///
+    /// The contents of this category are reserved for future use by ETG1000.6 and ETG2010 defines
+    /// no fields for it, so use [`eeprom_read_raw`](SubDevice::eeprom_read_raw) to read them.
+    pub async fn eeprom_data_types(
+        &self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<Option<SiiCategory>, Error> {
+        let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
+
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .data_types()
+            .await
+    }
+
+    /// Find the location of all vendor specific categories (`0x1000`-`0xfffe`) in the SubDevice's
+    /// EEPROM.
+    ///
+    /// Use [`eeprom_read_raw`](SubDevice::eeprom_read_raw) to read the contents of each category.
+    pub async fn eeprom_vendor_categories(
+        &self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<heapless::Vec<SiiCategory, 16>, Error> {
+        let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
+
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .vendor_categories()
+            .await
     }
 
     /// Read raw bytes from the SubDevice's EEPROM, starting at the given **word** address.
@@ -305,7 +460,7 @@ impl SubDevice {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
         let mut reader = subdevice_ref
-            .eeprom()
+            .eeprom_with_cache(self.identity)
             .start_at(start_word, buf.len() as u16);
 
         reader.read(buf).await
@@ -328,7 +483,7 @@ impl SubDevice {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
         let mut reader = subdevice_ref
-            .eeprom()
+            .eeprom_with_cache(self.identity)
             .start_at(start_word, T::PACKED_LEN as u16);
 
         let mut buf = T::buffer();
@@ -363,7 +518,7 @@ impl SubDevice {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
         let mut writer = subdevice_ref
-            .eeprom()
+            .eeprom_with_cache(self.identity)
             .start_at(start_word, T::PACKED_LEN as u16);
 
         writer.write_all(value.pack().as_ref()).await?;
@@ -371,6 +526,73 @@ impl SubDevice {
         Ok(())
     }
 
+    /// Write a complete SII image to the SubDevice's EEPROM, then read it back to verify it.
+    ///
+    /// <div class="warning">
+    ///
+    /// **Warning:** This method is safe in the Rust sense, but will overwrite the **entire**
+    /// EEPROM. A bad image can leave the SubDevice unable to communicate. The SubDevice must be
+    /// power cycled for most changes to take effect.
+    ///
+    /// </div>
+    ///
+    /// `image` is a raw SII binary, e.g. as produced by the `dump-eeprom` example. The
+    /// configuration area checksum (word `0x0007`) is recalculated before writing, so it does not
+    /// need to be correct in the image.
+    ///
+    /// Images whose vendor or product ID differs from this SubDevice's are refused with
+    /// [`EepromError::IdentityMismatch`](crate::error::EepromError::IdentityMismatch) unless
+    /// `force` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EepromError::InvalidImage`](crate::error::EepromError::InvalidImage) if the image is
+    ///   shorter than the fixed SII header or is an odd number of bytes long.
+    /// - [`EepromError::SectionOverrun`](crate::error::EepromError::SectionOverrun) if the image is
+    ///   larger than the SubDevice's EEPROM.
+    /// - [`EepromError::Write`](crate::error::EepromError::Write) if the SubDevice reports a write
+    ///   error.
+    /// - [`EepromError::Verify`](crate::error::EepromError::Verify) if the data read back does not
+    ///   match the image.
+    pub async fn eeprom_write_image_dangerously(
+        &self,
+        maindevice: &MainDevice<'_>,
+        image: &[u8],
+        force: bool,
+    ) -> Result<(), Error> {
+        let image_identity = image
+            .get(0x10..0x20)
+            .ok_or(Error::Eeprom(EepromError::InvalidImage))
+            .and_then(|raw| Ok(SubDeviceIdentity::unpack_from_slice(raw)?))?;
+
+        if image_identity.vendor_id != self.identity.vendor_id
+            || image_identity.product_id != self.identity.product_id
+        {
+            if force {
+                fmt::warn!(
+                    "SubDevice {:#06x} identity {} does not match image identity {}, writing anyway",
+                    self.configured_address,
+                    self.identity,
+                    image_identity
+                );
+            } else {
+                fmt::error!(
+                    "SubDevice {:#06x} identity {} does not match image identity {}",
+                    self.configured_address,
+                    self.identity,
+                    image_identity
+                );
+
+                return Err(Error::Eeprom(EepromError::IdentityMismatch));
+            }
+        }
+
+        SubDeviceRef::new(maindevice, self.configured_address, ())
+            .eeprom_with_cache(self.identity)
+            .write_image(image)
+            .await
+    }
+
     /// Get additional identifying details for the SubDevice.
     pub fn identity(&self) -> SubDeviceIdentity {
         self.identity
@@ -397,7 +619,10 @@ impl SubDevice {
     ) -> Result<(), Error> {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
-        subdevice_ref.eeprom().set_station_alias(new_alias).await?;
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .set_station_alias(new_alias)
+            .await?;
 
         self.alias_address = new_alias;
 
@@ -548,7 +773,10 @@ where
     ) -> Result<u16, Error> {
         let subdevice_ref = SubDeviceRef::new(maindevice, self.configured_address, ());
 
-        subdevice_ref.eeprom().station_alias().await
+        subdevice_ref
+            .eeprom_with_cache(self.identity)
+            .station_alias()
+            .await
     }
 
     pub(crate) fn dc_sync(&self) -> DcSync {
@@ -752,10 +980,406 @@ impl<'maindevice, S> SubDeviceRef<'maindevice, S> {
         futures_lite::future::try_zip(self.state(), code).await
     }
 
-    fn eeprom(&self) -> SubDeviceEeprom<DeviceEeprom> {
+    /// Request a state transition for this SubDevice only, and wait for it to complete.
+    ///
+    /// Any pending error is acknowledged with [`acknowledge_error`](SubDeviceRef::acknowledge_error)
+    /// first, so this can be used to bring back a single SubDevice that dropped to e.g.
+    /// `SAFE-OP + ERROR` without cycling its whole group.
+    ///
+    /// If the SubDevice refuses the transition, its AL status code is returned in
+    /// [`Error::SubDevice`]. If it does not reach the requested state within the
+    /// [`state_transition`](field@crate::Timeouts::state_transition) timeout, its AL status code is
+    /// returned the same way, or [`Error::Timeout`] if the SubDevice does not report one.
+    ///
+    /// This method does not change the typestate of the SubDevice's group. The caller must only
+    /// request states that are consistent with the group's typestate. See [`SubDeviceGroup`] for
+    /// details.
+    ///
+    /// [`SubDeviceGroup`]: crate::SubDeviceGroup
+    pub async fn request_state(&self, state: SubDeviceState) -> Result<(), Error> {
+        let status = self
+            .read(RegisterAddress::AlStatus)
+            .receive::<AlControl>(self.maindevice)
+            .await?;
+
+        if status.error {
+            self.acknowledge_error().await?;
+        }
+
+        fmt::debug!(
+            "Request state {} for SubDevice {:#06x}",
+            state,
+            self.configured_address
+        );
+
+        self.write(RegisterAddress::AlControl)
+            .send(self.maindevice, AlControl::new(state))
+            .await?;
+
+        let result = async {
+            loop {
+                let status = self
+                    .read(RegisterAddress::AlStatus)
+                    .receive::<AlControl>(self.maindevice)
+                    .await?;
+
+                if status.error {
+                    let code = self
+                        .read(RegisterAddress::AlStatusCode)
+                        .receive::<AlStatusCode>(self.maindevice)
+                        .await?;
+
+                    fmt::error!(
+                        "SubDevice {:#06x} refused transition to {}: {}",
+                        self.configured_address,
+                        state,
+                        code
+                    );
+
+                    break Err(Error::SubDevice(code));
+                }
+
+                if status.state == state {
+                    break Ok(());
+                }
+
+                self.maindevice.timeouts.loop_tick().await;
+            }
+        }
+        .timeout(self.maindevice.timeouts.state_transition())
+        .await;
+
+        match result {
+            Err(Error::Timeout(TimeoutError::StateTransition)) => {
+                let code = self
+                    .read(RegisterAddress::AlStatusCode)
+                    .receive::<AlStatusCode>(self.maindevice)
+                    .await?;
+
+                fmt::error!(
+                    "SubDevice {:#06x} timed out transitioning to {}: {}",
+                    self.configured_address,
+                    state,
+                    code
+                );
+
+                if code == AlStatusCode::NoError {
+                    Err(Error::Timeout(TimeoutError::StateTransition))
+                } else {
+                    Err(Error::SubDevice(code))
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Acknowledge an error reported by this SubDevice, returning the state it is left in.
+    ///
+    /// The error acknowledge flag is written to AL control along with the SubDevice's current
+    /// state, which leaves the state unchanged. This method then waits for the SubDevice to clear
+    /// the error flag in AL status. If the flag is still set after the
+    /// [`state_transition`](field@crate::Timeouts::state_transition) timeout, the SubDevice's AL
+    /// status code is returned in [`Error::SubDevice`].
+    ///
+    /// Returns the current state immediately if no error is pending.
+    pub async fn acknowledge_error(&self) -> Result<SubDeviceState, Error> {
+        let status = self
+            .read(RegisterAddress::AlStatus)
+            .receive::<AlControl>(self.maindevice)
+            .await?;
+
+        if !status.error {
+            return Ok(status.state);
+        }
+
+        let code = self
+            .read(RegisterAddress::AlStatusCode)
+            .receive::<AlStatusCode>(self.maindevice)
+            .await?;
+
+        fmt::debug!(
+            "Acknowledge error on SubDevice {:#06x} in state {}: {}",
+            self.configured_address,
+            status.state,
+            code
+        );
+
+        self.write(RegisterAddress::AlControl)
+            .send(
+                self.maindevice,
+                AlControl {
+                    error: true,
+                    ..AlControl::new(status.state)
+                },
+            )
+            .await?;
+
+        let result = async {
+            loop {
+                let status = self
+                    .read(RegisterAddress::AlStatus)
+                    .receive::<AlControl>(self.maindevice)
+                    .await?;
+
+                if !status.error {
+                    break Ok(status.state);
+                }
+
+                self.maindevice.timeouts.loop_tick().await;
+            }
+        }
+        .timeout(self.maindevice.timeouts.state_transition())
+        .await;
+
+        match result {
+            Err(Error::Timeout(TimeoutError::StateTransition)) => {
+                let code = self
+                    .read(RegisterAddress::AlStatusCode)
+                    .receive::<AlStatusCode>(self.maindevice)
+                    .await?;
+
+                Err(Error::SubDevice(code))
+            }
+            result => result,
+        }
+    }
+
+    /// Read the SubDevice's Explicit Device ID, as defined in ETG.1020.
+    ///
+    /// The Explicit Device ID is usually set with rotary switches on the SubDevice, and can be
+    /// used to tell apart SubDevices with the same identity. The SubDevice's EEPROM declares how
+    /// the ID is reported:
+    ///
+    /// - Through the AL status code register, requested with the ID request flag in AL control.
+    ///   This method waits for the SubDevice to set the ID loaded flag in AL status for up to the
+    ///   [`state_transition`](field@crate::Timeouts::state_transition) timeout, then clears the request.
+    /// - At a fixed address in ESC memory, which is read directly.
+    ///
+    /// Returns `None` if the SubDevice does not support Explicit Device ID.
+    pub async fn explicit_device_id(&self) -> Result<Option<u16>, Error> {
+        let Some(general) = self.eeprom().general().await.ignore_no_category()? else {
+            return Ok(None);
+        };
+
+        if general.flags.contains(Flags::IDENT_AL_STATUS) {
+            let status = self
+                .read(RegisterAddress::AlStatus)
+                .receive::<AlControl>(self.maindevice)
+                .await?;
+
+            // Request the ID without changing state
+            self.write(RegisterAddress::AlControl)
+                .send(
+                    self.maindevice,
+                    AlControl {
+                        id_request: true,
+                        ..AlControl::new(status.state)
+                    },
+                )
+                .await?;
+
+            let id = async {
+                loop {
+                    let status = self
+                        .read(RegisterAddress::AlStatus)
+                        .receive::<AlControl>(self.maindevice)
+                        .await?;
+
+                    if status.error {
+                        let code = self
+                            .read(RegisterAddress::AlStatusCode)
+                            .receive::<AlStatusCode>(self.maindevice)
+                            .await?;
+
+                        break Err(Error::SubDevice(code));
+                    }
+
+                    // The ID request flag is mirrored as "ID loaded" in AL status
+                    if status.id_request {
+                        break self
+                            .read(RegisterAddress::AlStatusCode)
+                            .receive::<u16>(self.maindevice)
+                            .await;
+                    }
+
+                    self.maindevice.timeouts.loop_tick().await;
+                }
+            }
+            .timeout(self.maindevice.timeouts.state_transition())
+            .await;
+
+            // Always withdraw the request so the AL status code register is used for errors again
+            self.write(RegisterAddress::AlControl)
+                .send(self.maindevice, AlControl::new(status.state))
+                .await?;
+
+            let id = id?;
+
+            fmt::debug!(
+                "SubDevice {:#06x} Explicit Device ID {:#06x} from AL status code",
+                self.configured_address,
+                id
+            );
+
+            Ok(Some(id))
+        } else if general.flags.contains(Flags::IDENT_PHY_M) {
+            let id = self
+                .read(general.physical_memory_addr)
+                .receive::<u16>(self.maindevice)
+                .await?;
+
+            fmt::debug!(
+                "SubDevice {:#06x} Explicit Device ID {:#06x} from register {:#06x}",
+                self.configured_address,
+                id,
+                general.physical_memory_addr
+            );
+
+            Ok(Some(id))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Read the SubDevice's port error counters.
+    ///
+    /// Rising counters on one port usually point to a bad cable or connector before the link
+    /// fails completely.
+    pub async fn error_counters(&self) -> Result<ErrorCounters, Error> {
+        let data = self
+            .read(RegisterAddress::RxErrorCounterPort0)
+            .receive_slice(self.maindevice, ErrorCounters::PACKED_LEN as u16)
+            .await?;
+
+        ErrorCounters::unpack_from_slice(self.configured_address, &data)
+    }
+
+    /// Read the loop setting of each port, indexed by port number.
+    pub async fn port_loops(&self) -> Result<[PortLoop; 4], Error> {
+        self.read(RegisterAddress::DlControl)
+            .receive::<DlControl>(self.maindevice)
+            .await
+            .map(|control| control.port_loops())
+    }
+
+    /// Force a port open or closed.
+    ///
+    /// Closing a port cuts off every SubDevice connected to it, which can be used to isolate a
+    /// faulty segment of the network at runtime. The other ports are left unchanged.
+    ///
+    /// Returns [`Error::NotFound`] if `port` is not a port number from 0 to 3.
+    pub async fn set_port_loop(&self, port: u8, setting: PortLoop) -> Result<(), Error> {
+        if port > 3 {
+            return Err(Error::NotFound {
+                item: Item::Port,
+                index: Some(usize::from(port)),
+            });
+        }
+
+        let mut control = self
+            .read(RegisterAddress::DlControl)
+            .receive::<DlControl>(self.maindevice)
+            .await?;
+
+        control.set_port_loop(port, setting);
+
+        fmt::debug!(
+            "SubDevice {:#06x} port {} loop set to {:?}",
+            self.configured_address,
+            port,
+            setting
+        );
+
+        self.write(RegisterAddress::DlControl)
+            .send(self.maindevice, control)
+            .await
+    }
+
+    /// Reset all of the SubDevice's error counters to zero.
+    pub async fn reset_error_counters(&self) -> Result<(), Error> {
+        self.write(RegisterAddress::RxErrorCounterPort0)
+            .send(self.maindevice, [0u8; ErrorCounters::PACKED_LEN])
+            .await
+    }
+
+    pub(crate) fn eeprom(&self) -> SubDeviceEeprom<DeviceEeprom> {
         SubDeviceEeprom::new(DeviceEeprom::new(self.maindevice, self.configured_address))
     }
 
+    /// Like [`eeprom`](SubDeviceRef::eeprom), but serves reads from the MainDevice's EEPROM cache
+    /// if one is set.
+    fn eeprom_with_cache(&self, identity: SubDeviceIdentity) -> SubDeviceEeprom<DeviceEeprom> {
+        let mut provider = DeviceEeprom::new(self.maindevice, self.configured_address);
+
+        // Identical SubDevices without a serial number would share one image
+        if let Some(cache) = self
+            .maindevice
+            .eeprom_cache
+            .filter(|_| identity.serial != 0)
+        {
+            provider = provider.with_cache(cache, identity);
+        }
+
+        SubDeviceEeprom::new(provider)
+    }
+
+    /// Make sure the MainDevice's EEPROM cache, if any, holds a valid image of this SubDevice's
+    /// EEPROM.
+    ///
+    /// A missing image is read from the SubDevice and stored in the cache. If the cache could not
+    /// store the image, reads fall back to the SubDevice's EEPROM.
+    ///
+    /// SubDevices with a serial number of zero are not cached, as identical SubDevices would
+    /// otherwise share, and concurrently populate, the same image.
+    async fn populate_eeprom_cache(&self, identity: SubDeviceIdentity) -> Result<(), Error> {
+        let Some(cache) = self.maindevice.eeprom_cache else {
+            return Ok(());
+        };
+
+        if identity.serial == 0 {
+            fmt::debug!(
+                "SubDevice {:#06x} has no serial number, not caching EEPROM",
+                self.configured_address
+            );
+
+            return Ok(());
+        }
+
+        let eeprom = self.eeprom();
+
+        let mut cached_checksum = [0u8; 2];
+
+        if cache.read(
+            &identity,
+            crate::eeprom::CHECKSUM_POSITION.start,
+            &mut cached_checksum,
+        ) {
+            if !self.maindevice.validate_eeprom_cache {
+                return Ok(());
+            }
+
+            let checksum = eeprom.checksum_word().await?;
+
+            if checksum == cached_checksum {
+                return Ok(());
+            }
+
+            fmt::warn!(
+                "SubDevice {:#06x} cached EEPROM image is stale, reloading",
+                self.configured_address
+            );
+
+            cache.remove(&identity);
+        }
+
+        fmt::debug!(
+            "Caching EEPROM image for SubDevice {:#06x}",
+            self.configured_address
+        );
+
+        eeprom.populate_cache(cache, &identity).await
+    }
+
     /// Read a register.
     ///
     /// Note that while this method is marked safe, raw alterations to SubDevice config or behaviour can
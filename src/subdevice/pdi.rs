@@ -1,9 +1,21 @@
+#[cfg(feature = "pdi-symbols")]
+use super::PdiSymbol;
 use super::{IoRanges, SubDevice, SubDeviceRef};
-use crate::subdevice_group::MySyncUnsafeCell;
+#[cfg(feature = "pdi-symbols")]
+use crate::error::Item;
+use crate::{
+    error::Error,
+    fmt,
+    pdi::{PdiSegment, read_bits, write_bits},
+    subdevice_group::MySyncUnsafeCell,
+};
 use core::{
     marker::PhantomData,
     ops::{Deref, DerefMut, Range},
 };
+use ethercrab_wire::{
+    EtherCrabWireReadSized, EtherCrabWireSized, EtherCrabWireWriteSized, WireError,
+};
 use lock_api::{RawRwLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 pub struct PdiReadGuard<'a, const N: usize, R: RawRwLock> {
@@ -84,6 +96,111 @@ impl<const N: usize, R: RawRwLock> DerefMut for PdiWriteGuard<'_, N, R> {
     }
 }
 
+/// A read-only view of a SubDevice's process data, decoded into `T`.
+///
+/// Created by [`SubDeviceRef::inputs_as`] or [`SubDeviceRef::outputs_as`]. The PDI is locked for
+/// as long as this guard is held.
+pub struct PdiTypedReadGuard<'a, const N: usize, R: RawRwLock, T> {
+    _lock: RwLockReadGuard<'a, R, MySyncUnsafeCell<[u8; N]>>,
+    value: T,
+}
+
+impl<const N: usize, R: RawRwLock, T> Deref for PdiTypedReadGuard<'_, N, R, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// A mutable view of a SubDevice's outputs, decoded into `T`.
+///
+/// Created by [`SubDeviceRef::outputs_as_mut`]. Any changes made to `T` are written back into the
+/// PDI when this guard is dropped. The PDI is locked for as long as this guard is held.
+pub struct PdiTypedWriteGuard<'a, const N: usize, R: RawRwLock, T>
+where
+    T: EtherCrabWireWriteSized,
+{
+    lock: RwLockWriteGuard<'a, R, MySyncUnsafeCell<[u8; N]>>,
+    segment: PdiSegment,
+    value: T,
+}
+
+impl<const N: usize, R: RawRwLock, T> Deref for PdiTypedWriteGuard<'_, N, R, T>
+where
+    T: EtherCrabWireWriteSized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<const N: usize, R: RawRwLock, T> DerefMut for PdiTypedWriteGuard<'_, N, R, T>
+where
+    T: EtherCrabWireWriteSized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<const N: usize, R: RawRwLock, T> Drop for PdiTypedWriteGuard<'_, N, R, T>
+where
+    T: EtherCrabWireWriteSized,
+{
+    fn drop(&mut self) {
+        // Length was checked when the guard was created, so this can't fail.
+        let _ = write_bits(
+            &mut self.lock.get_mut()[self.segment.bytes.clone()],
+            usize::from(self.segment.bit_offset),
+            self.segment.bit_len,
+            self.value.pack().as_ref(),
+        );
+    }
+}
+
+/// Check that a PDI segment is the same length as `T`.
+fn check_typed_len<T>(segment: &PdiSegment) -> Result<(), Error>
+where
+    T: EtherCrabWireSized,
+{
+    let pdi_length = segment.bit_len.div_ceil(8);
+
+    if pdi_length != T::PACKED_LEN {
+        fmt::error!(
+            "Mapped process data is {} bytes but type is {} bytes",
+            pdi_length,
+            T::PACKED_LEN
+        );
+
+        return Err(Error::PdiLengthMismatch {
+            pdi_length,
+            type_length: T::PACKED_LEN,
+        });
+    }
+
+    Ok(())
+}
+
+/// Read the given segment out of the PDI and decode it into `T`.
+fn read_segment<T>(pdi: &[u8], segment: &PdiSegment) -> Result<T, Error>
+where
+    T: EtherCrabWireReadSized,
+{
+    let mut buf = T::buffer();
+
+    read_bits(
+        &pdi[segment.bytes.clone()],
+        usize::from(segment.bit_offset),
+        segment.bit_len,
+        buf.as_mut(),
+    )?;
+
+    Ok(T::unpack_from_slice(buf.as_ref())?)
+}
+
 /// Process Data Image (PDI) segments for a given SubDevice.
 ///
 /// Used in conjunction with [`SubDeviceRef`].
@@ -185,6 +302,9 @@ impl<const MAX_PDI: usize, R: RawRwLock> SubDeviceRef<'_, SubDevicePdi<'_, MAX_P
     }
 
     /// Get a reference to the raw input data for this SubDevice in the Process Data Image (PDI).
+    ///
+    /// If the group uses [`PdiLayout::BitPacked`](crate::subdevice_group::PdiLayout::BitPacked),
+    /// the first and last bytes may contain data belonging to other SubDevices.
     pub fn inputs_raw(&self) -> PdiReadGuard<'_, MAX_PDI, R> {
         PdiReadGuard {
             lock: self.state.pdi.read(),
@@ -211,6 +331,299 @@ impl<const MAX_PDI: usize, R: RawRwLock> SubDeviceRef<'_, SubDevicePdi<'_, MAX_P
             _lt: PhantomData,
         }
     }
+
+    /// Decode this SubDevice's inputs into a type, for example a struct that derives
+    /// [`EtherCrabWireRead`](ethercrab_wire::EtherCrabWireRead).
+    ///
+    /// The PDI is locked while the returned guard is held, so it should be dropped before the next
+    /// call to [`tx_rx`](crate::SubDeviceGroup::tx_rx).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::PdiLengthMismatch`] if the SubDevice's mapped inputs are not exactly
+    /// `T::PACKED_LEN` bytes long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use ethercrab::{
+    /// #     error::Error, MainDevice, MainDeviceConfig, PduStorage, Timeouts,
+    /// # };
+    /// # async fn case() -> Result<(), Error> {
+    /// # static PDU_STORAGE: PduStorage<8, 32> = PduStorage::new();
+    /// # let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+    /// # let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+    /// use ethercrab::EtherCrabWireReadWrite;
+    ///
+    /// #[derive(EtherCrabWireReadWrite)]
+    /// #[wire(bytes = 6)]
+    /// struct DriveInputs {
+    ///     #[wire(bytes = 2)]
+    ///     status_word: u16,
+    ///     #[wire(bytes = 4)]
+    ///     actual_position: i32,
+    /// }
+    ///
+    /// #[derive(EtherCrabWireReadWrite)]
+    /// #[wire(bytes = 2)]
+    /// struct DriveOutputs {
+    ///     #[wire(bytes = 2)]
+    ///     control_word: u16,
+    /// }
+    ///
+    /// let mut group = maindevice.init_single_group::<8, 8>(ethercrab::std::ethercat_now).await?;
+    /// let group = group.into_op(&maindevice).await?;
+    /// let subdevice = group.subdevice(&maindevice, 0)?;
+    ///
+    /// let position = subdevice.inputs_as::<DriveInputs>()?.actual_position;
+    ///
+    /// subdevice.outputs_as_mut::<DriveOutputs>()?.control_word = 0x0f;
+    /// # Ok(()) }
+    /// ```
+    pub fn inputs_as<T>(&self) -> Result<PdiTypedReadGuard<'_, MAX_PDI, R, T>, Error>
+    where
+        T: EtherCrabWireReadSized,
+    {
+        self.typed_read_guard(&self.state.config.io.input)
+    }
+
+    /// Decode this SubDevice's outputs into a type.
+    ///
+    /// See [`inputs_as`](SubDeviceRef::inputs_as) for details.
+    pub fn outputs_as<T>(&self) -> Result<PdiTypedReadGuard<'_, MAX_PDI, R, T>, Error>
+    where
+        T: EtherCrabWireReadSized,
+    {
+        self.typed_read_guard(&self.state.config.io.output)
+    }
+
+    /// Decode this SubDevice's outputs into a type that can be modified. Any changes are written
+    /// back into the PDI when the returned guard is dropped.
+    ///
+    /// See [`inputs_as`](SubDeviceRef::inputs_as) for details and an example.
+    pub fn outputs_as_mut<T>(&self) -> Result<PdiTypedWriteGuard<'_, MAX_PDI, R, T>, Error>
+    where
+        T: EtherCrabWireReadSized + EtherCrabWireWriteSized,
+    {
+        let segment = self.state.config.io.output.clone();
+
+        check_typed_len::<T>(&segment)?;
+
+        let lock = self.state.pdi.write();
+
+        let value = read_segment(unsafe { &*lock.get() }, &segment)?;
+
+        Ok(PdiTypedWriteGuard {
+            lock,
+            segment,
+            value,
+        })
+    }
+
+    fn typed_read_guard<T>(
+        &self,
+        segment: &PdiSegment,
+    ) -> Result<PdiTypedReadGuard<'_, MAX_PDI, R, T>, Error>
+    where
+        T: EtherCrabWireReadSized,
+    {
+        check_typed_len::<T>(segment)?;
+
+        let lock = self.state.pdi.read();
+
+        let value = read_segment(unsafe { &*lock.get() }, segment)?;
+
+        Ok(PdiTypedReadGuard { _lock: lock, value })
+    }
+
+    /// Read a single bit from this SubDevice's inputs, where `0` is the first mapped input bit.
+    ///
+    /// This is useful for digital input terminals, and works the same for both byte-aligned and
+    /// [bit-packed](crate::subdevice_group::PdiLayout::BitPacked) groups.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is outside this SubDevice's mapped inputs.
+    pub fn input_bit(&self, index: usize) -> Result<bool, Error> {
+        let pdi = self.state.pdi.read();
+
+        read_pdi_bit(unsafe { &*pdi.get() }, &self.state.config.io.input, index)
+    }
+
+    /// Read back a single bit from this SubDevice's outputs.
+    ///
+    /// See [`input_bit`](SubDeviceRef::input_bit) for details.
+    pub fn output_bit(&self, index: usize) -> Result<bool, Error> {
+        let pdi = self.state.pdi.read();
+
+        read_pdi_bit(unsafe { &*pdi.get() }, &self.state.config.io.output, index)
+    }
+
+    /// Set a single bit in this SubDevice's outputs, leaving all other bits untouched.
+    ///
+    /// See [`input_bit`](SubDeviceRef::input_bit) for details.
+    pub fn set_output_bit(&self, index: usize, value: bool) -> Result<(), Error> {
+        let segment = &self.state.config.io.output;
+
+        if index >= segment.bit_len {
+            return Err(Error::Wire(WireError::WriteBufferTooShort));
+        }
+
+        let mut pdi = self.state.pdi.write();
+
+        write_bits(
+            &mut pdi.get_mut()[segment.bytes.clone()],
+            usize::from(segment.bit_offset) + index,
+            1,
+            &[u8::from(value)],
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "pdi-symbols")]
+impl<const MAX_PDI: usize, R: RawRwLock> SubDeviceRef<'_, SubDevicePdi<'_, MAX_PDI, R>> {
+    /// Read an input PDO entry by name.
+    ///
+    /// The name can either be the PDO entry name, e.g. `Position actual value`, or the PDO name and
+    /// entry name separated by a `.`, e.g. `Channel 1.Input`. See
+    /// [`SubDevice::input_symbols`] for a list of available entries.
+    ///
+    /// `T` must be at least as wide as the PDO entry. If `T` is wider, entries with a signed integer
+    /// [`data_type`](PdiSymbol::data_type) are sign extended, and the remaining bits of any other
+    /// entry are set to zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if no input entry with the given name exists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use ethercrab::{
+    /// #     error::Error, MainDevice, MainDeviceConfig, PduStorage, Timeouts,
+    /// # };
+    /// # async fn case() -> Result<(), Error> {
+    /// # static PDU_STORAGE: PduStorage<8, 32> = PduStorage::new();
+    /// # let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+    /// # let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+    /// let mut group = maindevice.init_single_group::<8, 8>(ethercrab::std::ethercat_now).await?;
+    /// let group = group.into_op(&maindevice).await?;
+    /// let subdevice = group.subdevice(&maindevice, 0)?;
+    ///
+    /// let position = subdevice.input::<i32>("Position actual value")?;
+    ///
+    /// subdevice.set_output("Control word", 0x0fu16)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn input<T>(&self, name: &str) -> Result<T, Error>
+    where
+        T: EtherCrabWireReadSized,
+    {
+        let symbol = self.symbol(false, name)?;
+
+        let pdi = self.inputs_raw();
+
+        read_symbol(&pdi, self.state.config.io.input.bit_offset, symbol)
+    }
+
+    /// Read back an output PDO entry by name.
+    ///
+    /// See [`input`](SubDeviceRef::input) for details.
+    pub fn output<T>(&self, name: &str) -> Result<T, Error>
+    where
+        T: EtherCrabWireReadSized,
+    {
+        let symbol = self.symbol(true, name)?;
+
+        let pdi = self.outputs_raw();
+
+        read_symbol(&pdi, self.state.config.io.output.bit_offset, symbol)
+    }
+
+    /// Write an output PDO entry by name.
+    ///
+    /// Only the lowest bits of `value` that fit in the PDO entry are written, so a wider type may be
+    /// used, e.g. an `i32` literal for a 16 bit entry. `T` must not be narrower than the PDO entry.
+    ///
+    /// See [`input`](SubDeviceRef::input) for details on naming.
+    pub fn set_output<T>(&self, name: &str, value: T) -> Result<(), Error>
+    where
+        T: EtherCrabWireWriteSized,
+    {
+        let symbol = self.symbol(true, name)?;
+
+        let mut pdi = self.outputs_raw_mut();
+
+        write_bits(
+            &mut pdi,
+            usize::from(self.state.config.io.output.bit_offset) + symbol.bit_offset as usize,
+            usize::from(symbol.bit_len),
+            value.pack().as_ref(),
+        )?;
+
+        Ok(())
+    }
+
+    fn symbol(&self, is_output: bool, name: &str) -> Result<PdiSymbol<'_>, Error> {
+        self.state.pdi_symbols.find(is_output, name).ok_or_else(|| {
+            fmt::error!("Could not find PDO entry {}", name);
+
+            Error::NotFound {
+                item: Item::PdoEntry,
+                index: None,
+            }
+        })
+    }
+}
+
+fn read_pdi_bit(pdi: &[u8], segment: &PdiSegment, index: usize) -> Result<bool, Error> {
+    if index >= segment.bit_len {
+        return Err(Error::Wire(WireError::ReadBufferTooShort));
+    }
+
+    let mut bit = [0u8; 1];
+
+    read_bits(
+        &pdi[segment.bytes.clone()],
+        usize::from(segment.bit_offset) + index,
+        1,
+        &mut bit,
+    )?;
+
+    Ok(bit[0] == 1)
+}
+
+#[cfg(feature = "pdi-symbols")]
+fn read_symbol<T>(pdi: &[u8], segment_bit_offset: u8, symbol: PdiSymbol<'_>) -> Result<T, Error>
+where
+    T: EtherCrabWireReadSized,
+{
+    let mut buf = T::buffer();
+
+    let bit_len = usize::from(symbol.bit_len);
+
+    read_bits(
+        pdi,
+        usize::from(segment_bit_offset) + symbol.bit_offset as usize,
+        bit_len,
+        buf.as_mut(),
+    )?;
+
+    let buf = buf.as_mut();
+
+    if symbol.data_type.is_signed() && bit_len > 0 && bit_len < buf.len() * 8 {
+        let sign_bit = bit_len - 1;
+
+        if buf[sign_bit / 8] & (1 << (sign_bit % 8)) != 0 {
+            buf[bit_len / 8] |= 0xff << (bit_len % 8);
+
+            buf[(bit_len / 8 + 1)..].fill(0xff);
+        }
+    }
+
+    Ok(T::unpack_from_slice(buf)?)
 }
 
 #[cfg(test)]
@@ -227,14 +640,8 @@ mod tests {
         let mut sd = SubDevice::default();
 
         sd.config.io = IoRanges {
-            input: PdiSegment {
-                bytes: 0..2,
-                // bit_len: 16,
-            },
-            output: PdiSegment {
-                bytes: 2..4,
-                // bit_len: 16,
-            },
+            input: PdiSegment::from_bytes(0..2),
+            output: PdiSegment::from_bytes(2..4),
         };
 
         const LEN: usize = 64;
@@ -257,4 +664,274 @@ mod tests {
             &[0xab, 0xab, 0xff, 0xab]
         );
     }
+
+    #[test]
+    fn typed_io() {
+        #[derive(Debug, PartialEq, ethercrab_wire::EtherCrabWireReadWrite)]
+        #[wire(bytes = 3)]
+        struct Inputs {
+            #[wire(bits = 4)]
+            status: u8,
+            #[wire(pre_skip = 4, bytes = 2)]
+            value: u16,
+        }
+
+        #[derive(Debug, PartialEq, ethercrab_wire::EtherCrabWireReadWrite)]
+        #[wire(bytes = 2)]
+        struct Outputs {
+            #[wire(bytes = 2)]
+            control_word: u16,
+        }
+
+        static PDU_STORAGE: PduStorage<8, 64> = PduStorage::new();
+        let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+        let maindevice =
+            MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+        let mut sd = SubDevice::default();
+
+        sd.config.io = IoRanges {
+            input: PdiSegment::from_bytes(0..3),
+            output: PdiSegment::from_bytes(3..5),
+        };
+
+        let pdi_storage = RwLock::<crate::DefaultLock, _>::new(MySyncUnsafeCell::new([
+            0xa5u8, 0x34, 0x12, 0x00, 0x00, 0xff,
+        ]));
+
+        let pdi = SubDevicePdi::new(&sd, &pdi_storage);
+
+        let sd_ref = SubDeviceRef::new(&maindevice, 0x1000, pdi);
+
+        assert_eq!(
+            sd_ref.inputs_as::<Inputs>().as_deref(),
+            Ok(&Inputs {
+                status: 0x05,
+                value: 0x1234
+            })
+        );
+
+        assert_eq!(
+            sd_ref.inputs_as::<Outputs>().map(|_| ()),
+            Err(Error::PdiLengthMismatch {
+                pdi_length: 3,
+                type_length: 2
+            })
+        );
+
+        sd_ref.outputs_as_mut::<Outputs>().unwrap().control_word = 0x0f;
+
+        assert_eq!(
+            sd_ref.outputs_as::<Outputs>().as_deref(),
+            Ok(&Outputs { control_word: 0x0f })
+        );
+        assert_eq!(
+            &pdi_storage.write().get_mut()[..],
+            &[0xa5, 0x34, 0x12, 0x0f, 0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn bit_packed_io() {
+        #[derive(Debug, PartialEq, ethercrab_wire::EtherCrabWireReadWrite)]
+        #[wire(bytes = 1)]
+        struct Channels {
+            #[wire(bits = 1)]
+            ch1: bool,
+            #[wire(bits = 1)]
+            ch2: bool,
+            #[wire(bits = 1)]
+            ch3: bool,
+            #[wire(bits = 1, post_skip = 4)]
+            ch4: bool,
+        }
+
+        static PDU_STORAGE: PduStorage<8, 64> = PduStorage::new();
+        let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+        let maindevice =
+            MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+        let mut sd = SubDevice::default();
+
+        // E.g. a 4 channel digital IO terminal packed after another SubDevice's 6 input bits and
+        // 2 output bits.
+        sd.config.io = IoRanges {
+            input: PdiSegment {
+                bytes: 0..2,
+                bit_offset: 6,
+                bit_len: 4,
+            },
+            output: PdiSegment {
+                bytes: 2..3,
+                bit_offset: 2,
+                bit_len: 4,
+            },
+        };
+
+        let pdi_storage = RwLock::<crate::DefaultLock, _>::new(MySyncUnsafeCell::new([
+            0b0100_0000u8,
+            0b1111_0001,
+            0b1100_0011,
+        ]));
+
+        let pdi = SubDevicePdi::new(&sd, &pdi_storage);
+
+        let sd_ref = SubDeviceRef::new(&maindevice, 0x1000, pdi);
+
+        assert_eq!(sd_ref.input_bit(0), Ok(true));
+        assert_eq!(sd_ref.input_bit(1), Ok(false));
+        assert_eq!(sd_ref.input_bit(2), Ok(true));
+        assert_eq!(sd_ref.input_bit(3), Ok(false));
+        assert_eq!(
+            sd_ref.input_bit(4),
+            Err(Error::Wire(WireError::ReadBufferTooShort))
+        );
+
+        assert_eq!(
+            sd_ref.inputs_as::<Channels>().as_deref(),
+            Ok(&Channels {
+                ch1: true,
+                ch2: false,
+                ch3: true,
+                ch4: false
+            })
+        );
+
+        assert_eq!(sd_ref.set_output_bit(1, true), Ok(()));
+        assert_eq!(sd_ref.output_bit(1), Ok(true));
+
+        sd_ref.outputs_as_mut::<Channels>().unwrap().ch4 = true;
+
+        // Neighbouring bits must be left untouched
+        assert_eq!(
+            &pdi_storage.write().get_mut()[..],
+            &[0b0100_0000, 0b1111_0001, 0b1110_1011]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "pdi-symbols")]
+    fn read_symbol_sign_extension() {
+        use crate::PrimitiveDataType;
+
+        let symbol = PdiSymbol {
+            name: "Value",
+            pdo_name: "",
+            index: 0x6000,
+            sub_index: 0x11,
+            bit_offset: 4,
+            bit_len: 12,
+            data_type: PrimitiveDataType::I16,
+        };
+
+        // -2 as a 12 bit integer, 4 bits into the PDI
+        let pdi = [0xe5u8, 0xff];
+
+        assert_eq!(read_symbol::<i16>(&pdi, 0, symbol), Ok(-2));
+        assert_eq!(read_symbol::<i32>(&pdi, 0, symbol), Ok(-2));
+        assert_eq!(read_symbol::<i64>(&pdi, 0, symbol), Ok(-2));
+        // Positive values are left alone
+        assert_eq!(read_symbol::<i16>(&[0xe5, 0x7f], 0, symbol), Ok(0x07fe));
+        // Unsigned types aren't sign extended
+        assert_eq!(
+            read_symbol::<i16>(
+                &pdi,
+                0,
+                PdiSymbol {
+                    data_type: PrimitiveDataType::Bit12,
+                    ..symbol
+                }
+            ),
+            Ok(0x0ffe)
+        );
+        assert_eq!(
+            read_symbol::<i32>(
+                &[0x80],
+                0,
+                PdiSymbol {
+                    bit_offset: 0,
+                    bit_len: 8,
+                    data_type: PrimitiveDataType::I8,
+                    ..symbol
+                }
+            ),
+            Ok(-128)
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "pdi-symbols")]
+    async fn access_by_name() {
+        use crate::{
+            eeprom::file_provider::EepromFile,
+            subdevice::{
+                configuration::PdoDirection, eeprom::SubDeviceEeprom, symbols::PendingSymbols,
+            },
+        };
+
+        static PDU_STORAGE: PduStorage<8, 64> = PduStorage::new();
+        let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+        let maindevice =
+            MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+        let mut sd = SubDevice::default();
+
+        sd.config.io = IoRanges {
+            input: PdiSegment::from_bytes(0..4),
+            output: PdiSegment::from_bytes(4..6),
+        };
+
+        let eeprom = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/ek1100.hex"
+        )));
+
+        let mut inputs = PendingSymbols::new(&eeprom, PdoDirection::MasterRead, false).await;
+        inputs
+            .push(0x1a00, 0x6000, 0x01, 4, 4, Some("Status"), None)
+            .unwrap();
+        inputs
+            .push(0x1a00, 0x6000, 0x11, 16, 8, Some("Value"), None)
+            .unwrap();
+
+        let mut outputs = PendingSymbols::new(&eeprom, PdoDirection::MasterWrite, false).await;
+        outputs
+            .push(0x1600, 0x7000, 0x01, 16, 0, Some("Control word"), None)
+            .unwrap();
+
+        sd.pdi_symbols
+            .replace(PdoDirection::MasterRead, &inputs, &eeprom)
+            .await
+            .unwrap();
+        sd.pdi_symbols
+            .replace(PdoDirection::MasterWrite, &outputs, &eeprom)
+            .await
+            .unwrap();
+
+        let pdi_storage = RwLock::<crate::DefaultLock, _>::new(MySyncUnsafeCell::new([
+            0xa5u8, 0x34, 0x12, 0x00, 0x00, 0x00,
+        ]));
+
+        let pdi = SubDevicePdi::new(&sd, &pdi_storage);
+
+        let sd_ref = SubDeviceRef::new(&maindevice, 0x1000, pdi);
+
+        assert_eq!(sd_ref.input::<u8>("Status"), Ok(0x0a));
+        assert_eq!(sd_ref.input::<u16>("Value"), Ok(0x1234));
+        assert_eq!(sd_ref.input::<u32>("Value"), Ok(0x1234));
+        assert_eq!(
+            sd_ref.input::<u8>("Value"),
+            Err(Error::Wire(ethercrab_wire::WireError::WriteBufferTooShort))
+        );
+        assert_eq!(
+            sd_ref.input::<u16>("Control word"),
+            Err(Error::NotFound {
+                item: Item::PdoEntry,
+                index: None
+            })
+        );
+
+        assert_eq!(sd_ref.set_output("Control word", 0x0f), Ok(()));
+        assert_eq!(sd_ref.output::<u16>("Control word"), Ok(0x0f));
+        assert_eq!(
+            &pdi_storage.write().get_mut()[..],
+            &[0xa5, 0x34, 0x12, 0x00, 0x0f, 0x00]
+        );
+    }
 }
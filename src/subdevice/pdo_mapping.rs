@@ -0,0 +1,295 @@
+//! Custom PDO mapping and assignment for a single SubDevice.
+
+use super::configuration::PdoDirection;
+
+/// A single PDO mapping entry.
+///
+/// Defined in ETG1000.6 Table 74/Table 75.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PdoEntry {
+    /// Object dictionary index of the mapped object, e.g. `0x6000`.
+    pub index: u16,
+
+    /// Sub-index of the mapped object.
+    pub sub_index: u8,
+
+    /// Length of the mapped object in bits.
+    pub bit_len: u8,
+
+    /// Entry name, used to access this entry in the PDI by name.
+    ///
+    /// If this is `None`, the name will be read from the SubDevice's EEPROM if it can be found.
+    pub name: Option<&'static str>,
+}
+
+impl PdoEntry {
+    /// Create a new PDO entry.
+    pub const fn new(index: u16, sub_index: u8, bit_len: u8) -> Self {
+        Self {
+            index,
+            sub_index,
+            bit_len,
+            name: None,
+        }
+    }
+
+    /// Set the name used to access this entry in the PDI.
+    ///
+    /// Names are usually taken from the SubDevice's ESI file.
+    pub const fn with_name(self, name: &'static str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    /// Create a padding entry of the given length.
+    ///
+    /// Padding entries map to object `0x0000:00` and occupy space in the PDI without carrying any
+    /// data.
+    pub const fn gap(bit_len: u8) -> Self {
+        Self::new(0x0000, 0x00, bit_len)
+    }
+
+    /// The value written to the PDO mapping object's sub-index for this entry.
+    pub(crate) const fn raw(&self) -> u32 {
+        ((self.index as u32) << 16) | ((self.sub_index as u32) << 8) | self.bit_len as u32
+    }
+}
+
+/// A PDO to assign to a sync manager, along with the entries to map into it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pdo {
+    /// PDO mapping object index, e.g. `0x1600` for the first RxPDO or `0x1a00` for the first
+    /// TxPDO.
+    pub index: u16,
+
+    /// Entries to map into this PDO.
+    ///
+    /// If this list is empty, the PDO's existing (possibly fixed) mapping is left untouched and the
+    /// PDO is only assigned to the sync manager.
+    pub entries: &'static [PdoEntry],
+
+    /// Oversampling factor for this PDO.
+    ///
+    /// This multiplies the size of the PDO in the PDI and should match the oversampling factor
+    /// configured in the SubDevice, e.g. with an SDO write. Defaults to `1`.
+    pub oversampling: u16,
+
+    /// PDO name, e.g. `Channel 1`.
+    ///
+    /// If this is `None`, the name will be read from the SubDevice's EEPROM if it can be found.
+    pub name: Option<&'static str>,
+}
+
+impl Pdo {
+    /// Create a PDO with the given mapping object index and entries.
+    pub const fn new(index: u16, entries: &'static [PdoEntry]) -> Self {
+        Self {
+            index,
+            entries,
+            oversampling: 1,
+            name: None,
+        }
+    }
+
+    /// Set the PDO name.
+    pub const fn with_name(self, name: &'static str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    /// Assign a PDO without changing its mapping.
+    ///
+    /// This is useful for PDOs with a fixed mapping.
+    pub const fn assign(index: u16) -> Self {
+        Self::new(index, &[])
+    }
+
+    /// Set the oversampling factor for this PDO.
+    pub const fn with_oversampling(self, oversampling: u16) -> Self {
+        Self {
+            oversampling,
+            ..self
+        }
+    }
+
+    /// Total length of this PDO's entries in bits, not including oversampling.
+    pub const fn bit_len(&self) -> u16 {
+        let mut bits = 0u16;
+        let mut i = 0;
+
+        while i < self.entries.len() {
+            bits += self.entries[i].bit_len as u16;
+
+            i += 1;
+        }
+
+        bits
+    }
+}
+
+/// Custom PDO mapping and assignment for a SubDevice.
+///
+/// By default, EtherCrab reads the PDO assignment and mapping already present in the SubDevice
+/// when configuring the PDI. A `PdoMapping` overrides this: the given RxPDOs (outputs) and TxPDOs
+/// (inputs) are mapped and assigned to the SubDevice's first output and input sync manager
+/// respectively during PRE-OP -> SAFE-OP configuration, before the PDI is sized.
+///
+/// Mappings are only applied to SubDevices that support CoE.
+///
+/// # Examples
+///
+/// Map two channels of a 16 bit analog input terminal, and assign a single fixed RxPDO.
+///
+/// ```rust
+/// use ethercrab::{Pdo, PdoEntry, PdoMapping};
+///
+/// static INPUTS: &[Pdo] = &[Pdo::new(
+///     0x1a00,
+///     &[
+///         // Status word
+///         PdoEntry::new(0x6000, 0x01, 16),
+///         // Value
+///         PdoEntry::new(0x6000, 0x11, 16),
+///     ],
+/// )];
+///
+/// static OUTPUTS: &[Pdo] = &[Pdo::assign(0x1600)];
+///
+/// let mapping = PdoMapping::new().with_inputs(INPUTS).with_outputs(OUTPUTS);
+///
+/// // Later, during PRE-OP:
+/// // subdevice.set_pdo_mapping(mapping);
+/// # let _ = mapping;
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PdoMapping {
+    /// TxPDOs to assign to the first input sync manager (usually `0x1c13`).
+    pub(crate) inputs: Option<&'static [Pdo]>,
+
+    /// RxPDOs to assign to the first output sync manager (usually `0x1c12`).
+    pub(crate) outputs: Option<&'static [Pdo]>,
+
+    /// Oversampling overrides for PDOs not otherwise configured by this mapping, as a list of
+    /// `(PDO, oversampling multiplier)`.
+    pub(crate) oversampling: &'static [(u16, u16)],
+}
+
+impl PdoMapping {
+    /// Create an empty mapping which leaves the SubDevice's existing PDO configuration untouched.
+    pub const fn new() -> Self {
+        Self {
+            inputs: None,
+            outputs: None,
+            oversampling: &[],
+        }
+    }
+
+    /// Set the TxPDOs (SubDevice inputs) to assign to the input sync manager.
+    pub const fn with_inputs(self, inputs: &'static [Pdo]) -> Self {
+        Self {
+            inputs: Some(inputs),
+            ..self
+        }
+    }
+
+    /// Set the RxPDOs (SubDevice outputs) to assign to the output sync manager.
+    pub const fn with_outputs(self, outputs: &'static [Pdo]) -> Self {
+        Self {
+            outputs: Some(outputs),
+            ..self
+        }
+    }
+
+    /// Set oversampling multipliers for PDOs as a list of `(PDO, oversampling multiplier)`.
+    ///
+    /// This applies to PDOs in the SubDevice's existing assignment. Oversampling for PDOs given to
+    /// [`with_inputs`](PdoMapping::with_inputs) or [`with_outputs`](PdoMapping::with_outputs)
+    /// should be set with [`Pdo::with_oversampling`].
+    pub const fn with_oversampling(self, oversampling: &'static [(u16, u16)]) -> Self {
+        Self {
+            oversampling,
+            ..self
+        }
+    }
+
+    /// Whether this mapping changes anything about the SubDevice's default PDO configuration.
+    pub const fn is_empty(&self) -> bool {
+        self.inputs.is_none() && self.outputs.is_none() && self.oversampling.is_empty()
+    }
+
+    /// The PDO assignment to write for a sync manager of the given direction, if any.
+    pub(crate) fn assignment(&self, direction: PdoDirection) -> Option<&'static [Pdo]> {
+        match direction {
+            PdoDirection::MasterRead => self.inputs,
+            PdoDirection::MasterWrite => self.outputs,
+        }
+    }
+
+    /// Find a PDO in this mapping's assignments.
+    pub(crate) fn find_pdo(&self, direction: PdoDirection, pdo_index: u16) -> Option<&'static Pdo> {
+        self.assignment(direction)?
+            .iter()
+            .find(|pdo| pdo.index == pdo_index)
+    }
+
+    /// Find the oversampling factor for the given PDO index.
+    pub(crate) fn oversampling_for(&self, pdo_index: u16) -> u16 {
+        self.inputs
+            .into_iter()
+            .chain(self.outputs)
+            .flatten()
+            .find(|pdo| pdo.index == pdo_index)
+            .map(|pdo| pdo.oversampling)
+            .or_else(|| {
+                self.oversampling
+                    .iter()
+                    .find_map(|(pdo, mul)| (*pdo == pdo_index).then_some(*mul))
+            })
+            .unwrap_or(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_raw() {
+        let entry = PdoEntry::new(0x6000, 0x11, 16);
+
+        assert_eq!(entry.raw(), 0x6000_1110);
+        assert_eq!(PdoEntry::gap(8).raw(), 0x0000_0008);
+    }
+
+    #[test]
+    fn oversampling_lookup() {
+        static INPUTS: &[Pdo] = &[
+            Pdo::new(0x1a00, &[PdoEntry::new(0x6000, 0x11, 16)]).with_oversampling(4),
+            Pdo::assign(0x1a01),
+        ];
+
+        let mapping = PdoMapping::new()
+            .with_inputs(INPUTS)
+            .with_oversampling(&[(0x1a80, 8), (0x1a00, 2)]);
+
+        assert_eq!(mapping.oversampling_for(0x1a00), 4);
+        assert_eq!(mapping.oversampling_for(0x1a01), 1);
+        assert_eq!(mapping.oversampling_for(0x1a80), 8);
+        assert_eq!(mapping.oversampling_for(0x1600), 1);
+        assert_eq!(INPUTS[0].bit_len(), 16);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(PdoMapping::new().is_empty());
+        assert!(PdoMapping::default().is_empty());
+        assert!(!PdoMapping::new().with_outputs(&[]).is_empty());
+    }
+}
@@ -26,8 +26,10 @@ impl Port {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The kind of node a SubDevice forms in the network topology, based on its number of open ports.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Topology {
     /// The SubDevice has two open ports, with only upstream and downstream subdevices.
     Passthrough,
@@ -40,6 +42,7 @@ pub enum Topology {
 }
 
 impl Topology {
+    /// Whether the SubDevice splits the network into more than one downstream branch.
     pub fn is_junction(&self) -> bool {
         matches!(self, Self::Fork | Self::Cross)
     }
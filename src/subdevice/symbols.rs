@@ -0,0 +1,648 @@
+//! Symbolic access to a SubDevice's process data by PDO entry name.
+
+use crate::{
+    base_data_types::PrimitiveDataType,
+    eeprom::{
+        EepromDataProvider,
+        types::{Pdo as EepromPdo, PdoEntry as EepromPdoEntry, PdoType},
+    },
+    error::{Error, Item},
+    fmt,
+    subdevice::{configuration::PdoDirection, eeprom::SubDeviceEeprom},
+};
+
+/// Maximum number of PDO entries that can be looked up by name in each SubDevice.
+pub(crate) const MAX_PDI_SYMBOLS: usize = 32;
+
+/// Maximum combined length of all PDO and PDO entry names stored for each SubDevice.
+const MAX_NAMES_LEN: usize = 256;
+
+/// A single PDO entry mapped into a SubDevice's process data.
+///
+/// Returned by [`SubDevice::input_symbols`](crate::SubDevice::input_symbols) and
+/// [`SubDevice::output_symbols`](crate::SubDevice::output_symbols).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PdiSymbol<'a> {
+    /// PDO entry name, e.g. `Position actual value`.
+    ///
+    /// This is empty if the SubDevice's EEPROM refers to a name that could not be read.
+    pub name: &'a str,
+
+    /// Name of the PDO containing this entry, e.g. `Channel 1`.
+    pub pdo_name: &'a str,
+
+    /// Object dictionary index of the entry.
+    pub index: u16,
+
+    /// Object dictionary sub-index of the entry.
+    pub sub_index: u8,
+
+    /// Offset in bits from the start of the SubDevice's input or output data.
+    pub bit_offset: u32,
+
+    /// Length of the entry in bits.
+    pub bit_len: u8,
+
+    /// Entry data type, if known.
+    pub data_type: PrimitiveDataType,
+}
+
+impl PdiSymbol<'_> {
+    /// Whether this symbol matches the given name.
+    ///
+    /// Matches either the entry name alone, e.g. `Input`, or the PDO name and entry name separated
+    /// by a `.`, e.g. `Channel 1.Input`.
+    fn matches(&self, name: &str) -> bool {
+        if self.name.is_empty() {
+            return false;
+        }
+
+        name == self.name
+            || name
+                .strip_prefix(self.pdo_name)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|rest| rest == self.name)
+    }
+}
+
+/// Where to get a symbol name from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SymbolName {
+    /// No name.
+    None,
+    /// 1-based index into the EEPROM strings category.
+    Eeprom(u8),
+    /// A name given by the user.
+    Static(&'static str),
+}
+
+impl SymbolName {
+    fn eeprom_index(self) -> Option<u8> {
+        match self {
+            SymbolName::Eeprom(idx) if idx > 0 => Some(idx),
+            _ => None,
+        }
+    }
+}
+
+/// A symbol discovered during PDI configuration whose name has not been read yet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct PendingSymbol {
+    pub index: u16,
+    pub sub_index: u8,
+    pub bit_offset: u32,
+    pub bit_len: u8,
+    pub data_type: PrimitiveDataType,
+    pub name: SymbolName,
+    pub pdo_name: SymbolName,
+}
+
+/// Symbols discovered for one direction during PDI configuration.
+#[derive(Debug, Default)]
+pub(crate) struct PendingSymbols {
+    symbols: heapless::Vec<PendingSymbol, MAX_PDI_SYMBOLS>,
+
+    /// PDO entries read from EEPROM, used to find names and data types.
+    eeprom_entries: heapless::Vec<(EepromPdo, EepromPdoEntry), 64>,
+}
+
+impl PendingSymbols {
+    /// Read PDO entry names and data types from the SubDevice EEPROM if `read_eeprom` is true.
+    ///
+    /// Symbol names are best effort, so any error here is logged and ignored.
+    pub(crate) async fn new<P>(
+        eeprom: &SubDeviceEeprom<P>,
+        direction: PdoDirection,
+        read_eeprom: bool,
+    ) -> Self
+    where
+        P: EepromDataProvider,
+    {
+        if !read_eeprom {
+            return Self::default();
+        }
+
+        let pdo_type = match direction {
+            PdoDirection::MasterRead => PdoType::Tx,
+            PdoDirection::MasterWrite => PdoType::Rx,
+        };
+
+        let eeprom_entries = eeprom.pdo_entries(pdo_type).await.unwrap_or_else(|e| {
+            fmt::warn!(
+                "Failed to read PDO entries from EEPROM, PDI symbols will not be named: {}",
+                e
+            );
+
+            heapless::Vec::new()
+        });
+
+        Self {
+            symbols: heapless::Vec::new(),
+            eeprom_entries,
+        }
+    }
+
+    /// Add all entries of the given PDO as read from EEPROM, starting at the given bit offset.
+    pub(crate) fn push_eeprom_pdo(
+        &mut self,
+        pdo_index: u16,
+        mut bit_offset: u32,
+    ) -> Result<(), Error> {
+        for i in 0..self.eeprom_entries.len() {
+            let (pdo, entry) = &self.eeprom_entries[i];
+
+            if pdo.index != pdo_index {
+                continue;
+            }
+
+            let (index, sub_index, bit_len) =
+                (entry.index, entry.sub_index, entry.data_length_bits);
+
+            self.push(pdo_index, index, sub_index, bit_len, bit_offset, None, None)?;
+
+            bit_offset += u32::from(bit_len);
+        }
+
+        Ok(())
+    }
+
+    /// Add a PDO entry at the given bit offset from the start of the SubDevice's IO segment.
+    ///
+    /// Names not given by the user are looked up from the EEPROM. Entries without a name can't be
+    /// looked up so are not stored.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn push(
+        &mut self,
+        pdo_index: u16,
+        index: u16,
+        sub_index: u8,
+        bit_len: u8,
+        bit_offset: u32,
+        name: Option<&'static str>,
+        pdo_name: Option<&'static str>,
+    ) -> Result<(), Error> {
+        // Padding
+        if index == 0 {
+            return Ok(());
+        }
+
+        let eeprom = self
+            .eeprom_entries
+            .iter()
+            .find(|(pdo, entry)| {
+                pdo.index == pdo_index && entry.index == index && entry.sub_index == sub_index
+            })
+            .or_else(|| {
+                self.eeprom_entries
+                    .iter()
+                    .find(|(_pdo, entry)| entry.index == index && entry.sub_index == sub_index)
+            });
+
+        let data_type = eeprom
+            .and_then(|(_pdo, entry)| PrimitiveDataType::try_from(entry.data_type).ok())
+            .unwrap_or(PrimitiveDataType::Unknown);
+
+        let name = name
+            .map(SymbolName::Static)
+            .or(eeprom.map(|(_pdo, entry)| SymbolName::Eeprom(entry.name_string_idx)))
+            .unwrap_or(SymbolName::None);
+
+        if matches!(name, SymbolName::None | SymbolName::Eeprom(0)) {
+            return Ok(());
+        }
+
+        let pdo_name = pdo_name
+            .map(SymbolName::Static)
+            .or(eeprom.map(|(pdo, _entry)| SymbolName::Eeprom(pdo.name_string_idx)))
+            .unwrap_or(SymbolName::None);
+
+        let symbol = PendingSymbol {
+            index,
+            sub_index,
+            bit_offset,
+            bit_len,
+            data_type,
+            name,
+            pdo_name,
+        };
+
+        self.symbols.push(symbol).map_err(|_| {
+            fmt::error!(
+                "Too many named PDO entries to store symbol {:#06x}:{}, max {}",
+                index,
+                sub_index,
+                MAX_PDI_SYMBOLS
+            );
+
+            Error::Capacity(Item::PdoEntry)
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Symbol {
+    is_output: bool,
+    index: u16,
+    sub_index: u8,
+    bit_offset: u32,
+    bit_len: u8,
+    data_type: PrimitiveDataType,
+    /// Start and length of name in names buffer.
+    name: (u16, u8),
+    /// Start and length of PDO name in names buffer.
+    pdo_name: (u16, u8),
+}
+
+/// Lookup table of PDO entry names to their location in a SubDevice's process data.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct PdiSymbols {
+    names: heapless::String<MAX_NAMES_LEN>,
+    symbols: heapless::Vec<Symbol, MAX_PDI_SYMBOLS>,
+}
+
+impl PdiSymbols {
+    /// Replace all symbols for the given direction with `pending`, reading any names from the
+    /// EEPROM strings category.
+    ///
+    /// Symbol names are best effort, so an error reading names is logged and the affected symbols
+    /// are left unnamed.
+    ///
+    /// Returns [`Error::Capacity`] if the symbols for both directions don't fit.
+    pub(crate) async fn replace<P>(
+        &mut self,
+        direction: PdoDirection,
+        pending: &PendingSymbols,
+        eeprom: &SubDeviceEeprom<P>,
+    ) -> Result<(), Error>
+    where
+        P: EepromDataProvider,
+    {
+        let is_output = matches!(direction, PdoDirection::MasterWrite);
+
+        self.clear(is_output);
+
+        let base = self.symbols.len();
+
+        for pending in pending.symbols.iter() {
+            let name = match pending.name {
+                SymbolName::Static(name) => self.push_name(name),
+                _ => (0, 0),
+            };
+
+            let pdo_name = match pending.pdo_name {
+                SymbolName::Static(name) => self.push_name(name),
+                _ => (0, 0),
+            };
+
+            let symbol = Symbol {
+                is_output,
+                index: pending.index,
+                sub_index: pending.sub_index,
+                bit_offset: pending.bit_offset,
+                bit_len: pending.bit_len,
+                data_type: pending.data_type,
+                name,
+                pdo_name,
+            };
+
+            self.symbols.push(symbol).map_err(|_| {
+                fmt::error!(
+                    "Too many named PDO entries in inputs and outputs, max {}",
+                    MAX_PDI_SYMBOLS
+                );
+
+                Error::Capacity(Item::PdoEntry)
+            })?;
+        }
+
+        let max_index = pending
+            .symbols
+            .iter()
+            .flat_map(|symbol| [symbol.name.eeprom_index(), symbol.pdo_name.eeprom_index()])
+            .flatten()
+            .max();
+
+        let Some(max_index) = max_index else {
+            return Ok(());
+        };
+
+        let result = eeprom
+            .visit_strings(max_index, |string_index, s| {
+                let mut range = None;
+
+                for (pending, symbol_index) in pending.symbols.iter().zip(base..) {
+                    let name = pending.name.eeprom_index() == Some(string_index);
+                    let pdo_name = pending.pdo_name.eeprom_index() == Some(string_index);
+
+                    if !name && !pdo_name {
+                        continue;
+                    }
+
+                    let range = *range.get_or_insert_with(|| self.push_name(s));
+
+                    let Some(symbol) = self.symbols.get_mut(symbol_index) else {
+                        break;
+                    };
+
+                    if name {
+                        symbol.name = range;
+                    }
+
+                    if pdo_name {
+                        symbol.pdo_name = range;
+                    }
+                }
+            })
+            .await;
+
+        if let Err(e) = result {
+            fmt::warn!(
+                "Failed to read PDI symbol names from EEPROM, some symbols will not be named: {}",
+                e
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Remove all symbols in the given direction.
+    fn clear(&mut self, is_output: bool) {
+        let old = core::mem::take(self);
+
+        for symbol in old
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.is_output != is_output)
+        {
+            let symbol = Symbol {
+                name: self.push_name(old.name(symbol.name)),
+                pdo_name: self.push_name(old.name(symbol.pdo_name)),
+                ..*symbol
+            };
+
+            // Capacity is the same as the old list, so this will never fail.
+            let _ = self.symbols.push(symbol);
+        }
+    }
+
+    /// Store a name, returning its position in the names buffer.
+    ///
+    /// Names that don't fit are stored as empty strings.
+    fn push_name(&mut self, name: &str) -> (u16, u8) {
+        if name.is_empty() {
+            return (0, 0);
+        }
+
+        // Reuse existing names, e.g. PDO names shared by multiple entries
+        if let Some(existing) = self
+            .symbols
+            .iter()
+            .flat_map(|symbol| [symbol.name, symbol.pdo_name])
+            .find(|range| self.name(*range) == name)
+        {
+            return existing;
+        }
+
+        let start = self.names.len();
+
+        let Ok(len) = u8::try_from(name.len()) else {
+            return (0, 0);
+        };
+
+        if self.names.push_str(name).is_err() {
+            fmt::warn!(
+                "Not enough space to store PDI symbol name {}, max {} bytes",
+                name,
+                MAX_NAMES_LEN
+            );
+
+            return (0, 0);
+        }
+
+        (start as u16, len)
+    }
+
+    fn name(&self, (start, len): (u16, u8)) -> &str {
+        let start = usize::from(start);
+
+        self.names
+            .get(start..(start + usize::from(len)))
+            .unwrap_or_default()
+    }
+
+    /// All symbols in the given direction.
+    pub(crate) fn iter(&self, is_output: bool) -> impl Iterator<Item = PdiSymbol<'_>> {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.is_output == is_output)
+            .map(|symbol| PdiSymbol {
+                name: self.name(symbol.name),
+                pdo_name: self.name(symbol.pdo_name),
+                index: symbol.index,
+                sub_index: symbol.sub_index,
+                bit_offset: symbol.bit_offset,
+                bit_len: symbol.bit_len,
+                data_type: symbol.data_type,
+            })
+    }
+
+    /// Find a symbol by name.
+    pub(crate) fn find(&self, is_output: bool, name: &str) -> Option<PdiSymbol<'_>> {
+        self.iter(is_output).find(|symbol| symbol.matches(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eeprom::file_provider::EepromFile;
+
+    #[tokio::test]
+    async fn el2828_output_names() {
+        let eeprom = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/el2828.hex"
+        )));
+
+        let mut pending = PendingSymbols::new(&eeprom, PdoDirection::MasterWrite, true).await;
+
+        for (i, pdo) in (0x1600..=0x1607).enumerate() {
+            pending.push_eeprom_pdo(pdo, i as u32).unwrap();
+        }
+
+        let mut symbols = PdiSymbols::default();
+
+        symbols
+            .replace(PdoDirection::MasterWrite, &pending, &eeprom)
+            .await
+            .unwrap();
+
+        assert_eq!(symbols.iter(true).count(), 8);
+        assert_eq!(symbols.iter(false).count(), 0);
+        assert_eq!(
+            symbols.find(true, "Channel 3.Output"),
+            Some(PdiSymbol {
+                name: "Output",
+                pdo_name: "Channel 3",
+                index: 0x7020,
+                sub_index: 1,
+                bit_offset: 2,
+                bit_len: 1,
+                data_type: PrimitiveDataType::Bool,
+            })
+        );
+        // Unqualified names match the first entry
+        assert_eq!(
+            symbols.find(true, "Output").map(|s| s.pdo_name),
+            Some("Channel 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn too_many_symbols() {
+        let eeprom = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/ek1100.hex"
+        )));
+
+        let mut pending = PendingSymbols::new(&eeprom, PdoDirection::MasterRead, false).await;
+
+        for i in 0..MAX_PDI_SYMBOLS as u8 {
+            pending
+                .push(0x1a00, 0x6000, i, 1, u32::from(i), Some("Input"), None)
+                .unwrap();
+        }
+
+        // Unnamed entries can't be looked up so don't take up space
+        assert_eq!(pending.push(0x1a00, 0x6010, 1, 1, 32, None, None), Ok(()));
+        assert_eq!(
+            pending.push(0x1a00, 0x6010, 2, 1, 33, Some("Input"), None),
+            Err(Error::Capacity(Item::PdoEntry))
+        );
+
+        let mut symbols = PdiSymbols::default();
+
+        symbols
+            .replace(PdoDirection::MasterRead, &pending, &eeprom)
+            .await
+            .unwrap();
+
+        // No room left for outputs
+        let mut pending = PendingSymbols::new(&eeprom, PdoDirection::MasterWrite, false).await;
+
+        pending
+            .push(0x1600, 0x7000, 1, 1, 0, Some("Output"), None)
+            .unwrap();
+
+        assert_eq!(
+            symbols
+                .replace(PdoDirection::MasterWrite, &pending, &eeprom)
+                .await,
+            Err(Error::Capacity(Item::PdoEntry))
+        );
+    }
+
+    #[tokio::test]
+    async fn static_names() {
+        let eeprom = SubDeviceEeprom::new(EepromFile::new(include_bytes!(
+            "../../dumps/eeprom/ek1100.hex"
+        )));
+
+        let mut pending = PendingSymbols::new(&eeprom, PdoDirection::MasterRead, true).await;
+
+        pending
+            .push(
+                0x1a00,
+                0x6000,
+                0x01,
+                16,
+                0,
+                Some("Status"),
+                Some("Channel 1"),
+            )
+            .unwrap();
+        pending
+            .push(
+                0x1a00,
+                0x6000,
+                0x11,
+                16,
+                16,
+                Some("Value"),
+                Some("Channel 1"),
+            )
+            .unwrap();
+        pending
+            .push(0x1a01, 0x0000, 0x00, 8, 32, None, None)
+            .unwrap();
+        pending
+            .push(
+                0x1a01,
+                0x6010,
+                0x01,
+                16,
+                40,
+                Some("Status"),
+                Some("Channel 2"),
+            )
+            .unwrap();
+
+        let mut symbols = PdiSymbols::default();
+
+        symbols
+            .replace(PdoDirection::MasterRead, &pending, &eeprom)
+            .await
+            .unwrap();
+
+        assert_eq!(symbols.iter(false).count(), 3, "padding should be skipped");
+        assert_eq!(symbols.find(false, "Value").map(|s| s.bit_offset), Some(16));
+        assert_eq!(symbols.find(false, "Status").map(|s| s.bit_offset), Some(0));
+        assert_eq!(
+            symbols
+                .find(false, "Channel 2.Status")
+                .map(|s| s.bit_offset),
+            Some(40)
+        );
+        assert_eq!(symbols.find(false, "Channel 3.Status"), None);
+        assert_eq!(symbols.find(true, "Value"), None);
+        // "Status" and "Channel 1" are only stored once
+        assert_eq!(symbols.names.as_str(), "StatusChannel 1ValueChannel 2");
+
+        // Replacing inputs with outputs leaves existing inputs alone
+        let mut pending = PendingSymbols::new(&eeprom, PdoDirection::MasterWrite, true).await;
+
+        pending
+            .push(
+                0x1600,
+                0x7000,
+                0x01,
+                1,
+                0,
+                Some("Output"),
+                Some("Channel 1"),
+            )
+            .unwrap();
+
+        symbols
+            .replace(PdoDirection::MasterWrite, &pending, &eeprom)
+            .await
+            .unwrap();
+
+        assert_eq!(symbols.iter(false).count(), 3);
+        assert_eq!(symbols.iter(true).count(), 1);
+
+        // And replacing inputs again removes the old ones
+        let pending = PendingSymbols::new(&eeprom, PdoDirection::MasterRead, true).await;
+
+        symbols
+            .replace(PdoDirection::MasterRead, &pending, &eeprom)
+            .await
+            .unwrap();
+
+        assert_eq!(symbols.iter(false).count(), 0);
+        assert_eq!(
+            symbols.find(true, "Channel 1.Output").map(|s| s.bit_len),
+            Some(1)
+        );
+        assert_eq!(symbols.names.as_str(), "OutputChannel 1");
+    }
+}
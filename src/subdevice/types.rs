@@ -2,8 +2,9 @@ use crate::{eeprom::types::MailboxProtocols, pdi::PdiSegment};
 use core::fmt::{self, Debug};
 
 /// SubDevice identity information (vendor ID, product ID, etc).
-#[derive(Default, Copy, Clone, PartialEq, ethercrab_wire::EtherCrabWireRead)]
+#[derive(Default, Copy, Clone, PartialEq, Eq, Hash, ethercrab_wire::EtherCrabWireReadWrite)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[wire(bytes = 16)]
 #[doc(alias = "SlaveIdentity")]
 pub struct SubDeviceIdentity {
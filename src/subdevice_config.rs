@@ -0,0 +1,417 @@
+//! Per-SubDevice configuration, selected by SubDevice identity.
+
+use crate::{PdoMapping, SubDevice, SubDeviceIdentity, fmt};
+
+#[cfg(feature = "std")]
+use crate::{SubDeviceRef, error::Error};
+#[cfg(feature = "std")]
+use core::{future::Future, pin::Pin};
+
+/// An async configuration function for a SubDevice.
+///
+/// The function is called with the SubDevice in PRE-OP, just before the PDI is configured during
+/// the PRE-OP -> SAFE-OP transition.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::{error::Error, SubDevice, SubDeviceRef};
+/// use std::{future::Future, pin::Pin};
+///
+/// fn configure_el3004<'a>(
+///     subdevice: SubDeviceRef<'a, &'a mut SubDevice>,
+/// ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + 'a>> {
+///     Box::pin(async move {
+///         subdevice.sdo_write(0x1c12, 0, 0u8).await?;
+///
+///         Ok(())
+///     })
+/// }
+/// # let _: ethercrab::ConfigCallback = configure_el3004;
+/// ```
+#[cfg(feature = "std")]
+pub type ConfigCallback =
+    for<'a> fn(
+        SubDeviceRef<'a, &'a mut SubDevice>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send + 'a>>;
+
+/// A pattern matched against a SubDevice's [`SubDeviceIdentity`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IdentityPattern {
+    /// Vendor ID.
+    pub vendor_id: u32,
+
+    /// Product ID.
+    pub product_id: u32,
+
+    /// Product revision to match, after applying [`revision_mask`](IdentityPattern::revision_mask).
+    pub revision: u32,
+
+    /// Bits of the SubDevice revision to compare against [`revision`](IdentityPattern::revision).
+    ///
+    /// A mask of `0` matches any revision. A mask of `0xffff_0000` matches only the major revision
+    /// of most Beckhoff devices.
+    pub revision_mask: u32,
+
+    /// Explicit Device ID to match, or `None` to match any.
+    ///
+    /// See [`SubDeviceRef::explicit_device_id`](crate::SubDeviceRef::explicit_device_id).
+    pub explicit_device_id: Option<u16>,
+}
+
+impl IdentityPattern {
+    /// Match any revision of the given vendor and product.
+    pub const fn new(vendor_id: u32, product_id: u32) -> Self {
+        Self {
+            vendor_id,
+            product_id,
+            revision: 0,
+            revision_mask: 0,
+            explicit_device_id: None,
+        }
+    }
+
+    /// Only match revisions where `subdevice_revision & mask == revision & mask`.
+    pub const fn with_revision(self, revision: u32, mask: u32) -> Self {
+        Self {
+            revision,
+            revision_mask: mask,
+            ..self
+        }
+    }
+
+    /// Only match SubDevices with the given Explicit Device ID, e.g. to tell apart SubDevices with
+    /// the same identity by their rotary switch settings.
+    ///
+    /// Explicit Device IDs are read during [`MainDevice::init`](crate::MainDevice::init) when
+    /// any registered pattern uses one.
+    pub const fn with_explicit_device_id(self, id: u16) -> Self {
+        Self {
+            explicit_device_id: Some(id),
+            ..self
+        }
+    }
+
+    /// Whether the given SubDevice identity and Explicit Device ID match this pattern.
+    pub const fn matches(
+        &self,
+        identity: &SubDeviceIdentity,
+        explicit_device_id: Option<u16>,
+    ) -> bool {
+        let id_matches = match (self.explicit_device_id, explicit_device_id) {
+            (None, _) => true,
+            (Some(expected), Some(id)) => expected == id,
+            (Some(_), None) => false,
+        };
+
+        self.vendor_id == identity.vendor_id
+            && self.product_id == identity.product_id
+            && (self.revision & self.revision_mask) == (identity.revision & self.revision_mask)
+            && id_matches
+    }
+}
+
+/// A value to write to a SubDevice SDO.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SdoValue {
+    /// A single byte.
+    U8(u8),
+    /// A 16 bit value.
+    U16(u16),
+    /// A 32 bit value.
+    U32(u32),
+    /// A 64 bit value.
+    U64(u64),
+    /// Raw bytes, written as-is.
+    Bytes(&'static [u8]),
+}
+
+/// A single SDO write, performed as part of a [`SubDeviceConfig`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SdoWrite {
+    /// SDO index, e.g. `0x8000`.
+    pub index: u16,
+
+    /// SDO sub-index.
+    pub sub_index: u8,
+
+    /// Value to write.
+    pub value: SdoValue,
+}
+
+impl SdoWrite {
+    /// Create a new SDO write.
+    pub const fn new(index: u16, sub_index: u8, value: SdoValue) -> Self {
+        Self {
+            index,
+            sub_index,
+            value,
+        }
+    }
+}
+
+/// Declarative configuration for a SubDevice.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SubDeviceConfig {
+    /// SDO writes to perform in order, just before the PDI is configured.
+    pub sdo_writes: &'static [SdoWrite],
+
+    /// PDO mapping, applied to the SubDevice as if
+    /// [`SubDevice::set_pdo_mapping`](crate::SubDevice::set_pdo_mapping) were called after init.
+    pub pdo_mapping: PdoMapping,
+}
+
+impl SubDeviceConfig {
+    /// Create an empty configuration.
+    pub const fn new() -> Self {
+        Self {
+            sdo_writes: &[],
+            pdo_mapping: PdoMapping::new(),
+        }
+    }
+
+    /// Set the SDO writes to perform.
+    pub const fn with_sdo_writes(self, sdo_writes: &'static [SdoWrite]) -> Self {
+        Self { sdo_writes, ..self }
+    }
+
+    /// Set the PDO mapping to apply.
+    pub const fn with_pdo_mapping(self, pdo_mapping: PdoMapping) -> Self {
+        Self {
+            pdo_mapping,
+            ..self
+        }
+    }
+}
+
+/// How to configure a SubDevice matched by a [`SubDeviceConfigEntry`].
+#[derive(Debug, Copy, Clone)]
+pub enum ConfigAction {
+    /// Apply a declarative configuration.
+    Static(SubDeviceConfig),
+
+    /// Call an async function.
+    #[cfg(feature = "std")]
+    Callback(ConfigCallback),
+}
+
+/// A single entry in a [`SubDeviceConfigs`] registry.
+#[derive(Debug, Copy, Clone)]
+pub struct SubDeviceConfigEntry {
+    /// SubDevices to apply this entry to.
+    pub identity: IdentityPattern,
+
+    /// Configuration to apply.
+    pub action: ConfigAction,
+}
+
+impl SubDeviceConfigEntry {
+    /// Apply a declarative configuration to SubDevices matching `identity`.
+    pub const fn new(identity: IdentityPattern, config: SubDeviceConfig) -> Self {
+        Self {
+            identity,
+            action: ConfigAction::Static(config),
+        }
+    }
+
+    /// Call an async function to configure SubDevices matching `identity`.
+    #[cfg(feature = "std")]
+    pub const fn callback(identity: IdentityPattern, callback: ConfigCallback) -> Self {
+        Self {
+            identity,
+            action: ConfigAction::Callback(callback),
+        }
+    }
+}
+
+/// A registry of SubDevice configurations, keyed by SubDevice identity.
+///
+/// When passed to [`MainDevice::with_subdevice_configs`](crate::MainDevice::with_subdevice_configs),
+/// matching configurations are applied automatically:
+///
+/// - During [`MainDevice::init`](crate::MainDevice::init), the
+///   [`pdo_mapping`](SubDeviceConfig::pdo_mapping) of a declarative configuration is set on each
+///   matching SubDevice.
+/// - During the PRE-OP -> SAFE-OP transition of a group, SDO writes are performed and callbacks are
+///   called for each matching SubDevice, before the group's PDI is configured.
+///
+/// The first matching entry is used, so more specific patterns should be given first.
+///
+/// # Examples
+///
+/// ```rust
+/// use ethercrab::{
+///     IdentityPattern, MainDevice, MainDeviceConfig, PduStorage, SdoValue, SdoWrite, SubDevice,
+///     SubDeviceConfig, SubDeviceConfigEntry, SubDeviceConfigs, Timeouts,
+/// };
+///
+/// static EL3004_SDOS: &[SdoWrite] = &[
+///     // Enable filter on channel 1
+///     SdoWrite::new(0x8000, 0x06, SdoValue::U8(1)),
+///     SdoWrite::new(0x8000, 0x15, SdoValue::U16(0)),
+/// ];
+///
+/// fn unconfigured(subdevice: &SubDevice) {
+///     log::warn!("No configuration for {}", subdevice.name());
+/// }
+///
+/// static CONFIGS: SubDeviceConfigs = SubDeviceConfigs::new(&[
+///     // EL3004, any revision
+///     SubDeviceConfigEntry::new(
+///         IdentityPattern::new(0x0000_0002, 0x0bbc_3052),
+///         SubDeviceConfig::new().with_sdo_writes(EL3004_SDOS),
+///     ),
+///     // EK1100 couplers don't need any config
+///     SubDeviceConfigEntry::new(
+///         IdentityPattern::new(0x0000_0002, 0x044c_2c52),
+///         SubDeviceConfig::new(),
+///     ),
+/// ])
+/// .with_unconfigured_hook(unconfigured);
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default())
+///     .with_subdevice_configs(&CONFIGS);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SubDeviceConfigs {
+    entries: &'static [SubDeviceConfigEntry],
+    on_unconfigured: Option<fn(&SubDevice)>,
+}
+
+impl SubDeviceConfigs {
+    /// Create a new registry from a list of entries.
+    pub const fn new(entries: &'static [SubDeviceConfigEntry]) -> Self {
+        Self {
+            entries,
+            on_unconfigured: None,
+        }
+    }
+
+    /// Set a function to call during [`MainDevice::init`](crate::MainDevice::init) for each
+    /// SubDevice that does not match any entry in this registry.
+    ///
+    /// If no hook is set, a warning is logged instead.
+    pub const fn with_unconfigured_hook(self, hook: fn(&SubDevice)) -> Self {
+        Self {
+            on_unconfigured: Some(hook),
+            ..self
+        }
+    }
+
+    /// Find the first entry matching the given identity and Explicit Device ID.
+    pub fn find(
+        &self,
+        identity: &SubDeviceIdentity,
+        explicit_device_id: Option<u16>,
+    ) -> Option<&SubDeviceConfigEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.identity.matches(identity, explicit_device_id))
+    }
+
+    /// Whether any entry matches on Explicit Device ID.
+    pub(crate) fn uses_explicit_device_id(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.identity.explicit_device_id.is_some())
+    }
+
+    /// Call the unconfigured hook, or log a warning if none is set.
+    pub(crate) fn unconfigured(&self, subdevice: &SubDevice) {
+        match self.on_unconfigured {
+            Some(hook) => hook(subdevice),
+            None => fmt::warn!(
+                "SubDevice {:#06x} {} ({}) has no registered configuration",
+                subdevice.configured_address(),
+                subdevice.name(),
+                subdevice.identity()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(revision: u32) -> SubDeviceIdentity {
+        SubDeviceIdentity {
+            vendor_id: 0x2,
+            product_id: 0x0bbc_3052,
+            revision,
+            serial: 0,
+        }
+    }
+
+    #[test]
+    fn pattern_revision_mask() {
+        let any = IdentityPattern::new(0x2, 0x0bbc_3052);
+        let major = any.with_revision(0x0014_0000, 0xffff_0000);
+
+        assert!(any.matches(&identity(0x0014_0000), None));
+        assert!(any.matches(&identity(0x0015_0000), None));
+        assert!(major.matches(&identity(0x0014_0003), None));
+        assert!(!major.matches(&identity(0x0015_0000), None));
+        assert!(!IdentityPattern::new(0x2, 0x044c_2c52).matches(&identity(0), None));
+    }
+
+    #[test]
+    fn pattern_explicit_device_id() {
+        let any = IdentityPattern::new(0x2, 0x0bbc_3052);
+        let switch_3 = any.with_explicit_device_id(3);
+
+        assert!(any.matches(&identity(0), Some(3)));
+        assert!(any.matches(&identity(0), None));
+        assert!(switch_3.matches(&identity(0), Some(3)));
+        assert!(!switch_3.matches(&identity(0), Some(4)));
+        assert!(!switch_3.matches(&identity(0), None));
+    }
+
+    #[test]
+    fn first_match_wins() {
+        static SDOS: &[SdoWrite] = &[SdoWrite::new(0x8000, 0x06, SdoValue::U8(1))];
+
+        static CONFIGS: SubDeviceConfigs = SubDeviceConfigs::new(&[
+            SubDeviceConfigEntry::new(
+                IdentityPattern::new(0x2, 0x0bbc_3052).with_revision(0x0014_0000, 0xffff_0000),
+                SubDeviceConfig::new().with_sdo_writes(SDOS),
+            ),
+            SubDeviceConfigEntry::new(
+                IdentityPattern::new(0x2, 0x0bbc_3052),
+                SubDeviceConfig::new(),
+            ),
+        ]);
+
+        let sdo_writes = |revision| {
+            CONFIGS
+                .find(&identity(revision), None)
+                .map(|entry| match entry.action {
+                    ConfigAction::Static(config) => config.sdo_writes.len(),
+                    #[cfg(feature = "std")]
+                    ConfigAction::Callback(_) => unreachable!(),
+                })
+        };
+
+        assert_eq!(sdo_writes(0x0014_0001), Some(1));
+        assert_eq!(sdo_writes(0x0013_0000), Some(0));
+        assert!(
+            CONFIGS
+                .find(
+                    &SubDeviceIdentity {
+                        vendor_id: 0x2,
+                        ..SubDeviceIdentity::default()
+                    },
+                    None
+                )
+                .is_none()
+        );
+    }
+}
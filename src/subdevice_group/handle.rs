@@ -1,5 +1,8 @@
+use super::{GroupDc, GroupState, HasDc, PdiLayout, configure_sync0};
 use crate::{
-    GroupId, MainDevice, SubDevice, SubDeviceGroup, SubDeviceRef, error::Error, fmt, pdi::PdiOffset,
+    DcSync, GroupId, InitEvent, MainDevice, SubDevice, SubDeviceGroup, SubDeviceRef,
+    SubDeviceState, error::Error, fmt, pdi::PdiOffset, register::RegisterAddress,
+    subdevice::configuration::PdoDirection,
 };
 use lock_api::RawRwLock;
 
@@ -16,13 +19,20 @@ pub trait SubDeviceGroupHandle: Sync {
 
     /// Get a reference to the group with const generic params erased.
     fn as_ref(&self) -> SubDeviceGroupRef<'_>;
+
+    /// Get a mutable reference to the group with const generic params erased.
+    fn as_mut(&mut self) -> SubDeviceGroupRef<'_>;
+
+    /// Get the group's SubDevices.
+    fn subdevices(&self) -> &[SubDevice];
 }
 
 #[sealed::sealed]
-impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock + Sync, S> SubDeviceGroupHandle
-    for SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, S>
+impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock + Sync, S, DC>
+    SubDeviceGroupHandle for SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, S, DC>
 where
-    S: Sync,
+    S: GroupState + Sync,
+    DC: GroupDc + Sync,
 {
     fn id(&self) -> GroupId {
         self.id
@@ -36,22 +46,47 @@ where
     fn as_ref(&self) -> SubDeviceGroupRef<'_> {
         SubDeviceGroupRef {
             max_pdi_len: MAX_PDI,
+            state: S::STATE,
+            has_pdi: S::HAS_PDI,
+            dc: self.dc_conf.dc(),
             inner: {
                 let inner = unsafe { fmt::unwrap_opt!(self.inner.get().as_mut()) };
 
                 GroupInnerRef {
                     subdevices: &mut inner.subdevices,
                     pdi_start: &mut inner.pdi_start,
+                    pdi_layout: inner.pdi_layout,
                 }
             },
         }
     }
+
+    fn as_mut(&mut self) -> SubDeviceGroupRef<'_> {
+        let inner = self.inner.get_mut();
+
+        SubDeviceGroupRef {
+            max_pdi_len: MAX_PDI,
+            state: S::STATE,
+            has_pdi: S::HAS_PDI,
+            dc: self.dc_conf.dc(),
+            inner: GroupInnerRef {
+                subdevices: &mut inner.subdevices,
+                pdi_start: &mut inner.pdi_start,
+                pdi_layout: inner.pdi_layout,
+            },
+        }
+    }
+
+    fn subdevices(&self) -> &[SubDevice] {
+        &self.inner().subdevices
+    }
 }
 
 #[derive(Debug)]
 struct GroupInnerRef<'a> {
     subdevices: &'a mut [SubDevice],
     pdi_start: &'a mut PdiOffset,
+    pdi_layout: PdiLayout,
 }
 
 /// A reference to a [`SubDeviceGroup`](crate::SubDeviceGroup) returned by the closure passed to
@@ -60,17 +95,26 @@ struct GroupInnerRef<'a> {
 pub struct SubDeviceGroupRef<'a> {
     /// Maximum PDI length in bytes.
     max_pdi_len: usize,
+    /// The state the group's SubDevices are in.
+    state: SubDeviceState,
+    /// Whether the group's FMMUs have been configured.
+    has_pdi: bool,
+    dc: Option<HasDc>,
     inner: GroupInnerRef<'a>,
 }
 
 impl SubDeviceGroupRef<'_> {
     /// Initialise all SubDevices in the group and place them in PRE-OP.
+    ///
+    /// If `configure_mailboxes` is `false`, the SubDevices must already have been transitioned
+    /// into PRE-OP.
     // Clippy: shush
     #[allow(clippy::wrong_self_convention)]
     pub(crate) async fn into_pre_op<'sto>(
         &mut self,
         pdi_position: PdiOffset,
         maindevice: &'sto MainDevice<'sto>,
+        configure_mailboxes: bool,
     ) -> Result<PdiOffset, Error> {
         let inner = &mut self.inner;
 
@@ -89,9 +133,133 @@ impl SubDeviceGroupRef<'_> {
                 SubDeviceRef::new(maindevice, subdevice.configured_address(), subdevice);
 
             // TODO: Move PRE-OP transition out of this so we can do it for the group just once
-            subdevice_config.configure_mailboxes().await?;
+            if configure_mailboxes {
+                subdevice_config.configure_mailboxes().await?;
+
+                maindevice.emit(InitEvent::StateChanged {
+                    configured_address: subdevice_config.configured_address(),
+                    state: SubDeviceState::PreOp,
+                });
+            }
+
+            subdevice_config.apply_registered_pdo_mapping();
         }
 
         Ok(pdi_position.increment(self.max_pdi_len as u16))
     }
+
+    /// The state the group's SubDevices are in.
+    pub(crate) fn state(&self) -> SubDeviceState {
+        self.state
+    }
+
+    /// The group's SubDevices.
+    pub(crate) fn subdevices(&mut self) -> &mut [SubDevice] {
+        self.inner.subdevices
+    }
+
+    /// Bring a SubDevice that was reset, e.g. by a power cycle or cable break, back to the state
+    /// of the rest of the group.
+    ///
+    /// The SubDevice must already have its configured address and DC offsets restored.
+    pub(crate) async fn restore_subdevice(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+        index: usize,
+    ) -> Result<(), Error> {
+        let group_start = self.inner.pdi_start.start_address;
+        let layout = self.inner.pdi_layout;
+
+        let subdevice = self
+            .inner
+            .subdevices
+            .get_mut(index)
+            .ok_or(Error::NotFound {
+                item: crate::error::Item::SubDevice,
+                index: Some(index),
+            })?;
+
+        let configured_address = subdevice.configured_address();
+
+        fmt::debug!(
+            "Restoring SubDevice {:#06x} {} to {}",
+            configured_address,
+            subdevice.name(),
+            self.state
+        );
+
+        let mut subdevice_ref = SubDeviceRef::new(maindevice, configured_address, subdevice);
+
+        // A SubDevice that dropped out of its state with an error ignores state requests until
+        // the error is acknowledged
+        subdevice_ref.acknowledge_error().await?;
+
+        subdevice_ref
+            .request_subdevice_state(SubDeviceState::Init)
+            .await?;
+
+        // A SubDevice that kept power still has its previous FMMU and SM configuration
+        subdevice_ref.clear_fmmus_and_sync_managers().await?;
+
+        if self.state == SubDeviceState::Init {
+            return Ok(());
+        }
+
+        subdevice_ref.configure_mailboxes().await?;
+        subdevice_ref.apply_registered_config().await?;
+
+        if self.has_pdi {
+            let io = subdevice_ref.io_segments().clone();
+
+            for (direction, segment) in [
+                (PdoDirection::MasterRead, io.input),
+                (PdoDirection::MasterWrite, io.output),
+            ] {
+                subdevice_ref
+                    .configure_fmmus(
+                        PdiOffset {
+                            start_address: group_start + segment.bytes.start as u32,
+                            start_bit: segment.bit_offset,
+                        },
+                        group_start,
+                        direction,
+                        layout,
+                    )
+                    .await?;
+            }
+        }
+
+        if let Some(dc) = self.dc {
+            let subdevice = SubDeviceRef::new(maindevice, configured_address, &*subdevice_ref);
+
+            if subdevice.dc_support().any() && !matches!(subdevice.dc_sync(), DcSync::Disabled) {
+                let system_time = SubDeviceRef::new(maindevice, dc.reference, ())
+                    .register_read::<u64>(RegisterAddress::DcSystemTime)
+                    .await?;
+
+                configure_sync0(
+                    maindevice,
+                    &subdevice,
+                    system_time,
+                    dc.start_delay,
+                    dc.sync0_period,
+                )
+                .await?;
+            }
+        }
+
+        if matches!(self.state, SubDeviceState::SafeOp | SubDeviceState::Op) {
+            subdevice_ref
+                .request_subdevice_state(SubDeviceState::SafeOp)
+                .await?;
+        }
+
+        if self.state == SubDeviceState::Op {
+            subdevice_ref
+                .request_subdevice_state(SubDeviceState::Op)
+                .await?;
+        }
+
+        Ok(())
+    }
 }
@@ -15,6 +15,7 @@ use crate::{
     al_control::AlControl,
     command::Command,
     error::{DistributedClockError, Error, Item},
+    error_counters::ErrorCounters,
     fmt,
     // lending_lock::LendingLock,
     pdi::PdiOffset,
@@ -107,6 +108,8 @@ pub struct NoDc;
 pub struct HasDc {
     sync0_period: u64,
     sync0_shift: u64,
+    /// Delay before the first SYNC0 pulse, used when reconfiguring recovered SubDevices.
+    start_delay: u64,
     /// Configured address of the DC reference SubDevice.
     reference: u16,
 }
@@ -125,10 +128,99 @@ pub trait IsPreOp {}
 impl IsPreOp for PreOp {}
 impl IsPreOp for PreOpPdi {}
 
+/// Maps a `SubDeviceGroup` typestate to the state its SubDevices are in.
+#[doc(hidden)]
+#[sealed::sealed]
+pub trait GroupState {
+    /// The EtherCAT state of SubDevices in a group in this typestate.
+    const STATE: SubDeviceState;
+
+    /// Whether the group's FMMUs and PDI have been configured.
+    const HAS_PDI: bool;
+}
+
+#[sealed::sealed]
+impl GroupState for Init {
+    const STATE: SubDeviceState = SubDeviceState::Init;
+    const HAS_PDI: bool = false;
+}
+
+#[sealed::sealed]
+impl GroupState for PreOp {
+    const STATE: SubDeviceState = SubDeviceState::PreOp;
+    const HAS_PDI: bool = false;
+}
+
+#[sealed::sealed]
+impl GroupState for PreOpPdi {
+    const STATE: SubDeviceState = SubDeviceState::PreOp;
+    const HAS_PDI: bool = true;
+}
+
+#[sealed::sealed]
+impl GroupState for SafeOp {
+    const STATE: SubDeviceState = SubDeviceState::SafeOp;
+    const HAS_PDI: bool = true;
+}
+
+#[sealed::sealed]
+impl GroupState for Op {
+    const STATE: SubDeviceState = SubDeviceState::Op;
+    const HAS_PDI: bool = true;
+}
+
+/// Access to a `SubDeviceGroup`'s Distributed Clock configuration, if it has one.
+#[doc(hidden)]
+#[sealed::sealed]
+pub trait GroupDc {
+    /// Get the group's DC configuration.
+    fn dc(&self) -> Option<HasDc>;
+}
+
+#[sealed::sealed]
+impl GroupDc for NoDc {
+    fn dc(&self) -> Option<HasDc> {
+        None
+    }
+}
+
+#[sealed::sealed]
+impl GroupDc for HasDc {
+    fn dc(&self) -> Option<HasDc> {
+        Some(*self)
+    }
+}
+
 #[derive(Default)]
 struct GroupInner<const MAX_SUBDEVICES: usize> {
     subdevices: heapless::Vec<SubDevice, MAX_SUBDEVICES>,
     pdi_start: PdiOffset,
+    pdi_layout: PdiLayout,
+}
+
+/// How SubDevice process data is laid out in a group's PDI.
+///
+/// Set with [`SubDeviceGroup::set_pdi_layout`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PdiLayout {
+    /// Every SubDevice's inputs and outputs start on a new byte. A 4 bit digital terminal will
+    /// occupy a whole byte of the PDI.
+    #[default]
+    ByteAligned,
+
+    /// SubDevice inputs and outputs are packed together with no padding, using the bit-oriented
+    /// start and end fields of each FMMU.
+    ///
+    /// This can noticeably reduce the size of the PDI for groups with many small digital IO
+    /// SubDevices. Not all ESCs support bitwise FMMU mapping, so check the datasheets of the
+    /// SubDevices in the group before enabling this mode.
+    ///
+    /// Because a SubDevice's data may start or end part way through a byte, the raw byte slices
+    /// returned by e.g. [`SubDeviceRef::inputs_raw`] may include bits belonging to neighbouring
+    /// SubDevices. Use the bit-aware accessors such as [`SubDeviceRef::input_bit`],
+    /// [`SubDeviceRef::inputs_as`] or [`SubDeviceRef::input`] instead.
+    BitPacked,
 }
 
 const CYCLIC_OP_ENABLE: u8 = 0b0000_0001;
@@ -174,6 +266,25 @@ pub struct CycleInfo {
 ///
 /// Groups are created during EtherCrab initialisation, and are the only way to access individual
 /// SubDevice PDI sections.
+///
+/// # Typestate
+///
+/// The `S` type parameter records the state the group was last driven to, e.g. [`SafeOp`] after
+/// [`into_safe_op`](SubDeviceGroup::into_safe_op) or [`Op`] after
+/// [`into_op`](SubDeviceGroup::into_op). Group transitions always move every SubDevice in the
+/// group together, and PDI methods are only available in typestates where the group's FMMUs and
+/// sync managers have been configured.
+///
+/// The typestate is not a live guarantee: a SubDevice can leave the group's state by itself, for
+/// example dropping to `SAFE-OP + ERROR` after a watchdog timeout. Such a SubDevice can be
+/// brought back on its own with [`SubDeviceRef::request_state`] and
+/// [`SubDeviceRef::acknowledge_error`], without cycling the rest of the group. When doing so:
+///
+/// - Only request states up to and including the group's current typestate. Driving one
+///   SubDevice past its group leaves it in a state the group's methods do not expect.
+/// - Do not request INIT for a SubDevice in a group with a PDI. Leaving PRE-OP discards the
+///   SubDevice's mailbox and process data configuration, which is only written during group
+///   initialisation.
 #[doc(alias = "SlaveGroup")]
 pub struct SubDeviceGroup<
     const MAX_SUBDEVICES: usize,
@@ -208,6 +319,13 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, DC>
             inner.pdi_start.start_address
         );
 
+        // Apply any registered configuration before PDOs are read to size the PDI
+        for subdevice in inner.subdevices.iter_mut() {
+            SubDeviceRef::new(maindevice, subdevice.configured_address(), subdevice)
+                .apply_registered_config()
+                .await?;
+        }
+
         // Configure master read PDI mappings in the first section of the PDI
         for subdevice in inner.subdevices.iter_mut() {
             // We're in PRE-OP at this point
@@ -216,10 +334,15 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, DC>
                     pdi_position,
                     inner.pdi_start.start_address,
                     PdoDirection::MasterRead,
+                    inner.pdi_layout,
                 )
                 .await?;
         }
 
+        // Outputs always start on a new byte so the inputs can be copied back from the received
+        // PDI without clobbering any outputs.
+        pdi_position = pdi_position.align_to_byte();
+
         self.read_pdi_len = (pdi_position.start_address - inner.pdi_start.start_address) as usize;
 
         fmt::debug!("SubDevice mailboxes configured and init hooks called");
@@ -238,12 +361,15 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, DC>
                     pdi_position,
                     inner.pdi_start.start_address,
                     PdoDirection::MasterWrite,
+                    inner.pdi_layout,
                 )
                 .await?;
         }
 
         fmt::debug!("SubDevice FMMUs configured for group. Able to move to SAFE-OP");
 
+        let pdi_position = pdi_position.align_to_byte();
+
         self.pdi_len = (pdi_position.start_address - inner.pdi_start.start_address) as usize;
 
         fmt::debug!(
@@ -263,6 +389,35 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, DC>
         Ok(())
     }
 
+    /// Set how SubDevice process data is laid out in this group's PDI.
+    ///
+    /// This must be called before the group's PDI is configured, e.g. by
+    /// [`into_safe_op`](SubDeviceGroup::into_safe_op). Defaults to [`PdiLayout::ByteAligned`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use ethercrab::{
+    /// #     error::Error, std::ethercat_now, subdevice_group::PdiLayout, MainDevice,
+    /// #     MainDeviceConfig, PduStorage, Timeouts,
+    /// # };
+    /// # async fn case() -> Result<(), Error> {
+    /// # static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+    /// # let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+    /// # let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+    /// let mut group = maindevice.init_single_group::<64, 32>(ethercat_now).await?;
+    ///
+    /// // Pack many small digital IO terminals tightly into the PDI
+    /// group.set_pdi_layout(PdiLayout::BitPacked);
+    ///
+    /// let group = group.into_op(&maindevice).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_pdi_layout(&mut self, layout: PdiLayout) {
+        self.inner.get_mut().pdi_layout = layout;
+    }
+
     /// Borrow an individual SubDevice.
     #[deny(clippy::panic)]
     #[doc(alias = "slave")]
@@ -427,53 +582,14 @@ where
         let first_pulse_delay = u64::from(u32::try_from(start_delay.as_nanos())?);
 
         for subdevice in dc_devices {
-            fmt::debug!(
-                "--> Configuring SubDevice {:#06x} {} DC mode {}",
-                subdevice.configured_address(),
-                subdevice.name(),
-                subdevice.dc_sync()
-            );
-
-            // Disable cyclic op, ignore WKC
-            subdevice
-                .write(RegisterAddress::DcSyncActive)
-                .ignore_wkc()
-                .send(maindevice, 0u8)
-                .await?;
-
-            // Round first pulse time to a whole number of cycles
-            let start_time = (system_time + first_pulse_delay) / sync0_period * sync0_period;
-
-            fmt::debug!("--> Computed DC sync start time: {}", start_time);
-
-            subdevice
-                .write(RegisterAddress::DcSyncStartTime)
-                .send(maindevice, start_time)
-                .await?;
-
-            // Cycle time in nanoseconds
-            subdevice
-                .write(RegisterAddress::DcSync0CycleTime)
-                .send(maindevice, sync0_period)
-                .await?;
-
-            let flags = if let DcSync::Sync01 { sync1_period } = subdevice.dc_sync() {
-                let sync1_period = u64::try_from(sync1_period.as_nanos())?;
-
-                subdevice
-                    .write(RegisterAddress::DcSync1CycleTime)
-                    .send(maindevice, sync1_period)
-                    .await?;
-
-                SYNC1_ACTIVATE | SYNC0_ACTIVATE | CYCLIC_OP_ENABLE
-            } else {
-                SYNC0_ACTIVATE | CYCLIC_OP_ENABLE
-            };
-
-            subdevice
-                .write(RegisterAddress::DcSyncActive)
-                .send(maindevice, flags)
-                .await?;
+            configure_sync0(
+                maindevice,
+                &subdevice,
+                system_time,
+                first_pulse_delay,
+                sync0_period,
+            )
+            .await?;
         }
 
         Ok(SubDeviceGroup {
@@ -485,6 +601,7 @@ where
             dc_conf: HasDc {
                 sync0_period: sync0_period,
                 sync0_shift: sync0_shift.as_nanos() as u64,
+                start_delay: first_pulse_delay,
                 reference,
             },
             _state: PhantomData,
@@ -636,6 +753,87 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, S, DC>
         self.inner().subdevices.is_empty()
     }
 
+    /// Read the error counters of every SubDevice in the group, in group order.
+    ///
+    /// As many SubDevices as will fit are read in a single frame, so a typical group is read in
+    /// one network round trip. See [`SubDeviceRef::error_counters`] to read a single SubDevice.
+    pub async fn error_counters(
+        &self,
+        maindevice: &MainDevice<'_>,
+    ) -> Result<heapless::Vec<ErrorCounters, MAX_SUBDEVICES>, Error> {
+        let mut counters = heapless::Vec::new();
+
+        let mut subdevices = self.inner().subdevices.iter();
+
+        loop {
+            let batch = subdevices.clone();
+
+            let mut frame = maindevice.pdu_loop.alloc_frame()?;
+
+            let (rest, num_in_this_frame) = push_error_counter_pdus(subdevices, &mut frame, false)?;
+
+            subdevices = rest;
+
+            if num_in_this_frame == 0 {
+                break;
+            }
+
+            let frame = frame.mark_sendable(
+                &maindevice.pdu_loop,
+                maindevice.timeouts.pdu(),
+                maindevice.config.retry_behaviour.retry_count(),
+            );
+
+            maindevice.pdu_loop.wake_sender();
+
+            let received = frame.await?;
+
+            for (subdevice, pdu) in batch.zip(received.into_pdu_iter()) {
+                let pdu = pdu?.wkc(1)?;
+
+                counters
+                    .push(ErrorCounters::unpack_from_slice(
+                        subdevice.configured_address(),
+                        &pdu,
+                    )?)
+                    .map_err(|_| Error::Capacity(Item::SubDevice))?;
+            }
+        }
+
+        Ok(counters)
+    }
+
+    /// Reset the error counters of every SubDevice in the group to zero.
+    pub async fn reset_error_counters(&self, maindevice: &MainDevice<'_>) -> Result<(), Error> {
+        let mut subdevices = self.inner().subdevices.iter();
+
+        loop {
+            let mut frame = maindevice.pdu_loop.alloc_frame()?;
+
+            let (rest, num_in_this_frame) = push_error_counter_pdus(subdevices, &mut frame, true)?;
+
+            subdevices = rest;
+
+            if num_in_this_frame == 0 {
+                break;
+            }
+
+            let frame = frame.mark_sendable(
+                &maindevice.pdu_loop,
+                maindevice.timeouts.pdu(),
+                maindevice.config.retry_behaviour.retry_count(),
+            );
+
+            maindevice.pdu_loop.wake_sender();
+
+            for pdu in frame.await?.into_pdu_iter() {
+                pdu?.wkc(1)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check if all SubDevices in the group are the given desired state.
     async fn is_state(
         &self,
@@ -744,6 +942,65 @@ impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, S, DC>
     }
 }
 
+/// Configure and activate SYNC0 (and SYNC1 if enabled) for a single SubDevice.
+async fn configure_sync0(
+    maindevice: &MainDevice<'_>,
+    subdevice: &SubDeviceRef<'_, &SubDevice>,
+    system_time: u64,
+    first_pulse_delay: u64,
+    sync0_period: u64,
+) -> Result<(), Error> {
+    fmt::debug!(
+        "--> Configuring SubDevice {:#06x} {} DC mode {}",
+        subdevice.configured_address(),
+        subdevice.name(),
+        subdevice.dc_sync()
+    );
+
+    // Disable cyclic op, ignore WKC
+    subdevice
+        .write(RegisterAddress::DcSyncActive)
+        .ignore_wkc()
+        .send(maindevice, 0u8)
+        .await?;
+
+    // Round first pulse time to a whole number of cycles
+    let start_time = (system_time + first_pulse_delay) / sync0_period * sync0_period;
+
+    fmt::debug!("--> Computed DC sync start time: {}", start_time);
+
+    subdevice
+        .write(RegisterAddress::DcSyncStartTime)
+        .send(maindevice, start_time)
+        .await?;
+
+    // Cycle time in nanoseconds
+    subdevice
+        .write(RegisterAddress::DcSync0CycleTime)
+        .send(maindevice, sync0_period)
+        .await?;
+
+    let flags = if let DcSync::Sync01 { sync1_period } = subdevice.dc_sync() {
+        let sync1_period = u64::try_from(sync1_period.as_nanos())?;
+
+        subdevice
+            .write(RegisterAddress::DcSync1CycleTime)
+            .send(maindevice, sync1_period)
+            .await?;
+
+        SYNC1_ACTIVATE | SYNC0_ACTIVATE | CYCLIC_OP_ENABLE
+    } else {
+        SYNC0_ACTIVATE | CYCLIC_OP_ENABLE
+    };
+
+    subdevice
+        .write(RegisterAddress::DcSyncActive)
+        .send(maindevice, flags)
+        .await?;
+
+    Ok(())
+}
+
 fn push_state_checks<'group, 'sto, I>(
     mut subdevices: I,
     frame: &mut CreatedFrame<'sto>,
@@ -786,6 +1043,51 @@ where
     Ok((subdevices, num_in_this_frame))
 }
 
+/// Push a read or reset of the error counters of each SubDevice into `frame` until it is full.
+fn push_error_counter_pdus<'group, 'sto, I>(
+    mut subdevices: I,
+    frame: &mut CreatedFrame<'sto>,
+    reset: bool,
+) -> Result<(I, usize), Error>
+where
+    I: Iterator<Item = &'group SubDevice>,
+{
+    let mut num_in_this_frame = 0;
+
+    // Same limit as `push_state_checks` to leave PDU indices free for other users.
+    while frame.can_push_pdu_payload(ErrorCounters::PACKED_LEN) && num_in_this_frame <= 128 {
+        let Some(sd) = subdevices.next() else {
+            break;
+        };
+
+        if reset {
+            frame.push_pdu(
+                Command::fpwr(
+                    sd.configured_address(),
+                    RegisterAddress::RxErrorCounterPort0.into(),
+                )
+                .into(),
+                [0u8; ErrorCounters::PACKED_LEN],
+                None,
+            )?;
+        } else {
+            frame.push_pdu(
+                Command::fprd(
+                    sd.configured_address(),
+                    RegisterAddress::RxErrorCounterPort0.into(),
+                )
+                .into(),
+                (),
+                Some(ErrorCounters::PACKED_LEN as u16),
+            )?;
+        }
+
+        num_in_this_frame += 1;
+    }
+
+    Ok((subdevices, num_in_this_frame))
+}
+
 // Methods for any state where a PDI has been configured.
 impl<const MAX_SUBDEVICES: usize, const MAX_PDI: usize, R: RawRwLock, S, DC>
     SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, S, DC>
@@ -1460,6 +1762,7 @@ mod tests {
                 inner: MySyncUnsafeCell::new(GroupInner {
                     subdevices: heapless::Vec::new(),
                     pdi_start: PdiOffset::default(),
+                    pdi_layout: PdiLayout::ByteAligned,
                 }),
                 dc_conf: NoDc,
                 _state: PhantomData,
@@ -1564,6 +1867,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_counter_reads_split_across_frames() {
+        const MAX_FRAMES: usize = 1;
+        const MAX_PDU_DATA: usize = PduStorage::element_size(
+            (ErrorCounters::PACKED_LEN + CreatedFrame::PDU_OVERHEAD_BYTES) * 2,
+        );
+        static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+        crate::test_logger();
+
+        let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+        let mut frame = pdu_loop.alloc_frame().expect("No frame");
+
+        let sds = vec![
+            SubDevice::default(),
+            SubDevice::default(),
+            SubDevice::default(),
+        ];
+
+        let (rest, num_pushed) = push_error_counter_pdus(sds.iter(), &mut frame, false)
+            .expect("Could not push error counter read");
+
+        assert_eq!(num_pushed, 2, "frame should hold two error counter reads");
+        assert_eq!(rest.count(), 1);
+        assert!(
+            !frame.can_push_pdu_payload(ErrorCounters::PACKED_LEN),
+            "frame should be full"
+        );
+    }
+
     // This records the behaviour of a DC setup of the following 16 SubDevices:
     //
     // - EK1100
@@ -1688,11 +2022,13 @@ mod tests {
             pdi_len: 474,
             inner: MySyncUnsafeCell::new(GroupInner {
                 subdevices,
-                pdi_start: PdiOffset { start_address: 0 },
+                pdi_start: PdiOffset::default(),
+                pdi_layout: PdiLayout::ByteAligned,
             }),
             dc_conf: HasDc {
                 sync0_period: 100_000,
                 sync0_shift: 0,
+                start_delay: 0,
                 reference: 0,
             },
             _state: PhantomData::<Op>,
@@ -0,0 +1,452 @@
+//! Automatic recovery of SubDevices that leave OP during process data exchange.
+
+use crate::{
+    AlStatusCode, MainDevice, SubDeviceGroup, SubDeviceState, TxRxResponse,
+    al_control::AlControl,
+    error::{Error, TimeoutError},
+    fmt,
+    register::RegisterAddress,
+    subdevice_group::Op,
+};
+use core::time::Duration;
+use lock_api::RawRwLock;
+
+/// How often a [`FaultSupervisor`] retries bringing a SubDevice back to OP.
+///
+/// The first attempt is made as soon as a fault is detected. After each failed attempt, the delay
+/// before the next one starts at [`initial_delay`](BackoffPolicy::initial_delay) and doubles up to
+/// [`max_delay`](BackoffPolicy::max_delay).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    /// Delay after the first failed attempt.
+    ///
+    /// Defaults to 100ms.
+    pub initial_delay: Duration,
+
+    /// Upper limit of the delay between attempts.
+    ///
+    /// Defaults to 5s.
+    pub max_delay: Duration,
+
+    /// Give up after this many failed attempts, or never give up if `None`.
+    ///
+    /// Defaults to `None`.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            max_attempts: None,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before the next attempt, given the number of failed attempts so far.
+    fn delay(&self, failed_attempts: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(failed_attempts.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// Receives [`SupervisorEvent`]s from a [`FaultSupervisor`].
+///
+/// Events are delivered synchronously from [`FaultSupervisor::check`], so implementations should
+/// return quickly. Closures taking a [`SupervisorEvent`] implement this trait.
+pub trait SupervisorObserver: Sync {
+    /// Called for each event.
+    fn on_event(&self, event: SupervisorEvent);
+}
+
+impl<F> SupervisorObserver for F
+where
+    F: Fn(SupervisorEvent) + Sync,
+{
+    fn on_event(&self, event: SupervisorEvent) {
+        self(event)
+    }
+}
+
+impl core::fmt::Debug for dyn SupervisorObserver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SupervisorObserver")
+    }
+}
+
+/// An event emitted by a [`FaultSupervisor`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum SupervisorEvent {
+    /// A SubDevice left OP.
+    Fault {
+        /// Configured address of the SubDevice.
+        configured_address: u16,
+        /// The state the SubDevice dropped to.
+        state: SubDeviceState,
+        /// The SubDevice's AL status code.
+        code: AlStatusCode,
+    },
+    /// An attempt to bring a SubDevice back to OP was started.
+    RecoveryAttempt {
+        /// Configured address of the SubDevice.
+        configured_address: u16,
+        /// The attempt number, starting at 1.
+        attempt: u32,
+    },
+    /// An attempt to bring a SubDevice back to OP failed.
+    RecoveryFailed {
+        /// Configured address of the SubDevice.
+        configured_address: u16,
+        /// The attempt number, starting at 1.
+        attempt: u32,
+        /// The reason the attempt failed, e.g. [`Error::SubDevice`] with the AL status code if the
+        /// SubDevice refused a state transition.
+        error: Error,
+    },
+    /// A SubDevice is back in OP.
+    Recovered {
+        /// Configured address of the SubDevice.
+        configured_address: u16,
+        /// The number of attempts it took, including the successful one.
+        attempts: u32,
+    },
+    /// The [`max_attempts`](BackoffPolicy::max_attempts) limit was reached. No more attempts will
+    /// be made until the SubDevice is back in OP.
+    GaveUp {
+        /// Configured address of the SubDevice.
+        configured_address: u16,
+        /// The number of failed attempts.
+        attempts: u32,
+    },
+}
+
+/// A state transition requested by the supervisor that has not completed yet.
+#[derive(Debug, Copy, Clone)]
+struct PendingTransition {
+    target: SubDeviceState,
+    /// Time in nanoseconds after which the transition is considered failed.
+    deadline: u64,
+}
+
+/// Recovery progress of a single SubDevice.
+#[derive(Debug, Copy, Clone)]
+struct Fault {
+    /// Number of attempts started so far.
+    attempts: u32,
+    /// Earliest time in nanoseconds the next attempt may be started.
+    next_attempt: u64,
+    pending: Option<PendingTransition>,
+    gave_up: bool,
+}
+
+/// Detects SubDevices that have left OP and brings them back.
+///
+/// Call [`check`](FaultSupervisor::check) after each process data cycle with the cycle's
+/// [`TxRxResponse`]. For each SubDevice in the group that is not in OP, the supervisor:
+///
+/// 1. Reads its AL status code and emits [`SupervisorEvent::Fault`].
+/// 2. Acknowledges the error and requests SAFE-OP, then OP, according to the
+///    [`BackoffPolicy`].
+/// 3. Emits [`SupervisorEvent::Recovered`] once the SubDevice reports OP again.
+///
+/// `check` never waits for a state transition to complete. Transitions are followed through the
+/// states in later responses, so the process data cycle keeps running while SubDevices recover.
+/// A transition that does not complete within the
+/// [`state_transition`](field@crate::Timeouts::state_transition) timeout counts as a failed attempt.
+///
+/// Only SubDevices in PRE-OP or SAFE-OP are recovered. A SubDevice that has dropped to INIT has
+/// lost its configuration and must be reconfigured, e.g. with
+/// [`MainDevice::recover`](crate::MainDevice::recover).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ethercrab::{
+///     FaultSupervisor, MainDevice, MainDeviceConfig, PduStorage, SupervisorEvent, Timeouts,
+///     error::Error, std::ethercat_now,
+/// };
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// fn on_event(event: SupervisorEvent) {
+///     log::warn!("{:?}", event);
+/// }
+///
+/// # async fn case() -> Result<(), Error> {
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+///
+/// let group = maindevice
+///     .init_single_group::<16, 64>(ethercat_now)
+///     .await?
+///     .into_op(&maindevice)
+///     .await?;
+///
+/// let mut supervisor = FaultSupervisor::<16>::new(Default::default()).with_observer(&on_event);
+///
+/// loop {
+///     let response = group.tx_rx(&maindevice).await?;
+///
+///     supervisor
+///         .check(&maindevice, &group, &response, ethercat_now)
+///         .await;
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FaultSupervisor<const MAX_SUBDEVICES: usize> {
+    policy: BackoffPolicy,
+    observer: Option<&'static dyn SupervisorObserver>,
+    faults: [Option<Fault>; MAX_SUBDEVICES],
+}
+
+impl<const MAX_SUBDEVICES: usize> FaultSupervisor<MAX_SUBDEVICES> {
+    /// Create a new supervisor with the given retry policy.
+    pub const fn new(policy: BackoffPolicy) -> Self {
+        Self {
+            policy,
+            observer: None,
+            faults: [None; MAX_SUBDEVICES],
+        }
+    }
+
+    /// Set an observer to receive events as SubDevices fault and recover.
+    pub const fn with_observer(mut self, observer: &'static dyn SupervisorObserver) -> Self {
+        self.observer = Some(observer);
+
+        self
+    }
+
+    /// The retry policy.
+    pub fn policy(&self) -> &BackoffPolicy {
+        &self.policy
+    }
+
+    /// Whether any SubDevice is currently faulted.
+    pub fn has_faults(&self) -> bool {
+        self.faults.iter().any(Option::is_some)
+    }
+
+    fn emit(&self, event: SupervisorEvent) {
+        if let Some(observer) = self.observer {
+            observer.on_event(event);
+        }
+    }
+
+    /// Check the SubDevice states from a process data cycle, and progress recovery of any
+    /// SubDevices not in OP.
+    ///
+    /// `now` returns the current time in nanoseconds, and is used to schedule retries.
+    pub async fn check<const MAX_PDI: usize, R: RawRwLock, DC, T>(
+        &mut self,
+        maindevice: &MainDevice<'_>,
+        group: &SubDeviceGroup<MAX_SUBDEVICES, MAX_PDI, R, Op, DC>,
+        response: &TxRxResponse<MAX_SUBDEVICES, T>,
+        now: impl Fn() -> u64,
+    ) {
+        for (index, state) in response.subdevice_states.iter().copied().enumerate() {
+            let Ok(subdevice) = group.subdevice(maindevice, index) else {
+                continue;
+            };
+
+            let configured_address = subdevice.configured_address();
+
+            if state == SubDeviceState::Op {
+                if let Some(fault) = self.faults[index].take() {
+                    fmt::info!("SubDevice {:#06x} recovered", configured_address);
+
+                    self.emit(SupervisorEvent::Recovered {
+                        configured_address,
+                        attempts: fault.attempts,
+                    });
+                }
+
+                continue;
+            }
+
+            let Some(mut fault) = self.faults[index] else {
+                let code = subdevice
+                    .register_read::<AlStatusCode>(RegisterAddress::AlStatusCode)
+                    .await
+                    .unwrap_or(AlStatusCode::Unknown(0));
+
+                fmt::warn!(
+                    "SubDevice {:#06x} left OP, now in {}: {}",
+                    configured_address,
+                    state,
+                    code
+                );
+
+                self.faults[index] = Some(Fault {
+                    attempts: 0,
+                    next_attempt: now(),
+                    pending: None,
+                    gave_up: false,
+                });
+
+                self.emit(SupervisorEvent::Fault {
+                    configured_address,
+                    state,
+                    code,
+                });
+
+                continue;
+            };
+
+            if fault.gave_up || !matches!(state, SubDeviceState::PreOp | SubDeviceState::SafeOp) {
+                continue;
+            }
+
+            let result = match fault.pending {
+                Some(pending) => {
+                    async {
+                        let status = subdevice
+                            .register_read::<AlControl>(RegisterAddress::AlStatus)
+                            .await?;
+
+                        if status.error {
+                            let code = subdevice
+                                .register_read::<AlStatusCode>(RegisterAddress::AlStatusCode)
+                                .await?;
+
+                            return Err(Error::SubDevice(code));
+                        }
+
+                        if status.state == pending.target {
+                            if pending.target == SubDeviceState::Op {
+                                // Picked up as OP in the next response
+                                return Ok(());
+                            }
+
+                            // SAFE-OP reached, continue to OP
+                            subdevice
+                                .register_write(
+                                    RegisterAddress::AlControl,
+                                    AlControl::new(SubDeviceState::Op),
+                                )
+                                .await?;
+
+                            fault.pending = Some(PendingTransition {
+                                target: SubDeviceState::Op,
+                                deadline: self.deadline(maindevice, &now),
+                            });
+                        } else if now() > pending.deadline {
+                            return Err(Error::Timeout(TimeoutError::StateTransition));
+                        }
+
+                        Ok(())
+                    }
+                    .await
+                }
+                None if now() >= fault.next_attempt => {
+                    fault.attempts += 1;
+
+                    self.emit(SupervisorEvent::RecoveryAttempt {
+                        configured_address,
+                        attempt: fault.attempts,
+                    });
+
+                    // OP can only be requested from SAFE-OP
+                    let target = if state == SubDeviceState::SafeOp {
+                        SubDeviceState::Op
+                    } else {
+                        SubDeviceState::SafeOp
+                    };
+
+                    // Acknowledge any error and request the next state in one write
+                    let result = subdevice
+                        .register_write(
+                            RegisterAddress::AlControl,
+                            AlControl {
+                                error: true,
+                                ..AlControl::new(target)
+                            },
+                        )
+                        .await
+                        .map(|_| ());
+
+                    fault.pending = Some(PendingTransition {
+                        target,
+                        deadline: self.deadline(maindevice, &now),
+                    });
+
+                    result
+                }
+                None => Ok(()),
+            };
+
+            if let Err(error) = result {
+                fmt::warn!(
+                    "SubDevice {:#06x} recovery attempt {} failed: {}",
+                    configured_address,
+                    fault.attempts,
+                    error
+                );
+
+                self.emit(SupervisorEvent::RecoveryFailed {
+                    configured_address,
+                    attempt: fault.attempts,
+                    error,
+                });
+
+                fault.pending = None;
+
+                if self
+                    .policy
+                    .max_attempts
+                    .is_some_and(|max| fault.attempts >= max)
+                {
+                    fault.gave_up = true;
+
+                    self.emit(SupervisorEvent::GaveUp {
+                        configured_address,
+                        attempts: fault.attempts,
+                    });
+                } else {
+                    fault.next_attempt =
+                        now().saturating_add(self.policy.delay(fault.attempts).as_nanos() as u64);
+                }
+            }
+
+            self.faults[index] = Some(fault);
+        }
+    }
+
+    fn deadline(&self, maindevice: &MainDevice<'_>, now: &impl Fn() -> u64) -> u64 {
+        now().saturating_add(maindevice.timeouts.state_transition.as_nanos() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = BackoffPolicy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            max_attempts: None,
+        };
+
+        let delays = (1..=6).map(|n| policy.delay(n)).collect::<Vec<_>>();
+
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis)
+        );
+
+        assert_eq!(policy.delay(u32::MAX), policy.max_delay);
+    }
+}
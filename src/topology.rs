@@ -0,0 +1,304 @@
+//! The physical layout of SubDevices in an EtherCAT network.
+
+use crate::{
+    SubDevice, SubDeviceGroupHandle, SubDeviceIdentity,
+    error::{Error, Item},
+    subdevice::ports::Topology,
+};
+use core::fmt::Write;
+
+/// A port on a SubDevice in a [`NetworkTopology`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopologyPort {
+    /// The EtherCAT port number, 0 to 3.
+    pub number: u8,
+    /// Whether the port has a link.
+    pub active: bool,
+    /// The index of the SubDevice connected to this port on its way out of the MainDevice, if
+    /// any.
+    pub downstream_to: Option<u16>,
+}
+
+/// A SubDevice and its connections in a [`NetworkTopology`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TopologyNode {
+    /// The SubDevice's position in the network, starting from zero.
+    pub index: u16,
+    /// The SubDevice's configured station address.
+    pub configured_address: u16,
+    /// The SubDevice's name.
+    pub name: heapless::String<64>,
+    /// The SubDevice's identity.
+    pub identity: SubDeviceIdentity,
+    /// The kind of node the SubDevice forms in the topology tree, based on its number of open
+    /// ports.
+    pub kind: Topology,
+    /// The index of the SubDevice this one is connected to on the MainDevice side, or `None` for
+    /// the first SubDevice in the network.
+    pub parent: Option<u16>,
+    /// The number of the port on the parent SubDevice this SubDevice is connected to.
+    pub parent_port: Option<u8>,
+    /// The number of the port that first receives traffic from the MainDevice.
+    pub entry_port: u8,
+    /// The SubDevice's ports in EtherCAT processing order: 0, 3, 1, 2.
+    pub ports: [TopologyPort; 4],
+    /// The time taken for a frame to travel from the first SubDevice to this SubDevice in
+    /// nanoseconds.
+    ///
+    /// This is only measured if the SubDevice supports Distributed Clocks, and is zero otherwise.
+    pub propagation_delay: u32,
+}
+
+impl TopologyNode {
+    pub(crate) fn new(subdevice: &SubDevice) -> Self {
+        let ports = subdevice.ports.0.map(|port| TopologyPort {
+            number: port.number,
+            active: port.active,
+            downstream_to: port.downstream_to.map(|index| index.get()),
+        });
+
+        Self {
+            index: subdevice.index,
+            configured_address: subdevice.configured_address(),
+            name: subdevice.name.clone(),
+            identity: subdevice.identity(),
+            kind: subdevice.ports.topology(),
+            parent: subdevice.parent_index,
+            parent_port: None,
+            entry_port: subdevice.ports.entry_port().number,
+            ports,
+            propagation_delay: subdevice.propagation_delay(),
+        }
+    }
+}
+
+/// The tree of SubDevices in an EtherCAT network, as discovered by
+/// [`MainDevice::init`](crate::MainDevice::init).
+///
+/// Use this to see which junction port each SubDevice is connected to when diagnosing wiring
+/// problems. The topology can be rendered as a [Graphviz](https://graphviz.org/) graph with
+/// [`write_dot`](NetworkTopology::write_dot), or serialised with the `serde` feature.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ethercrab::{
+///     MainDevice, MainDeviceConfig, NetworkTopology, PduStorage, Timeouts, error::Error,
+///     std::ethercat_now,
+/// };
+///
+/// static PDU_STORAGE: PduStorage<16, 1100> = PduStorage::new();
+///
+/// # async fn case() -> Result<(), Error> {
+/// let (_tx, _rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+///
+/// let maindevice = MainDevice::new(pdu_loop, Timeouts::default(), MainDeviceConfig::default());
+///
+/// let group = maindevice.init_single_group::<16, 64>(ethercat_now).await?;
+///
+/// let topology = NetworkTopology::<16>::from_groups(&[&group])?;
+///
+/// let mut dot = String::new();
+///
+/// topology.write_dot(&mut dot).expect("write DOT");
+///
+/// std::fs::write("network.dot", dot).expect("write file");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NetworkTopology<const MAX_SUBDEVICES: usize> {
+    subdevices: heapless::Vec<TopologyNode, MAX_SUBDEVICES>,
+}
+
+impl<const MAX_SUBDEVICES: usize> NetworkTopology<MAX_SUBDEVICES> {
+    /// Build the topology of the SubDevices in the given groups.
+    ///
+    /// Every SubDevice in the network should be in one of the groups, otherwise the tree will
+    /// have gaps.
+    pub fn from_groups(groups: &[&dyn SubDeviceGroupHandle]) -> Result<Self, Error> {
+        let mut subdevices = heapless::Vec::new();
+
+        for group in groups {
+            for subdevice in group.subdevices() {
+                subdevices
+                    .push(TopologyNode::new(subdevice))
+                    .map_err(|_| Error::Capacity(Item::SubDevice))?;
+            }
+        }
+
+        Ok(Self::new(subdevices))
+    }
+
+    pub(crate) fn new(mut subdevices: heapless::Vec<TopologyNode, MAX_SUBDEVICES>) -> Self {
+        subdevices.sort_unstable_by_key(|node| node.index);
+
+        for i in 0..subdevices.len() {
+            let index = subdevices[i].index;
+
+            let parent_port = subdevices[i].parent.and_then(|parent| {
+                subdevices
+                    .iter()
+                    .find(|node| node.index == parent)?
+                    .ports
+                    .iter()
+                    .find(|port| port.downstream_to == Some(index))
+                    .map(|port| port.number)
+            });
+
+            subdevices[i].parent_port = parent_port;
+        }
+
+        Self { subdevices }
+    }
+
+    /// All SubDevices in network order.
+    pub fn subdevices(&self) -> &[TopologyNode] {
+        &self.subdevices
+    }
+
+    /// Get a SubDevice by its position in the network.
+    pub fn subdevice(&self, index: u16) -> Option<&TopologyNode> {
+        self.subdevices.iter().find(|node| node.index == index)
+    }
+
+    /// The SubDevices directly connected to the SubDevice at `index` on the side away from the
+    /// MainDevice.
+    pub fn children(&self, index: u16) -> impl Iterator<Item = &TopologyNode> {
+        self.subdevices
+            .iter()
+            .filter(move |node| node.parent == Some(index))
+    }
+
+    /// Write the topology as a [Graphviz](https://graphviz.org/) DOT graph.
+    ///
+    /// Each edge is labelled with the port numbers at each end, and each SubDevice with its
+    /// configured address and propagation delay.
+    pub fn write_dot(&self, f: &mut impl Write) -> core::fmt::Result {
+        f.write_str("digraph ethercat {\n")?;
+        f.write_str("    node [shape=box];\n")?;
+        f.write_str("    maindevice [label=\"MainDevice\", shape=ellipse];\n")?;
+
+        for node in self.subdevices.iter() {
+            write!(f, "    subdevice{} [label=\"#{} ", node.index, node.index)?;
+
+            for c in node.name.chars() {
+                if matches!(c, '"' | '\\') {
+                    f.write_char('\\')?;
+                }
+
+                f.write_char(c)?;
+            }
+
+            writeln!(
+                f,
+                "\\n{:#06x}\\n{} ns\"];",
+                node.configured_address, node.propagation_delay
+            )?;
+        }
+
+        for node in self.subdevices.iter() {
+            match (node.parent, node.parent_port) {
+                (Some(parent), Some(parent_port)) => writeln!(
+                    f,
+                    "    subdevice{} -> subdevice{} [taillabel=\"{}\", headlabel=\"{}\"];",
+                    parent, node.index, parent_port, node.entry_port
+                )?,
+                (Some(parent), None) => writeln!(
+                    f,
+                    "    subdevice{} -> subdevice{} [headlabel=\"{}\"];",
+                    parent, node.index, node.entry_port
+                )?,
+                (None, _) => writeln!(
+                    f,
+                    "    maindevice -> subdevice{} [headlabel=\"{}\"];",
+                    node.index, node.entry_port
+                )?,
+            }
+        }
+
+        f.write_str("}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subdevice::ports::tests::make_ports;
+    use core::num::NonZeroU16;
+
+    #[test]
+    fn fork_dot() {
+        let mut ek1100 = SubDevice {
+            index: 0,
+            configured_address: 0x1000,
+            name: "EK1100".try_into().unwrap(),
+            ports: make_ports(true, true, true, false),
+            ..SubDevice::default()
+        };
+
+        ek1100.ports.0[1].downstream_to = NonZeroU16::new(1);
+        ek1100.ports.0[2].downstream_to = NonZeroU16::new(2);
+
+        let el2004 = SubDevice {
+            index: 1,
+            configured_address: 0x1001,
+            name: "EL2004".try_into().unwrap(),
+            ports: make_ports(true, false, false, false),
+            parent_index: Some(0),
+            propagation_delay: 140,
+            ..SubDevice::default()
+        };
+
+        let lan9252 = SubDevice {
+            index: 2,
+            configured_address: 0x1002,
+            name: "LAN\"9252\"".try_into().unwrap(),
+            ports: make_ports(true, false, false, false),
+            parent_index: Some(0),
+            propagation_delay: 330,
+            ..SubDevice::default()
+        };
+
+        let topology = NetworkTopology::<4>::new(
+            [&lan9252, &ek1100, &el2004]
+                .into_iter()
+                .map(TopologyNode::new)
+                .collect(),
+        );
+
+        assert_eq!(topology.subdevice(1).unwrap().parent_port, Some(3));
+        assert_eq!(topology.subdevice(2).unwrap().parent_port, Some(1));
+        assert_eq!(topology.subdevice(0).unwrap().kind, Topology::Fork);
+        assert_eq!(
+            topology
+                .children(0)
+                .map(|node| node.index)
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        let mut dot = String::new();
+
+        topology.write_dot(&mut dot).unwrap();
+
+        assert_eq!(
+            dot,
+            r##"digraph ethercat {
+    node [shape=box];
+    maindevice [label="MainDevice", shape=ellipse];
+    subdevice0 [label="#0 EK1100\n0x1000\n0 ns"];
+    subdevice1 [label="#1 EL2004\n0x1001\n140 ns"];
+    subdevice2 [label="#2 LAN\"9252\"\n0x1002\n330 ns"];
+    maindevice -> subdevice0 [headlabel="0"];
+    subdevice0 -> subdevice1 [taillabel="3", headlabel="0"];
+    subdevice0 -> subdevice2 [taillabel="1", headlabel="0"];
+}
+"##
+        );
+    }
+}
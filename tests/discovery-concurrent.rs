@@ -0,0 +1,84 @@
+//! Discover SubDevices concurrently with one SubDevice failing to read its EEPROM.
+
+mod sim;
+
+use ethercrab::{
+    InitStage, MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, SubDeviceIdentity,
+    error::{Error, TimeoutError},
+};
+use sim::{Network, SimSubDevice};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn concurrent_discovery_keeps_network_order() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut subdevices = (1..=6)
+        .map(|serial| {
+            SimSubDevice::new(
+                SubDeviceIdentity {
+                    vendor_id: 0x0000_0002,
+                    product_id: 0x0b1c_3052,
+                    revision: 0x0010_0000,
+                    serial,
+                },
+                0,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Times out, so SubDevices after it finish discovery first
+    subdevices[2].eeprom_broken = true;
+
+    sim::spawn_tx_rx(Network::new(subdevices), tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        sim::timeouts(),
+        MainDeviceConfig {
+            discovery_concurrency: 4,
+            ..MainDeviceConfig::default()
+        },
+    );
+
+    let (group, report) = maindevice
+        .init_tolerant::<MAX_SUBDEVICES, _>(
+            || 0,
+            SubDeviceGroup::<MAX_SUBDEVICES, PDI_LEN>::default(),
+            |group, _subdevice| Ok(group),
+        )
+        .await
+        .expect("Init");
+
+    let discovered = group
+        .iter(&maindevice)
+        .map(|subdevice| (subdevice.configured_address(), subdevice.identity().serial))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        discovered,
+        [
+            (0x1000, 1),
+            (0x1001, 2),
+            (0x1003, 4),
+            (0x1004, 5),
+            (0x1005, 6)
+        ]
+    );
+
+    let [failed] = report.quarantined() else {
+        panic!("expected one quarantined SubDevice, got {:?}", report);
+    };
+
+    assert_eq!(failed.index, 2);
+    assert_eq!(failed.configured_address, 0x1002);
+    assert_eq!(failed.stage, InitStage::Discovery);
+    assert_eq!(failed.error, Error::Timeout(TimeoutError::Eeprom));
+}
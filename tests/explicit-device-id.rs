@@ -0,0 +1,89 @@
+//! Address SubDevices by Explicit Device ID, quarantining a SubDevice whose ID can't be read.
+
+mod sim;
+
+use ethercrab::{
+    AddressingStrategy, InitStage, MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup,
+    SubDeviceIdentity,
+    error::Error,
+    sii::{SiiGeneralCategory, SiiImageBuilder},
+};
+use sim::{Network, SimSubDevice};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const INIT: u16 = 0x01;
+
+fn subdevice(serial: u32, explicit_device_id: u16) -> SimSubDevice {
+    let builder = SiiImageBuilder::new(SubDeviceIdentity {
+        vendor_id: 0x0000_0002,
+        product_id: 0x0b1c_3052,
+        revision: 0x0010_0000,
+        serial,
+    })
+    .with_general(SiiGeneralCategory {
+        // ID is read through AL status
+        flags: 0x08,
+        ..SiiGeneralCategory::default()
+    });
+
+    let mut subdevice = SimSubDevice::from_builder(builder);
+
+    subdevice.explicit_device_id = Some(explicit_device_id);
+
+    subdevice
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn quarantine_unreadable_explicit_device_id() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut broken = subdevice(3, 0x0300);
+
+    // Invalid requested state change
+    broken.refuse = Some((INIT, 0x0011));
+
+    let network = Network::new(vec![subdevice(1, 0x0100), subdevice(2, 0x0200), broken]);
+
+    sim::spawn_tx_rx(network, tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        sim::timeouts(),
+        MainDeviceConfig {
+            addressing: AddressingStrategy::ExplicitDeviceId,
+            ..MainDeviceConfig::default()
+        },
+    );
+
+    let (group, report) = maindevice
+        .init_tolerant::<MAX_SUBDEVICES, _>(
+            || 0,
+            SubDeviceGroup::<MAX_SUBDEVICES, PDI_LEN>::default(),
+            |group, _subdevice| Ok(group),
+        )
+        .await
+        .expect("Init");
+
+    let addresses = group
+        .iter(&maindevice)
+        .map(|subdevice| subdevice.configured_address())
+        .collect::<Vec<_>>();
+
+    assert_eq!(addresses, [0x0100, 0x0200]);
+
+    let [failed] = report.quarantined() else {
+        panic!("expected one quarantined SubDevice, got {:?}", report);
+    };
+
+    assert_eq!(failed.index, 2);
+    assert_eq!(failed.configured_address, 0x1002);
+    assert_eq!(failed.stage, InitStage::Discovery);
+    assert!(matches!(failed.error, Error::SubDevice(_)));
+}
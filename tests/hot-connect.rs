@@ -0,0 +1,187 @@
+//! Address hot-connect segment SubDevices with the configured addressing strategy.
+
+mod sim;
+
+use ethercrab::{
+    AddressingStrategy, HotConnectEvent, HotConnectGroup, MainDevice, MainDeviceConfig, PduStorage,
+    SubDeviceIdentity, error::Error,
+};
+use sim::{Network, SimSubDevice};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const SEGMENT_ALIAS: u16 = 0x0100;
+
+fn subdevice(serial: u32, alias: u16) -> SimSubDevice {
+    SimSubDevice::new(
+        SubDeviceIdentity {
+            vendor_id: 0x0000_0002,
+            product_id: 0x0b1c_3052,
+            revision: 0x0010_0000,
+            serial,
+        },
+        alias,
+    )
+}
+
+fn maindevice(pdu_loop: ethercrab::PduLoop<'static>) -> MainDevice<'static> {
+    MainDevice::new(
+        pdu_loop,
+        sim::timeouts(),
+        MainDeviceConfig {
+            addressing: AddressingStrategy::Alias,
+            ..MainDeviceConfig::default()
+        },
+    )
+    .with_hot_connect(&[SEGMENT_ALIAS])
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn segment_uses_addressing_strategy() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut subdevices = vec![
+        subdevice(1, 0x0010),
+        subdevice(2, 0),
+        subdevice(3, SEGMENT_ALIAS),
+        subdevice(4, 0),
+        subdevice(5, 0x0050),
+    ];
+
+    for segment in &mut subdevices[2..] {
+        segment.present = false;
+    }
+
+    let network = Network::new(subdevices);
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = maindevice(pdu_loop);
+
+    let _group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init")
+        .into_op(&maindevice)
+        .await
+        .expect("OP");
+
+    let mut segment = HotConnectGroup::<8, 32>::new(SEGMENT_ALIAS);
+
+    assert_eq!(
+        segment.poll(&maindevice).await,
+        Ok(HotConnectEvent::Unchanged)
+    );
+
+    for segment in &mut network.lock().unwrap().subdevices[2..] {
+        segment.present = true;
+    }
+
+    assert_eq!(
+        segment.poll(&maindevice).await,
+        Ok(HotConnectEvent::Connected)
+    );
+
+    let addresses = segment
+        .group()
+        .expect("connected")
+        .iter(&maindevice)
+        .map(|subdevice| subdevice.configured_address())
+        .collect::<Vec<_>>();
+
+    // SubDevices without an alias fall back to their position
+    assert_eq!(addresses, [SEGMENT_ALIAS, 0x1003, 0x0050]);
+
+    let addresses = network
+        .lock()
+        .unwrap()
+        .subdevices
+        .iter()
+        .map(|subdevice| subdevice.configured_address())
+        .collect::<Vec<_>>();
+
+    assert_eq!(addresses, [0x0010, 0x1001, SEGMENT_ALIAS, 0x1003, 0x0050]);
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn duplicate_segment_address_fails_init() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(vec![
+        subdevice(1, 0x0050),
+        subdevice(2, SEGMENT_ALIAS),
+        subdevice(3, 0x0050),
+    ]);
+
+    sim::spawn_tx_rx(network, tx, rx);
+
+    let maindevice = maindevice(pdu_loop);
+
+    let res = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await;
+
+    assert!(
+        matches!(
+            res,
+            Err(Error::DuplicateAddress {
+                configured_address: 0x0050
+            })
+        ),
+        "{:?}",
+        res.err()
+    );
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn duplicate_segment_address_fails_connect() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut subdevices = vec![
+        subdevice(1, 0x0050),
+        subdevice(2, SEGMENT_ALIAS),
+        subdevice(3, 0x0050),
+    ];
+
+    for segment in &mut subdevices[1..] {
+        segment.present = false;
+    }
+
+    let network = Network::new(subdevices);
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = maindevice(pdu_loop);
+
+    let _group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init");
+
+    for segment in &mut network.lock().unwrap().subdevices[1..] {
+        segment.present = true;
+    }
+
+    let mut segment = HotConnectGroup::<8, 32>::new(SEGMENT_ALIAS);
+
+    assert_eq!(
+        segment.poll(&maindevice).await,
+        Err(Error::DuplicateAddress {
+            configured_address: 0x0050
+        })
+    );
+
+    assert!(!segment.is_connected());
+}
@@ -0,0 +1,98 @@
+//! Tolerant init with SubDevices that refuse state transitions.
+
+mod sim;
+
+use ethercrab::{
+    InitStage, MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, SubDeviceIdentity,
+    Timeouts,
+    error::{Error, TimeoutError},
+};
+use sim::{Network, SimSubDevice};
+use std::time::Duration;
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const PRE_OP: u16 = 0x02;
+const SAFE_OP: u16 = 0x04;
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn tolerance_ends_at_pre_op() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut subdevices = (1..=4)
+        .map(|serial| {
+            SimSubDevice::new(
+                SubDeviceIdentity {
+                    vendor_id: 0x0000_0002,
+                    product_id: 0x0b1c_3052,
+                    revision: 0x0010_0000,
+                    serial,
+                },
+                0,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Invalid requested state change
+    subdevices[1].refuse = Some((PRE_OP, 0x0011));
+    // Invalid output configuration
+    subdevices[3].refuse = Some((SAFE_OP, 0x001d));
+
+    let network = Network::new(subdevices);
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        Timeouts {
+            state_transition: Duration::from_millis(500),
+            ..sim::timeouts()
+        },
+        MainDeviceConfig::default(),
+    );
+
+    let (group, report) = maindevice
+        .init_tolerant::<MAX_SUBDEVICES, _>(
+            || 0,
+            SubDeviceGroup::<MAX_SUBDEVICES, PDI_LEN>::default(),
+            |group, _subdevice| Ok(group),
+        )
+        .await
+        .expect("Init");
+
+    let addresses = group
+        .iter(&maindevice)
+        .map(|subdevice| subdevice.configured_address())
+        .collect::<Vec<_>>();
+
+    assert_eq!(addresses, [0x1000, 0x1002, 0x1003]);
+
+    let [failed] = report.quarantined() else {
+        panic!("expected one quarantined SubDevice, got {:?}", report);
+    };
+
+    assert_eq!(failed.configured_address, 0x1001);
+    assert_eq!(failed.stage, InitStage::Mailbox);
+    assert_eq!(failed.error, Error::Timeout(TimeoutError::StateTransition));
+
+    {
+        let network = network.lock().unwrap();
+
+        let states = network
+            .subdevices
+            .iter()
+            .map(|subdevice| subdevice.state())
+            .collect::<Vec<_>>();
+
+        assert_eq!(states, [PRE_OP, 0x01, PRE_OP, PRE_OP]);
+    }
+
+    // Refusing a later transition fails the whole group
+    assert!(group.into_safe_op(&maindevice).await.is_err());
+}
@@ -0,0 +1,188 @@
+//! Recover SubDevices that lost power or dropped out of OP while the network was in OP.
+
+mod sim;
+
+use ethercrab::{
+    MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, SubDeviceIdentity, Timeouts,
+    error::Error,
+};
+use sim::{Network, SimSubDevice};
+use std::time::Duration;
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const PRE_OP: u16 = 0x02;
+const SAFE_OP: u16 = 0x04;
+const OP: u16 = 0x08;
+
+const IDENTITY: SubDeviceIdentity = SubDeviceIdentity {
+    vendor_id: 0x0000_0002,
+    product_id: 0x0b1c_3052,
+    revision: 0x0010_0000,
+    serial: 0,
+};
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn recover_power_cycled_subdevice() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(
+        (1..=3)
+            .map(|serial| SimSubDevice::new(SubDeviceIdentity { serial, ..IDENTITY }, 0))
+            .collect(),
+    );
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(pdu_loop, sim::timeouts(), MainDeviceConfig::default());
+
+    let mut group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init")
+        .into_op(&maindevice)
+        .await
+        .expect("OP");
+
+    // Nothing to do on a healthy network
+    assert_eq!(maindevice.recover(&mut [&mut group]).await, Ok(0));
+
+    network.lock().unwrap().subdevices[1].power_cycle();
+
+    assert_eq!(maindevice.recover(&mut [&mut group]).await, Ok(1));
+
+    let network = network.lock().unwrap();
+
+    let restored = &network.subdevices[1];
+
+    assert_eq!(restored.configured_address(), 0x1001);
+    assert_eq!(restored.state(), OP);
+    assert!(!restored.has_error());
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn recover_faulted_subdevice_with_process_data() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(
+        (1..=3)
+            .map(|serial| SimSubDevice::with_process_data(SubDeviceIdentity { serial, ..IDENTITY }))
+            .collect(),
+    );
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(pdu_loop, sim::timeouts(), MainDeviceConfig::default());
+
+    let mut group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init")
+        .into_op(&maindevice)
+        .await
+        .expect("OP");
+
+    let fmmus = network.lock().unwrap().subdevices[1].fmmus().to_vec();
+
+    // Watchdog timeout. The SubDevice keeps its configuration.
+    network.lock().unwrap().subdevices[1].fault(SAFE_OP, 0x001b);
+
+    assert_eq!(maindevice.recover(&mut [&mut group]).await, Ok(1));
+
+    {
+        let network = network.lock().unwrap();
+
+        let restored = &network.subdevices[1];
+
+        assert_eq!(restored.state(), OP);
+        assert!(!restored.has_error());
+        assert_eq!(
+            restored.fmmus(),
+            fmmus,
+            "FMMUs must be configured as before"
+        );
+    }
+
+    group
+        .subdevice(&maindevice, 1)
+        .expect("SubDevice")
+        .outputs_raw_mut()
+        .copy_from_slice(&[0xaa, 0x55]);
+
+    group.tx_rx(&maindevice).await.expect("TX/RX");
+
+    let network = network.lock().unwrap();
+
+    let outputs = network
+        .subdevices
+        .iter()
+        .map(|subdevice| subdevice.process_data(sim::OUTPUTS, 2).to_vec())
+        .collect::<Vec<_>>();
+
+    assert_eq!(outputs, [[0, 0], [0xaa, 0x55], [0, 0]]);
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn unmanaged_subdevice_does_not_take_healthy_record() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    // Same identity and serial, so SubDevices can only be told apart by position
+    let mut subdevices = vec![SimSubDevice::new(IDENTITY, 0); 3];
+
+    // Invalid requested state change
+    subdevices[1].refuse = Some((PRE_OP, 0x0011));
+
+    let network = Network::new(subdevices);
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        Timeouts {
+            state_transition: Duration::from_millis(500),
+            ..sim::timeouts()
+        },
+        MainDeviceConfig::default(),
+    );
+
+    let (group, report) = maindevice
+        .init_tolerant::<MAX_SUBDEVICES, _>(
+            || 0,
+            SubDeviceGroup::<MAX_SUBDEVICES, PDI_LEN>::default(),
+            |group, _subdevice| Ok(group),
+        )
+        .await
+        .expect("Init");
+
+    assert_eq!(report.quarantined().len(), 1);
+
+    let mut group = group.into_op(&maindevice).await.expect("OP");
+
+    assert_eq!(
+        maindevice.recover(&mut [&mut group]).await,
+        Err(Error::UnknownSubDevice)
+    );
+
+    let network = network.lock().unwrap();
+
+    let healthy = [&network.subdevices[0], &network.subdevices[2]]
+        .map(|subdevice| (subdevice.configured_address(), subdevice.state()));
+
+    assert_eq!(healthy, [(0x1000, OP), (0x1002, OP)]);
+    assert!(!matches!(
+        network.subdevices[1].configured_address(),
+        0x1000 | 0x1002
+    ));
+}
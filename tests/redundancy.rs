@@ -0,0 +1,100 @@
+//! Exchange process data over a redundant ring before and after a cable break.
+
+mod sim;
+
+use ethercrab::{
+    Command, MainDevice, MainDeviceConfig, PduStorage, RegisterAddress, SubDeviceIdentity,
+    SubDeviceState, error::Error,
+};
+use sim::{Network, SimSubDevice};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn broken_ring() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(
+        (1..=4)
+            .map(|serial| {
+                SimSubDevice::with_process_data(SubDeviceIdentity {
+                    vendor_id: 0x0000_0002,
+                    product_id: 0x0b1c_3052,
+                    revision: 0x0010_0000,
+                    serial,
+                })
+            })
+            .collect(),
+    );
+
+    sim::spawn_tx_rx_redundant(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(pdu_loop, sim::timeouts(), MainDeviceConfig::default());
+
+    let group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init")
+        .into_op(&maindevice)
+        .await
+        .expect("OP");
+
+    // Between the second and third SubDevice
+    network.lock().unwrap().ring_break = Some(2);
+
+    for (i, subdevice) in group.iter(&maindevice).enumerate() {
+        subdevice
+            .outputs_raw_mut()
+            .copy_from_slice(&[0xa0 + i as u8, i as u8]);
+    }
+
+    for (i, subdevice) in network.lock().unwrap().subdevices.iter_mut().enumerate() {
+        subdevice.set_process_data(sim::INPUTS, &[0xb0 + i as u8, i as u8]);
+    }
+
+    group.tx_rx(&maindevice).await.expect("TX/RX");
+
+    let inputs = group
+        .iter(&maindevice)
+        .map(|subdevice| subdevice.inputs_raw().to_vec())
+        .collect::<Vec<_>>();
+
+    assert_eq!(inputs, [[0xb0, 0], [0xb1, 1], [0xb2, 2], [0xb3, 3]]);
+
+    let outputs = network
+        .lock()
+        .unwrap()
+        .subdevices
+        .iter()
+        .map(|subdevice| subdevice.process_data(sim::OUTPUTS, 2).to_vec())
+        .collect::<Vec<_>>();
+
+    assert_eq!(outputs, [[0xa0, 0], [0xa1, 1], [0xa2, 2], [0xa3, 3]]);
+
+    // Broadcast reads must see every SubDevice
+    assert_eq!(maindevice.wait_for_state(SubDeviceState::Op).await, Ok(()));
+
+    // Position addressed PDUs only reach SubDevices before the break
+    assert_eq!(
+        Command::aprd(1, RegisterAddress::ConfiguredStationAddress.into())
+            .receive::<u16>(&maindevice)
+            .await,
+        Ok(0x1001)
+    );
+
+    assert_eq!(
+        Command::aprd(3, RegisterAddress::ConfiguredStationAddress.into())
+            .receive::<u16>(&maindevice)
+            .await,
+        Err(Error::WorkingCounter {
+            expected: 1,
+            received: 0
+        })
+    );
+}
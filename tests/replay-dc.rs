@@ -10,7 +10,7 @@ mod util;
 
 use env_logger::Env;
 use ethercrab::{
-    DcSync, MainDevice, MainDeviceConfig, PduStorage, RegisterAddress, Timeouts, TxRxResponse,
+    DcSync, MainDevice, MainDeviceConfig, PduStorage, RegisterAddress, TxRxResponse,
     error::Error,
     subdevice_group::{CycleInfo, DcConfiguration},
 };
@@ -34,7 +34,7 @@ async fn replay_dc() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             ..Default::default()
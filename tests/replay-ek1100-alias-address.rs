@@ -7,7 +7,7 @@
 mod util;
 
 use env_logger::Env;
-use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, Timeouts, error::Error};
+use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, error::Error};
 use std::{path::PathBuf, time::Duration};
 use tokio::time::sleep;
 
@@ -27,7 +27,7 @@ async fn replay_ek1100_alias_address() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             ..Default::default()
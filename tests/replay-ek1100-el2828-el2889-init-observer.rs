@@ -0,0 +1,109 @@
+//! Check the events emitted by an init observer against the `replay-ek1100-el2828-el2889` capture.
+//!
+//! The observer doesn't change what is sent on the network, so this test shares its capture with
+//! `replay-ek1100-el2828-el2889` and only replays init.
+//!
+//! Required hardware:
+//!
+//! - EK1100
+//! - EL2828
+//! - EL2889
+
+mod util;
+
+use env_logger::Env;
+use ethercrab::{
+    InitEvent, MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, SubDeviceState,
+    error::Error,
+};
+use std::sync::Mutex;
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 128;
+
+#[derive(Default)]
+struct Groups {
+    slow_outputs: SubDeviceGroup<2, 2>,
+    fast_outputs: SubDeviceGroup<1, 1>,
+}
+
+/// Init events, formatted for comparison.
+static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn observer(event: InitEvent<'_>) {
+    let event = match event {
+        InitEvent::SubDevicesCounted { count } => format!("count {count}"),
+        InitEvent::SubDeviceDiscovered { subdevice, .. } => {
+            format!(
+                "discovered {:#06x} {}",
+                subdevice.configured_address(),
+                subdevice.name()
+            )
+        }
+        InitEvent::DcConfigured { reference } => format!("dc {reference:x?}"),
+        InitEvent::PdiAllocated { start_address, len } => format!("pdi {start_address} {len}"),
+        InitEvent::StateChanged {
+            configured_address,
+            state: SubDeviceState::PreOp,
+        } => format!("preop {configured_address:#06x}"),
+        InitEvent::Finished { .. } => "finished".to_string(),
+        other => format!("{other:?}"),
+    };
+
+    EVENTS.lock().unwrap().push(event);
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn replay_ek1100_el2828_el2889_init_observer() -> Result<(), Error> {
+    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
+
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        util::timeouts(),
+        MainDeviceConfig {
+            dc_static_sync_iterations: 100,
+            ..Default::default()
+        },
+    )
+    .with_init_observer(&observer);
+
+    util::spawn_tx_rx("tests/replay-ek1100-el2828-el2889.pcapng", tx, rx);
+
+    maindevice
+        .init::<MAX_SUBDEVICES, _>(
+            || 0,
+            Groups::default(),
+            |groups: &Groups, subdevice| match subdevice.name() {
+                "EL2889" | "EK1100" => Ok(&groups.slow_outputs),
+                "EL2828" => Ok(&groups.fast_outputs),
+                _ => Err(Error::UnknownSubDevice),
+            },
+        )
+        .await
+        .expect("Init");
+
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            "count 3",
+            "discovered 0x1000 EK1100",
+            "discovered 0x1001 EL2828",
+            "discovered 0x1002 EL2889",
+            "dc Some(1000)",
+            "preop 0x1001",
+            "pdi 0 1",
+            "preop 0x1000",
+            "preop 0x1002",
+            "pdi 1 2",
+            "finished",
+        ]
+    );
+
+    Ok(())
+}
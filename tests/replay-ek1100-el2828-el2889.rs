@@ -9,7 +9,7 @@
 mod util;
 
 use env_logger::Env;
-use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, Timeouts, error::Error};
+use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, SubDeviceGroup, error::Error};
 use std::{path::PathBuf, time::Duration};
 use tokio::time::MissedTickBehavior;
 
@@ -38,7 +38,7 @@ async fn replay_ek1100_el2828_el2889() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             ..Default::default()
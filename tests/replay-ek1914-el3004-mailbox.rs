@@ -6,7 +6,7 @@
 mod util;
 
 use env_logger::Env;
-use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, Timeouts, error::Error};
+use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, error::Error};
 use std::path::PathBuf;
 
 const MAX_SUBDEVICES: usize = 16;
@@ -25,7 +25,7 @@ async fn replay_ek1914_el3004_mailbox() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             ..Default::default()
@@ -25,11 +25,12 @@ async fn replay_ek1914_no_complete_access() -> Result<(), Error> {
         pdu_loop,
         Timeouts {
             wait_loop_delay: Duration::from_millis(5),
-            ..Timeouts::default()
+            ..util::timeouts()
         },
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             retry_behaviour: RetryBehaviour::None,
+            ..Default::default()
         },
     );
 
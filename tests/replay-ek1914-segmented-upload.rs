@@ -5,7 +5,7 @@
 mod util;
 
 use env_logger::Env;
-use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, Timeouts, error::Error};
+use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, error::Error};
 use std::path::PathBuf;
 
 const MAX_SUBDEVICES: usize = 16;
@@ -24,7 +24,7 @@ async fn replay_ek1914_segmented_upload() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 100,
             ..Default::default()
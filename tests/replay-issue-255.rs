@@ -8,7 +8,7 @@
 mod util;
 
 use env_logger::Env;
-use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, Timeouts, error::Error};
+use ethercrab::{MainDevice, MainDeviceConfig, PduStorage, error::Error};
 use std::{hint::black_box, path::PathBuf, time::Duration};
 use tokio::time::MissedTickBehavior;
 
@@ -28,7 +28,7 @@ async fn replay_issue_255() -> Result<(), Error> {
 
     let maindevice = MainDevice::new(
         pdu_loop,
-        Timeouts::default(),
+        util::timeouts(),
         MainDeviceConfig {
             dc_static_sync_iterations: 0,
             ..Default::default()
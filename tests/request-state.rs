@@ -0,0 +1,84 @@
+//! Request states for single SubDevices and check how failures are reported.
+
+mod sim;
+
+use ethercrab::{
+    AlStatusCode, MainDevice, MainDeviceConfig, PduStorage, SubDeviceIdentity, SubDeviceState,
+    Timeouts,
+    error::{Error, TimeoutError},
+};
+use sim::{Network, SimSubDevice};
+use std::time::Duration;
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const SAFE_OP: u16 = 0x04;
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn request_state_timeout_reports_al_status_code() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let mut subdevices = (1..=3)
+        .map(|serial| {
+            SimSubDevice::new(
+                SubDeviceIdentity {
+                    vendor_id: 0x0000_0002,
+                    product_id: 0x0b1c_3052,
+                    revision: 0x0010_0000,
+                    serial,
+                },
+                0,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Never reaches SAFE-OP, but reports why
+    subdevices[1].stall = Some((SAFE_OP, 0x0011));
+    // Never reaches SAFE-OP and doesn't say why
+    subdevices[2].stall = Some((SAFE_OP, 0x0000));
+
+    let network = Network::new(subdevices);
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        Timeouts {
+            state_transition: Duration::from_millis(500),
+            ..sim::timeouts()
+        },
+        MainDeviceConfig::default(),
+    );
+
+    let group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init");
+
+    let healthy = group.subdevice(&maindevice, 0).expect("SubDevice 0");
+    let stalled = group.subdevice(&maindevice, 1).expect("SubDevice 1");
+    let silent = group.subdevice(&maindevice, 2).expect("SubDevice 2");
+
+    assert_eq!(healthy.request_state(SubDeviceState::SafeOp).await, Ok(()));
+
+    assert_eq!(
+        stalled.request_state(SubDeviceState::SafeOp).await,
+        Err(Error::SubDevice(AlStatusCode::InvalidRequestedStateChange))
+    );
+
+    assert_eq!(
+        silent.request_state(SubDeviceState::SafeOp).await,
+        Err(Error::Timeout(TimeoutError::StateTransition))
+    );
+
+    let network = network.lock().unwrap();
+
+    assert_eq!(network.subdevices[0].state(), SAFE_OP);
+    assert_ne!(network.subdevices[1].state(), SAFE_OP);
+}
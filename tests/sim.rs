@@ -0,0 +1,638 @@
+//! A simulated EtherCAT network for tests that need behaviour no capture covers, e.g. failing
+//! SubDevices or hot-connected segments.
+//!
+//! Each SubDevice has its own register memory and an SII EEPROM image. Only the registers and
+//! side effects EtherCrab relies on during init and state changes are emulated.
+
+use ethercrab::{
+    PduRx, PduTx, ReceiveAction, RedundantPort, SubDeviceIdentity, Timeouts,
+    error::Error,
+    sii::{
+        FmmuUsage, PdoFlags, SiiConfigArea, SiiImageBuilder, SiiPdo, SiiPdoEntry, SiiSyncManager,
+        SyncManagerEnable, SyncManagerType,
+    },
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+    time::Duration,
+};
+
+const ETHERNET_HEADER_LEN: usize = 14;
+const ECAT_HEADER_LEN: usize = 2;
+const PDU_HEADER_LEN: usize = 10;
+
+const REG_CONFIGURED_ADDRESS: usize = 0x0010;
+const REG_ALIAS: usize = 0x0012;
+const REG_DL_STATUS: usize = 0x0110;
+const REG_AL_CONTROL: usize = 0x0120;
+const REG_AL_STATUS: usize = 0x0130;
+const REG_AL_STATUS_CODE: usize = 0x0134;
+const REG_SII_CONTROL: usize = 0x0502;
+const REG_SII_ADDRESS: usize = 0x0504;
+const REG_SII_DATA: usize = 0x0508;
+const REG_FMMU: usize = 0x0600;
+
+/// Start of the output process data of SubDevices created with
+/// [`SimSubDevice::with_process_data`].
+#[allow(unused)]
+pub const OUTPUTS: usize = 0x1000;
+/// Start of the input process data of SubDevices created with
+/// [`SimSubDevice::with_process_data`].
+#[allow(unused)]
+pub const INPUTS: usize = 0x1100;
+
+const AL_ERROR: u16 = 0x10;
+const AL_ID_REQUEST: u16 = 0x20;
+
+/// One simulated SubDevice.
+#[derive(Debug, Clone)]
+#[allow(unused)]
+pub struct SimSubDevice {
+    registers: Vec<u8>,
+    eeprom: Vec<u8>,
+    /// Whether the SubDevice is connected. Disconnected SubDevices don't process frames.
+    pub present: bool,
+    /// Whether EEPROM reads never complete.
+    pub eeprom_broken: bool,
+    /// Refuse a state transition into the given AL state with the given AL status code.
+    pub refuse: Option<(u16, u16)>,
+    /// Ignore requests into the given AL state, leaving the AL status code set to the given
+    /// value. An error acknowledged with the request is still cleared.
+    pub stall: Option<(u16, u16)>,
+    /// Explicit Device ID returned when requested through AL control.
+    pub explicit_device_id: Option<u16>,
+}
+
+#[allow(unused)]
+impl SimSubDevice {
+    /// Create a SubDevice with the given identity and station alias, without process data or
+    /// mailboxes.
+    pub fn new(identity: SubDeviceIdentity, alias: u16) -> Self {
+        Self::from_builder(
+            SiiImageBuilder::new(identity).with_config_area(SiiConfigArea {
+                station_alias: alias,
+                ..SiiConfigArea::default()
+            }),
+        )
+    }
+
+    /// Create a SubDevice with one 16 bit output at [`OUTPUTS`] and one 16 bit input at [`INPUTS`],
+    /// without mailboxes.
+    pub fn with_process_data(identity: SubDeviceIdentity) -> Self {
+        let entry = |index| SiiPdoEntry {
+            index,
+            sub_index: 1,
+            // UNSIGNED16
+            data_type: 0x06,
+            bit_len: 16,
+            ..SiiPdoEntry::default()
+        };
+
+        let outputs = [entry(0x7000)];
+        let inputs = [entry(0x6000)];
+
+        let pdo = |index, sync_manager, entries| SiiPdo {
+            index,
+            sync_manager,
+            dc_sync: 0,
+            name_string_idx: 0,
+            flags: PdoFlags::PDO_MANDATORY | PdoFlags::PDO_DEFAULT,
+            entries,
+        };
+
+        let rx_pdos = [pdo(0x1600, 0, &outputs[..])];
+        let tx_pdos = [pdo(0x1a00, 1, &inputs[..])];
+
+        let sync_managers = [
+            SiiSyncManager {
+                start_addr: OUTPUTS as u16,
+                length: 2,
+                control: 0x64,
+                enable: SyncManagerEnable::ENABLE,
+                usage_type: SyncManagerType::ProcessDataWrite,
+            },
+            SiiSyncManager {
+                start_addr: INPUTS as u16,
+                length: 2,
+                control: 0x20,
+                enable: SyncManagerEnable::ENABLE,
+                usage_type: SyncManagerType::ProcessDataRead,
+            },
+        ];
+
+        Self::from_builder(
+            SiiImageBuilder::new(identity)
+                .with_fmmus(&[FmmuUsage::Outputs, FmmuUsage::Inputs])
+                .with_sync_managers(&sync_managers)
+                .with_rx_pdos(&rx_pdos)
+                .with_tx_pdos(&tx_pdos),
+        )
+    }
+
+    /// Create a SubDevice with the EEPROM image produced by `builder`.
+    pub fn from_builder(builder: SiiImageBuilder<'_>) -> Self {
+        let mut eeprom = vec![0xffu8; 2048];
+
+        let len = builder.build(&mut eeprom).expect("build SII image");
+
+        eeprom.truncate(len);
+
+        let mut subdevice = Self {
+            // Registers followed by 4KiB of process data RAM
+            registers: vec![0u8; 0x2000],
+            eeprom,
+            present: true,
+            eeprom_broken: false,
+            refuse: None,
+            stall: None,
+            explicit_device_id: None,
+        };
+
+        subdevice.power_cycle();
+
+        subdevice
+    }
+
+    /// Reset all registers to their power-on values, as if the SubDevice lost power.
+    pub fn power_cycle(&mut self) {
+        self.registers.fill(0);
+
+        let alias = [self.eeprom[8], self.eeprom[9]];
+
+        self.registers[REG_ALIAS..REG_ALIAS + 2].copy_from_slice(&alias);
+        self.set_u16(REG_AL_STATUS, 0x01);
+        // EEPROM reads return 8 bytes at a time
+        self.registers[REG_SII_CONTROL] = 0x40;
+    }
+
+    /// The current AL state, without the error flag.
+    pub fn state(&self) -> u16 {
+        self.u16(REG_AL_STATUS) & 0x0f
+    }
+
+    /// Whether the AL status error flag is set.
+    pub fn has_error(&self) -> bool {
+        self.u16(REG_AL_STATUS) & AL_ERROR != 0
+    }
+
+    /// The configured station address.
+    pub fn configured_address(&self) -> u16 {
+        self.u16(REG_CONFIGURED_ADDRESS)
+    }
+
+    /// The raw configuration of all 16 FMMUs.
+    pub fn fmmus(&self) -> &[u8] {
+        &self.registers[REG_FMMU..REG_FMMU + 16 * 16]
+    }
+
+    /// Process data RAM or registers starting at `address`.
+    pub fn process_data(&self, address: usize, len: usize) -> &[u8] {
+        &self.registers[address..address + len]
+    }
+
+    /// Set process data RAM or registers starting at `address`, e.g. to provide inputs.
+    pub fn set_process_data(&mut self, address: usize, data: &[u8]) {
+        self.registers[address..address + data.len()].copy_from_slice(data);
+    }
+
+    /// Drop the SubDevice into a lower state with an error, as it would after e.g. a watchdog
+    /// timeout.
+    pub fn fault(&mut self, state: u16, code: u16) {
+        self.set_u16(REG_AL_STATUS, state | AL_ERROR);
+        self.set_u16(REG_AL_STATUS_CODE, code);
+    }
+
+    fn u16(&self, register: usize) -> u16 {
+        u16::from_le_bytes([self.registers[register], self.registers[register + 1]])
+    }
+
+    fn set_u16(&mut self, register: usize, value: u16) {
+        self.registers[register..register + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn read(&self, register: usize, data: &mut [u8]) {
+        let end = (register + data.len()).min(self.registers.len());
+
+        if register < end {
+            data[..end - register].copy_from_slice(&self.registers[register..end]);
+        }
+    }
+
+    fn write(&mut self, register: usize, data: &[u8]) {
+        let end = (register + data.len()).min(self.registers.len());
+
+        if register >= end {
+            return;
+        }
+
+        // Read-only: the EEPROM read size is fixed by the hardware
+        let read_size = self.registers[REG_SII_CONTROL] & 0x40;
+
+        self.registers[register..end].copy_from_slice(&data[..end - register]);
+
+        self.registers[REG_SII_CONTROL] = (self.registers[REG_SII_CONTROL] & !0x40) | read_size;
+
+        let touches = |reg: usize| register <= reg && reg < end;
+
+        if touches(REG_AL_CONTROL) {
+            self.al_control();
+        }
+
+        if touches(REG_SII_CONTROL + 1) {
+            self.sii_command();
+        }
+    }
+
+    fn al_control(&mut self) {
+        let control = self.u16(REG_AL_CONTROL);
+        let requested = control & 0x0f;
+        let acknowledge = control & AL_ERROR != 0;
+        let current = self.u16(REG_AL_STATUS);
+
+        // An error must be acknowledged before the SubDevice will change state
+        if current & AL_ERROR != 0 && !acknowledge {
+            return;
+        }
+
+        if let Some((_, code)) = self.stall.filter(|(state, _)| *state == requested) {
+            self.set_u16(REG_AL_STATUS, current & !AL_ERROR);
+            self.set_u16(REG_AL_STATUS_CODE, code);
+
+            return;
+        }
+
+        if let Some((_, code)) = self.refuse.filter(|(state, _)| *state == requested) {
+            self.set_u16(REG_AL_STATUS, (current & 0x0f) | AL_ERROR);
+            self.set_u16(REG_AL_STATUS_CODE, code);
+
+            return;
+        }
+
+        match self
+            .explicit_device_id
+            .filter(|_| control & AL_ID_REQUEST != 0)
+        {
+            Some(id) => {
+                self.set_u16(REG_AL_STATUS, requested | AL_ID_REQUEST);
+                self.set_u16(REG_AL_STATUS_CODE, id);
+            }
+            None => {
+                self.set_u16(REG_AL_STATUS, requested);
+                self.set_u16(REG_AL_STATUS_CODE, 0);
+            }
+        }
+    }
+
+    fn sii_command(&mut self) {
+        let command = self.registers[REG_SII_CONTROL + 1];
+
+        // Clear error flags, busy and the command itself
+        self.registers[REG_SII_CONTROL + 1] = 0;
+
+        // Read
+        if command & 0x01 == 0 {
+            return;
+        }
+
+        if self.eeprom_broken {
+            // Busy
+            self.registers[REG_SII_CONTROL + 1] = 0x80;
+
+            return;
+        }
+
+        let word = usize::from(self.u16(REG_SII_ADDRESS));
+
+        for i in 0..8 {
+            self.registers[REG_SII_DATA + i] =
+                self.eeprom.get(word * 2 + i).copied().unwrap_or(0xff);
+        }
+    }
+
+    /// Read or write process data through the SubDevice's FMMUs.
+    ///
+    /// Returns the working counter increment.
+    fn logical(&mut self, command: u8, address: u32, data: &mut [u8]) -> u16 {
+        let mut wkc = 0;
+
+        for fmmu in 0..16 {
+            let base = REG_FMMU + fmmu * 16;
+            let config = &self.registers[base..base + 16];
+
+            let logical_start = u32::from_le_bytes(config[0..4].try_into().unwrap());
+            let len = u32::from(u16::from_le_bytes([config[4], config[5]]));
+            let physical_start = usize::from(u16::from_le_bytes([config[8], config[9]]));
+            let is_read = config[11] & 0x01 != 0;
+            let is_write = config[11] & 0x02 != 0;
+            let enabled = config[12] & 0x01 != 0;
+
+            if !enabled || len == 0 {
+                continue;
+            }
+
+            let start = logical_start.max(address);
+            let end = (logical_start + len).min(address + data.len() as u32);
+
+            if start >= end {
+                continue;
+            }
+
+            let frame_range = (start - address) as usize..(end - address) as usize;
+            let physical = physical_start + (start - logical_start) as usize;
+
+            // LRD = 10, LWR = 11, LRW = 12
+            if is_read && matches!(command, 10 | 12) {
+                let mut buf = vec![0u8; frame_range.len()];
+
+                self.read(physical, &mut buf);
+
+                data[frame_range.clone()].copy_from_slice(&buf);
+
+                wkc |= 1;
+            }
+
+            if is_write && matches!(command, 11 | 12) {
+                self.write(physical, &data[frame_range]);
+
+                wkc |= 2;
+            }
+        }
+
+        if command == 12 { wkc } else { wkc.min(1) }
+    }
+}
+
+/// A line of simulated SubDevices.
+#[derive(Debug, Default)]
+pub struct Network {
+    /// SubDevices in network order.
+    pub subdevices: Vec<SimSubDevice>,
+    /// Break a redundant ring after this many connected SubDevices.
+    pub ring_break: Option<usize>,
+}
+
+#[allow(unused)]
+impl Network {
+    /// Create a network with the given SubDevices in network order.
+    pub fn new(subdevices: Vec<SimSubDevice>) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self {
+            subdevices,
+            ring_break: None,
+        }))
+    }
+
+    /// Process every PDU in an EtherCAT frame, as it would be by passing through each SubDevice
+    /// in turn.
+    fn process(&mut self, frame: &mut [u8]) {
+        self.update_dl_status();
+
+        let mut subdevices = self.connected().collect::<Vec<_>>();
+
+        Self::process_through(&mut subdevices, frame);
+    }
+
+    /// Process a frame sent on `port` of a redundant ring, returning the port it is received on.
+    ///
+    /// While the ring is intact, a frame sent from the main interface passes through every
+    /// SubDevice to the redundant interface, and a frame sent from the redundant interface passes
+    /// through the ring unprocessed. If the ring is broken, each frame is processed by the
+    /// SubDevices on its side of the break, in the order it reaches them, and looped back.
+    fn process_redundant(&mut self, port: RedundantPort, frame: &mut [u8]) -> RedundantPort {
+        self.update_dl_status();
+
+        let ring_break = self.ring_break;
+
+        let mut subdevices = self.connected().collect::<Vec<_>>();
+
+        match (ring_break, port) {
+            (None, RedundantPort::Main) => {
+                Self::process_through(&mut subdevices, frame);
+
+                RedundantPort::Redundant
+            }
+            (None, RedundantPort::Redundant) => RedundantPort::Main,
+            (Some(at), RedundantPort::Main) => {
+                Self::process_through(&mut subdevices[..at], frame);
+
+                RedundantPort::Main
+            }
+            (Some(at), RedundantPort::Redundant) => {
+                let mut after = subdevices.split_off(at);
+
+                after.reverse();
+
+                Self::process_through(&mut after, frame);
+
+                RedundantPort::Redundant
+            }
+        }
+    }
+
+    /// SubDevices that are connected to the network, in network order.
+    fn connected(&mut self) -> impl Iterator<Item = &mut SimSubDevice> {
+        self.subdevices
+            .iter_mut()
+            .filter(|subdevice| subdevice.present)
+    }
+
+    /// Process every PDU in `frame` by each of `subdevices` in turn.
+    fn process_through(subdevices: &mut [&mut SimSubDevice], frame: &mut [u8]) {
+        if subdevices.is_empty() {
+            return;
+        }
+
+        // The first SubDevice sets the locally administered bit of the source address
+        frame[6] |= 0x02;
+
+        let mut pos = ETHERNET_HEADER_LEN + ECAT_HEADER_LEN;
+
+        loop {
+            let header = &frame[pos..pos + PDU_HEADER_LEN];
+
+            let command = header[0];
+            let adp = u16::from_le_bytes([header[2], header[3]]);
+            let ado = usize::from(u16::from_le_bytes([header[4], header[5]]));
+            let logical_address = u32::from_le_bytes(header[2..6].try_into().unwrap());
+            let flags = u16::from_le_bytes([header[6], header[7]]);
+            let len = usize::from(flags & 0x07ff);
+            let more_follows = flags & 0x8000 != 0;
+
+            let data_start = pos + PDU_HEADER_LEN;
+            let data_end = data_start + len;
+
+            let mut wkc = 0u16;
+
+            let (_, rest) = frame.split_at_mut(data_start);
+            let (data, after) = rest.split_at_mut(len);
+
+            for (position, subdevice) in subdevices.iter_mut().enumerate() {
+                let addressed = match command {
+                    // APRD, APWR, APRW
+                    1..=3 => adp.wrapping_add(position as u16) == 0,
+                    // FPRD, FPWR, FPRW
+                    4..=6 => subdevice.configured_address() == adp,
+                    // BRD, BWR, BRW
+                    7..=9 => true,
+                    // LRD, LWR, LRW
+                    10..=12 => {
+                        wkc += subdevice.logical(command, logical_address, data);
+
+                        continue;
+                    }
+                    _ => false,
+                };
+
+                if !addressed {
+                    continue;
+                }
+
+                match command {
+                    // Reads
+                    1 | 4 => subdevice.read(ado, data),
+                    // Writes
+                    2 | 5 | 8 => subdevice.write(ado, data),
+                    // Broadcast reads OR the register contents into the frame
+                    7 => {
+                        let mut buf = vec![0u8; data.len()];
+
+                        subdevice.read(ado, &mut buf);
+
+                        data.iter_mut().zip(buf).for_each(|(d, b)| *d |= b);
+                    }
+                    _ => unimplemented!("command {}", command),
+                }
+
+                wkc += 1;
+            }
+
+            after[0..2].copy_from_slice(&wkc.to_le_bytes());
+
+            pos = data_end + 2;
+
+            if !more_follows {
+                break;
+            }
+        }
+    }
+
+    /// Set each SubDevice's link and loopback flags to describe a line topology.
+    fn update_dl_status(&mut self) {
+        let count = self.subdevices.iter().filter(|s| s.present).count();
+
+        for (position, subdevice) in self
+            .subdevices
+            .iter_mut()
+            .filter(|subdevice| subdevice.present)
+            .enumerate()
+        {
+            // Port 0 link and signal, ports 2 and 3 closed
+            let mut status = 0x0010 | 0x0200 | 0x1000 | 0x4000;
+
+            if position + 1 < count {
+                // Port 1 link and signal
+                status |= 0x0020 | 0x0800;
+            } else {
+                // Port 1 closed
+                status |= 0x0400;
+            }
+
+            subdevice.set_u16(REG_DL_STATUS, status);
+        }
+    }
+}
+
+struct SimTxRxFut {
+    network: Arc<Mutex<Network>>,
+    tx: PduTx<'static>,
+    rx: PduRx<'static>,
+}
+
+impl Future for SimTxRxFut {
+    type Output = Result<ReceiveAction, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        self.tx.replace_waker(ctx.waker());
+
+        while let Some(frame) = self.tx.next_sendable_frame() {
+            let mut buf = Vec::new();
+
+            frame
+                .send_blocking(|bytes| {
+                    buf.extend_from_slice(bytes);
+
+                    Ok(bytes.len())
+                })
+                .expect("Failed to send");
+
+            self.network.lock().unwrap().process(&mut buf);
+
+            while self.rx.receive_frame(&buf).is_err() {}
+        }
+
+        Poll::Pending
+    }
+}
+
+struct SimTxRxRedundantFut {
+    network: Arc<Mutex<Network>>,
+    tx: PduTx<'static>,
+    rx: PduRx<'static>,
+}
+
+impl Future for SimTxRxRedundantFut {
+    type Output = Result<ReceiveAction, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        self.tx.replace_waker(ctx.waker());
+
+        while let Some(frame) = self.tx.next_sendable_frame() {
+            let mut copies = Vec::new();
+
+            frame
+                .send_blocking_redundant(|port, bytes| {
+                    copies.push((port, bytes.to_vec()));
+
+                    Ok(bytes.len())
+                })
+                .expect("Failed to send");
+
+            for (port, mut buf) in copies {
+                let received_on = self
+                    .network
+                    .lock()
+                    .unwrap()
+                    .process_redundant(port, &mut buf);
+
+                while self.rx.receive_frame_redundant(&buf, received_on).is_err() {}
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Spawn a TX/RX task that passes frames through `network` as a redundant ring, sending every
+/// frame from both ends. See [`Network::ring_break`] to break the ring.
+#[allow(unused)]
+pub fn spawn_tx_rx_redundant(network: Arc<Mutex<Network>>, tx: PduTx<'static>, rx: PduRx<'static>) {
+    tokio::spawn(SimTxRxRedundantFut { network, tx, rx });
+}
+
+/// Spawn a TX/RX task that passes frames through `network`.
+#[allow(unused)]
+pub fn spawn_tx_rx(network: Arc<Mutex<Network>>, tx: PduTx<'static>, rx: PduRx<'static>) {
+    tokio::spawn(SimTxRxFut { network, tx, rx });
+}
+
+/// Timeouts long enough that tests don't fail when the machine is under load.
+#[allow(unused)]
+pub fn timeouts() -> Timeouts {
+    Timeouts {
+        pdu: Duration::from_millis(1000),
+        eeprom: Duration::from_millis(250),
+        ..Timeouts::default()
+    }
+}
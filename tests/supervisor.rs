@@ -0,0 +1,252 @@
+//! Bring a SubDevice that dropped out of OP back with a `FaultSupervisor`.
+
+mod sim;
+
+use ethercrab::{
+    AlStatusCode, BackoffPolicy, DefaultLock, FaultSupervisor, MainDevice, MainDeviceConfig,
+    PduStorage, SubDeviceGroup, SubDeviceIdentity, SubDeviceState, SupervisorEvent, Timeouts,
+    error::{Error, TimeoutError},
+    subdevice_group::{NoDc, Op},
+};
+use sim::{Network, SimSubDevice};
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+const PRE_OP: u16 = 0x02;
+const SAFE_OP: u16 = 0x04;
+const OP: u16 = 0x08;
+
+const MS: u64 = 1_000_000;
+
+/// Simulated time in nanoseconds, advanced by the test.
+static NOW: AtomicU64 = AtomicU64::new(0);
+
+static EVENTS: Mutex<Vec<SupervisorEvent>> = Mutex::new(Vec::new());
+
+fn now() -> u64 {
+    NOW.load(Ordering::Relaxed)
+}
+
+fn advance(ms: u64) {
+    NOW.fetch_add(ms * MS, Ordering::Relaxed);
+}
+
+fn on_event(event: SupervisorEvent) {
+    EVENTS.lock().unwrap().push(event);
+}
+
+/// Run one process data cycle and return the events it produced.
+async fn cycle(
+    maindevice: &MainDevice<'_>,
+    group: &SubDeviceGroup<MAX_SUBDEVICES, PDI_LEN, DefaultLock, Op, NoDc>,
+    supervisor: &mut FaultSupervisor<MAX_SUBDEVICES>,
+) -> Vec<SupervisorEvent> {
+    let response = group.tx_rx(maindevice).await.expect("TX/RX");
+
+    supervisor.check(maindevice, group, &response, now).await;
+
+    core::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn retry_until_max_attempts_then_clear_on_op() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(
+        (1..=3)
+            .map(|serial| {
+                SimSubDevice::with_process_data(SubDeviceIdentity {
+                    vendor_id: 0x0000_0002,
+                    product_id: 0x0b1c_3052,
+                    revision: 0x0010_0000,
+                    serial,
+                })
+            })
+            .collect(),
+    );
+
+    sim::spawn_tx_rx(network.clone(), tx, rx);
+
+    let maindevice = MainDevice::new(
+        pdu_loop,
+        Timeouts {
+            state_transition: Duration::from_millis(500),
+            ..sim::timeouts()
+        },
+        MainDeviceConfig::default(),
+    );
+
+    let group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init")
+        .into_op(&maindevice)
+        .await
+        .expect("OP");
+
+    let mut supervisor = FaultSupervisor::<MAX_SUBDEVICES>::new(BackoffPolicy {
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(100),
+        max_attempts: Some(2),
+    })
+    .with_observer(&on_event);
+
+    assert_eq!(cycle(&maindevice, &group, &mut supervisor).await, []);
+
+    {
+        let mut network = network.lock().unwrap();
+
+        let subdevice = &mut network.subdevices[1];
+
+        // Watchdog timeout, and the SubDevice won't go back to OP
+        subdevice.fault(PRE_OP, 0x001b);
+        subdevice.stall = Some((OP, 0x001b));
+    }
+
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::Fault {
+            configured_address: 0x1001,
+            state: SubDeviceState::PreOp,
+            code: AlStatusCode::SyncManagerWatchdog,
+        }]
+    );
+    assert!(supervisor.has_faults());
+
+    // Acknowledge and request SAFE-OP
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::RecoveryAttempt {
+            configured_address: 0x1001,
+            attempt: 1,
+        }]
+    );
+    assert!(!network.lock().unwrap().subdevices[1].has_error());
+
+    // SAFE-OP reached, so OP is requested, which stalls
+    assert_eq!(cycle(&maindevice, &group, &mut supervisor).await, []);
+    assert_eq!(network.lock().unwrap().subdevices[1].state(), SAFE_OP);
+
+    // Still within the state transition timeout
+    advance(400);
+
+    assert_eq!(cycle(&maindevice, &group, &mut supervisor).await, []);
+
+    advance(200);
+
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::RecoveryFailed {
+            configured_address: 0x1001,
+            attempt: 1,
+            error: Error::Timeout(TimeoutError::StateTransition),
+        }]
+    );
+
+    // Waiting for the backoff delay
+    advance(50);
+
+    assert_eq!(cycle(&maindevice, &group, &mut supervisor).await, []);
+
+    advance(50);
+
+    // Already in SAFE-OP, so OP is requested straight away
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::RecoveryAttempt {
+            configured_address: 0x1001,
+            attempt: 2,
+        }]
+    );
+
+    advance(600);
+
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [
+            SupervisorEvent::RecoveryFailed {
+                configured_address: 0x1001,
+                attempt: 2,
+                error: Error::Timeout(TimeoutError::StateTransition),
+            },
+            SupervisorEvent::GaveUp {
+                configured_address: 0x1001,
+                attempts: 2,
+            }
+        ]
+    );
+
+    // No more attempts after giving up
+    advance(10_000);
+
+    assert_eq!(cycle(&maindevice, &group, &mut supervisor).await, []);
+    assert!(supervisor.has_faults());
+
+    // Fixed by the application
+    network.lock().unwrap().subdevices[1].stall = None;
+
+    group
+        .subdevice(&maindevice, 1)
+        .expect("SubDevice")
+        .request_state(SubDeviceState::Op)
+        .await
+        .expect("Request OP");
+
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::Recovered {
+            configured_address: 0x1001,
+            attempts: 2,
+        }]
+    );
+    assert!(!supervisor.has_faults());
+
+    // A new fault starts counting attempts from the beginning
+    network.lock().unwrap().subdevices[1].fault(SAFE_OP, 0x001b);
+
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::Fault {
+            configured_address: 0x1001,
+            state: SubDeviceState::SafeOp,
+            code: AlStatusCode::SyncManagerWatchdog,
+        }]
+    );
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::RecoveryAttempt {
+            configured_address: 0x1001,
+            attempt: 1,
+        }]
+    );
+    assert_eq!(
+        cycle(&maindevice, &group, &mut supervisor).await,
+        [SupervisorEvent::Recovered {
+            configured_address: 0x1001,
+            attempts: 1,
+        }]
+    );
+    assert!(!supervisor.has_faults());
+
+    let network = network.lock().unwrap();
+
+    assert!(
+        network
+            .subdevices
+            .iter()
+            .all(|subdevice| subdevice.state() == OP && !subdevice.has_error())
+    );
+}
@@ -0,0 +1,100 @@
+//! Serialise the topology of a simulated network with the `serde` feature.
+
+#![cfg(feature = "serde")]
+
+mod sim;
+
+use ethercrab::{MainDevice, MainDeviceConfig, NetworkTopology, PduStorage, SubDeviceIdentity};
+use serde_json::json;
+use sim::{Network, SimSubDevice};
+
+const MAX_SUBDEVICES: usize = 16;
+const MAX_PDU_DATA: usize = PduStorage::element_size(1100);
+const MAX_FRAMES: usize = 16;
+const PDI_LEN: usize = 64;
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn serialize_line_topology() {
+    static PDU_STORAGE: PduStorage<MAX_FRAMES, MAX_PDU_DATA> = PduStorage::new();
+
+    let (tx, rx, pdu_loop) = PDU_STORAGE.try_split().expect("can only split once");
+
+    let network = Network::new(
+        (1..=3)
+            .map(|serial| {
+                SimSubDevice::new(
+                    SubDeviceIdentity {
+                        vendor_id: 0x0000_0002,
+                        product_id: 0x0b1c_3052,
+                        revision: 0x0010_0000,
+                        serial,
+                    },
+                    0,
+                )
+            })
+            .collect(),
+    );
+
+    sim::spawn_tx_rx(network, tx, rx);
+
+    let maindevice = MainDevice::new(pdu_loop, sim::timeouts(), MainDeviceConfig::default());
+
+    let group = maindevice
+        .init_single_group::<MAX_SUBDEVICES, PDI_LEN>(|| 0)
+        .await
+        .expect("Init");
+
+    let topology = NetworkTopology::<MAX_SUBDEVICES>::from_groups(&[&group]).expect("Topology");
+
+    let json = serde_json::to_value(&topology).expect("Serialize");
+
+    let nodes = json["subdevices"].as_array().expect("SubDevice list");
+
+    assert_eq!(nodes.len(), 3);
+
+    let summary = nodes
+        .iter()
+        .map(|node| {
+            json!({
+                "index": node["index"],
+                "configured_address": node["configured_address"],
+                "kind": node["kind"],
+                "parent": node["parent"],
+                "serial": node["identity"]["serial"],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        summary,
+        [
+            json!({
+                "index": 0,
+                "configured_address": 0x1000,
+                "kind": "Passthrough",
+                "parent": null,
+                "serial": 1,
+            }),
+            json!({
+                "index": 1,
+                "configured_address": 0x1001,
+                "kind": "Passthrough",
+                "parent": 0,
+                "serial": 2,
+            }),
+            json!({
+                "index": 2,
+                "configured_address": 0x1002,
+                "kind": "LineEnd",
+                "parent": 1,
+                "serial": 3,
+            }),
+        ]
+    );
+
+    assert_eq!(
+        nodes[1]["ports"][0],
+        json!({ "number": 0, "active": true, "downstream_to": null })
+    );
+}
@@ -1,6 +1,6 @@
 //! Utilities to replay Wireshark captures as part of regression/integration tests.
 
-use ethercrab::{PduRx, PduTx, ReceiveAction, error::Error, std::tx_rx_task};
+use ethercrab::{PduRx, PduTx, ReceiveAction, Timeouts, error::Error, std::tx_rx_task};
 use pcap_file::pcapng::{Block, PcapNgReader};
 use smoltcp::wire::EthernetFrame;
 use std::{
@@ -37,6 +37,18 @@ pub fn spawn_tx_rx(capture_file_path: &str, tx: PduTx<'static>, rx: PduRx<'stati
     };
 }
 
+/// Timeouts long enough that EEPROM reads don't time out when the machine is under load.
+///
+/// Replays respond to every EEPROM busy poll in the order they were captured, so a longer timeout
+/// doesn't change the frames that are sent.
+#[allow(unused)]
+pub fn timeouts() -> Timeouts {
+    Timeouts {
+        eeprom: Duration::from_millis(250),
+        ..Timeouts::default()
+    }
+}
+
 const MAINDEVICE_ADDR: [u8; 6] = [0x10, 0x10, 0x10, 0x10, 0x10, 0x10];
 const REPLY_ADDR: [u8; 6] = [0x12, 0x10, 0x10, 0x10, 0x10, 0x10];
 